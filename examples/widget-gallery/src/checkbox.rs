@@ -1,9 +1,10 @@
 use floem::{
+    event::Event,
     peniko::Color,
-    reactive::{create_signal, SignalUpdate},
+    reactive::{create_rw_signal, SignalUpdate},
     style::Style,
     view::View,
-    views::{checkbox, label, stack, Decorators},
+    views::{label, stack, tri_state_checkbox, CheckState, Decorators},
     ViewContext,
 };
 
@@ -11,35 +12,37 @@ use crate::form::{form, form_item};
 
 pub fn checkbox_view() -> impl View {
     let cx = ViewContext::get_current();
-    let (is_checked, set_is_checked) = create_signal(cx.scope, true);
+    let state = create_rw_signal(cx.scope, CheckState::Checked);
+    let toggle = move |_: &Event| {
+        state.update(|state| {
+            *state = match state {
+                CheckState::Checked => CheckState::Unchecked,
+                CheckState::Unchecked | CheckState::Indeterminate => CheckState::Checked,
+            }
+        });
+        true
+    };
     form(move || {
         (
             form_item("Basic Checkbox:".to_string(), 120.0, move || {
-                checkbox(is_checked)
+                tri_state_checkbox(state)
                     .focus_visible_style(|| Style::BASE.border_color(Color::BLUE).border(2.))
-                    .on_click(move |_| {
-                        set_is_checked.update(|checked| *checked = !*checked);
-                        true
-                    })
             }),
             form_item("Labelled Checkbox:".to_string(), 120.0, move || {
                 stack(|| {
                     (
-                        checkbox(is_checked).focus_visible_style(|| {
+                        tri_state_checkbox(state).focus_visible_style(|| {
                             Style::BASE.border_color(Color::BLUE).border(2.)
                         }),
                         label(|| "Check me!".to_string()),
                     )
                 })
-                .on_click(move |_| {
-                    set_is_checked.update(|checked| *checked = !*checked);
-                    true
-                })
+                .on_click(toggle)
             }),
             form_item("Disabled Checkbox:".to_string(), 120.0, move || {
                 stack(|| {
                     (
-                        checkbox(is_checked).focus_visible_style(|| {
+                        tri_state_checkbox(state).focus_visible_style(|| {
                             Style::BASE.border_color(Color::BLUE).border(2.)
                         }),
                         label(|| "Check me!".to_string()),
@@ -47,10 +50,7 @@ pub fn checkbox_view() -> impl View {
                 })
                 .style(|| Style::BASE.color(Color::GRAY))
                 .disabled(|| true)
-                .on_click(move |_| {
-                    set_is_checked.update(|checked| *checked = !*checked);
-                    true
-                })
+                .on_click(toggle)
             }),
         )
     })
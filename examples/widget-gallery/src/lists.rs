@@ -3,12 +3,12 @@ use floem::{
     event::{Event, EventListener},
     glazier::keyboard_types::Key,
     peniko::Color,
-    reactive::{create_signal, SignalGet, SignalGetUntracked, SignalUpdate},
+    reactive::{create_rw_signal, create_signal, SignalGet, SignalGetUntracked, SignalUpdate},
     style::{CursorStyle, Dimension, JustifyContent, Style},
     view::View,
     views::{
-        checkbox, container, label, scroll, stack, virtual_list, Decorators, VirtualListDirection,
-        VirtualListItemSize,
+        container, label, scroll, stack, tri_state_checkbox, virtual_list, CheckState, Decorators,
+        VirtualListDirection, VirtualListItemSize,
     },
     ViewContext,
 };
@@ -61,14 +61,11 @@ fn enhanced_list() -> impl View {
                     .iter()
                     .position(|it| *it == item)
                     .unwrap();
-                let (is_checked, set_is_checked) = create_signal(cx.scope, true);
+                let state = create_rw_signal(cx.scope, CheckState::Checked);
                 container(move || {
                     stack(move || {
                         (
-                            checkbox(is_checked).on_click(move |_| {
-                                set_is_checked.update(|checked: &mut bool| *checked = !*checked);
-                                true
-                            }),
+                            tri_state_checkbox(state),
                             label(move || item.to_string())
                                 .style(|| Style::BASE.height_px(32.0).font_size(32.0)),
                             container(move || {
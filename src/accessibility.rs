@@ -0,0 +1,47 @@
+//! # Accessibility
+//! What the views report about themselves to assistive technologies, with
+//! [View::accessibility](crate::view::View::accessibility).
+//!
+//! A view describes each part a user can interact with as an [AccessNode], like the thumbs of
+//! a range slider, and returns [ChangeFlags::ACCESSIBILITY](crate::view::ChangeFlags) from
+//! [View::update](crate::view::View::update) when they change.
+
+/// What kind of control an [AccessNode] is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Slider,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Horizontal,
+    Vertical,
+}
+
+/// A part of a view that assistive technologies can read and control
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessNode {
+    pub role: Role,
+    /// A name telling the node apart from the other nodes of the view
+    pub name: Option<String>,
+    pub value: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// How much the value moves at once, if it only takes some values
+    pub step: Option<f64>,
+    pub orientation: Option<Orientation>,
+}
+
+impl AccessNode {
+    pub fn new(role: Role) -> Self {
+        Self {
+            role,
+            name: None,
+            value: None,
+            min: None,
+            max: None,
+            step: None,
+            orientation: None,
+        }
+    }
+}
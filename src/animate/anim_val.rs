@@ -1,22 +1,24 @@
 use vello::peniko::Color;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum AnimValue {
     Float(f64),
+    /// A percentage of the parent's size, from 0 to 100
+    Pct(f64),
     Color(Color),
 }
 
 impl AnimValue {
     pub fn get_f32(self) -> f32 {
         match self {
-            AnimValue::Float(v) => v as f32,
+            AnimValue::Float(v) | AnimValue::Pct(v) => v as f32,
             AnimValue::Color(_) => panic!(),
         }
     }
 
     pub fn get_f64(self) -> f64 {
         match self {
-            AnimValue::Float(v) => v,
+            AnimValue::Float(v) | AnimValue::Pct(v) => v,
             AnimValue::Color(_) => panic!(),
         }
     }
@@ -24,7 +26,7 @@ impl AnimValue {
     pub fn get_color(self) -> Color {
         match self {
             AnimValue::Color(c) => c,
-            AnimValue::Float(_) => panic!(),
+            AnimValue::Float(_) | AnimValue::Pct(_) => panic!(),
         }
     }
 }
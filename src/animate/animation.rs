@@ -1,6 +1,6 @@
 use super::{
-    anim_val::AnimValue, AnimId, AnimPropKind, AnimState, AnimStateKind, AnimatedProp, Easing,
-    EasingFn, EasingMode,
+    anim_val::AnimValue, AnimGroupId, AnimId, AnimPropKind, AnimState, AnimStateKind, AnimatedProp,
+    Clock, Easing, EasingFn, EasingMode,
 };
 use std::{borrow::BorrowMut, collections::HashMap, time::Duration, time::Instant};
 
@@ -17,6 +17,8 @@ pub struct Animation {
     pub(crate) auto_reverse: bool,
     pub(crate) skip: Option<Duration>,
     pub(crate) duration: Duration,
+    pub(crate) delay: Duration,
+    pub(crate) group: Option<AnimGroupId>,
     pub(crate) repeat_mode: RepeatMode,
     pub(crate) repeat_count: usize,
     pub(crate) animated_props: HashMap<AnimPropKind, AnimatedProp>,
@@ -47,6 +49,8 @@ pub fn animation() -> Animation {
         auto_reverse: false,
         skip: None,
         duration: Duration::from_secs(1),
+        delay: Duration::ZERO,
+        group: None,
         repeat_mode: RepeatMode::Times(1),
         repeat_count: 0,
         animated_props: HashMap::new(),
@@ -60,6 +64,12 @@ pub enum AnimUpdateMsg {
         kind: AnimPropKind,
         val: AnimValue,
     },
+    GroupBegin {
+        id: AnimGroupId,
+    },
+    GroupStop {
+        id: AnimGroupId,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -68,6 +78,15 @@ pub enum SizeUnit {
     Pct,
 }
 
+impl SizeUnit {
+    pub(crate) fn value(&self, val: f64) -> AnimValue {
+        match self {
+            SizeUnit::Px => AnimValue::Float(val),
+            SizeUnit::Pct => AnimValue::Pct(val),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum AnimDirection {
     Forward,
@@ -84,6 +103,27 @@ impl Animation {
         self
     }
 
+    /// How long the animation waits after it begins before the first pass starts. The
+    /// animated props hold their starting values while waiting.
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// The group this animation was added to, if any.
+    pub fn group(&self) -> Option<AnimGroupId> {
+        self.group
+    }
+
+    /// How long all passes of the animation take, not counting the delay. An animation that
+    /// loops forever counts as a single pass.
+    pub(crate) fn active_duration(&self) -> Duration {
+        match self.repeat_mode {
+            RepeatMode::LoopForever => self.duration,
+            RepeatMode::Times(times) => self.duration * times.max(1) as u32,
+        }
+    }
+
     pub fn is_idle(&self) -> bool {
         matches!(self.state_kind(), AnimStateKind::Idle)
     }
@@ -176,6 +216,172 @@ impl Animation {
         self
     }
 
+    /// Animates the width as a percentage (0 to 100) of the parent's width
+    pub fn width_pct(self, width_fn: impl Fn() -> f64 + 'static) -> Self {
+        let cx = ViewContext::get_current();
+        create_effect(cx.scope, move |_| {
+            let to_width = width_fn();
+
+            self.id
+                .update_prop(AnimPropKind::Width, AnimValue::Pct(to_width));
+        });
+
+        self
+    }
+
+    /// Animates the height as a percentage (0 to 100) of the parent's height
+    pub fn height_pct(self, height_fn: impl Fn() -> f64 + 'static) -> Self {
+        let cx = ViewContext::get_current();
+        create_effect(cx.scope, move |_| {
+            let height = height_fn();
+
+            self.id
+                .update_prop(AnimPropKind::Height, AnimValue::Pct(height));
+        });
+
+        self
+    }
+
+    pub fn border_left(self, border_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::BorderLeft], border_fn)
+    }
+
+    pub fn border_top(self, border_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::BorderTop], border_fn)
+    }
+
+    pub fn border_right(self, border_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::BorderRight], border_fn)
+    }
+
+    pub fn border_bottom(self, border_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::BorderBottom], border_fn)
+    }
+
+    /// Animates the border width on all sides
+    pub fn border(self, border_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(
+            &[
+                AnimPropKind::BorderLeft,
+                AnimPropKind::BorderTop,
+                AnimPropKind::BorderRight,
+                AnimPropKind::BorderBottom,
+            ],
+            border_fn,
+        )
+    }
+
+    pub fn padding_left(self, padding_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::PaddingLeft], padding_fn)
+    }
+
+    pub fn padding_top(self, padding_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::PaddingTop], padding_fn)
+    }
+
+    pub fn padding_right(self, padding_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::PaddingRight], padding_fn)
+    }
+
+    pub fn padding_bottom(self, padding_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::PaddingBottom], padding_fn)
+    }
+
+    /// Animates the padding on all sides
+    pub fn padding(self, padding_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(
+            &[
+                AnimPropKind::PaddingLeft,
+                AnimPropKind::PaddingTop,
+                AnimPropKind::PaddingRight,
+                AnimPropKind::PaddingBottom,
+            ],
+            padding_fn,
+        )
+    }
+
+    pub fn margin_left(self, margin_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::MarginLeft], margin_fn)
+    }
+
+    pub fn margin_top(self, margin_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::MarginTop], margin_fn)
+    }
+
+    pub fn margin_right(self, margin_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::MarginRight], margin_fn)
+    }
+
+    pub fn margin_bottom(self, margin_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::MarginBottom], margin_fn)
+    }
+
+    /// Animates the margin on all sides
+    pub fn margin(self, margin_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(
+            &[
+                AnimPropKind::MarginLeft,
+                AnimPropKind::MarginTop,
+                AnimPropKind::MarginRight,
+                AnimPropKind::MarginBottom,
+            ],
+            margin_fn,
+        )
+    }
+
+    pub fn inset_left(self, inset_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::InsetLeft], inset_fn)
+    }
+
+    pub fn inset_top(self, inset_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::InsetTop], inset_fn)
+    }
+
+    pub fn inset_right(self, inset_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::InsetRight], inset_fn)
+    }
+
+    pub fn inset_bottom(self, inset_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::InsetBottom], inset_fn)
+    }
+
+    /// Animates the gap between columns
+    pub fn column_gap(self, gap_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::ColumnGap], gap_fn)
+    }
+
+    /// Animates the gap between rows
+    pub fn row_gap(self, gap_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::RowGap], gap_fn)
+    }
+
+    /// Animates the gap between both rows and columns
+    pub fn gap(self, gap_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::ColumnGap, AnimPropKind::RowGap], gap_fn)
+    }
+
+    pub fn font_size(self, font_size_fn: impl Fn() -> f64 + 'static) -> Self {
+        self.float_props(&[AnimPropKind::FontSize], font_size_fn)
+    }
+
+    /// Animates each of `kinds` to the value returned by `val_fn`, tracking its signals.
+    fn float_props(
+        self,
+        kinds: &'static [AnimPropKind],
+        val_fn: impl Fn() -> f64 + 'static,
+    ) -> Self {
+        let cx = ViewContext::get_current();
+        let id = self.id;
+        create_effect(cx.scope, move |_| {
+            let val = val_fn();
+            for kind in kinds {
+                id.update_prop(kind.clone(), AnimValue::Float(val));
+            }
+        });
+
+        self
+    }
+
     pub fn auto_reverse(mut self, auto_rev: bool) -> Self {
         self.auto_reverse = auto_rev;
         self
@@ -220,9 +426,14 @@ impl Animation {
     }
 
     pub fn begin(&mut self) {
+        self.begin_at(Clock::now());
+    }
+
+    /// Begins the animation at `now`, so the first pass starts once the delay has passed.
+    pub(crate) fn begin_at(&mut self, now: Instant) {
         self.repeat_count = 0;
         self.state = AnimState::PassInProgress {
-            started_on: Instant::now(),
+            started_on: now + self.delay,
             elapsed: Duration::ZERO,
         }
     }
@@ -234,7 +445,7 @@ impl Animation {
                 started_on,
                 elapsed,
             } => {
-                let duration = Instant::now() - *started_on;
+                let duration = Clock::elapsed_since(*started_on);
                 let elapsed = *elapsed + duration;
                 self.state = AnimState::Completed {
                     elapsed: Some(elapsed),
@@ -253,33 +464,38 @@ impl Animation {
     }
 
     pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed_at(Clock::now())
+    }
+
+    /// How far into the current pass the animation is at the frame timestamp `now`.
+    pub(crate) fn elapsed_at(&self, now: Instant) -> Option<Duration> {
         match &self.state {
             AnimState::Idle => None,
             AnimState::PassInProgress {
                 started_on,
                 elapsed,
             } => {
-                let duration = Instant::now() - started_on.clone();
+                let duration = now.saturating_duration_since(*started_on);
                 Some(*elapsed + duration)
             }
-            AnimState::PassFinished { elapsed } => Some(elapsed.clone()),
-            AnimState::Completed { elapsed, .. } => elapsed.clone(),
+            AnimState::PassFinished { elapsed } => Some(*elapsed),
+            AnimState::Completed { elapsed, .. } => *elapsed,
         }
     }
 
-    pub fn advance(&mut self) {
+    /// Moves the animation state forward to the frame timestamp `now`.
+    ///
+    /// All animations in a window are advanced with the same `now` so they stay in lockstep.
+    pub fn advance(&mut self, now: Instant) {
         match &mut self.state {
             AnimState::Idle => {
-                self.begin();
+                self.begin_at(now);
             }
             AnimState::PassInProgress {
                 started_on,
-                mut elapsed,
+                elapsed,
             } => {
-                let now = Instant::now();
-                let duration = now - started_on.clone();
-                elapsed += duration;
-
+                let elapsed = *elapsed + now.saturating_duration_since(*started_on);
                 if elapsed >= self.duration {
                     self.state = AnimState::PassFinished { elapsed };
                 }
@@ -287,7 +503,7 @@ impl Animation {
             AnimState::PassFinished { elapsed } => match self.repeat_mode {
                 RepeatMode::LoopForever => {
                     self.state = AnimState::PassInProgress {
-                        started_on: Instant::now(),
+                        started_on: now,
                         elapsed: Duration::ZERO,
                     }
                 }
@@ -299,7 +515,7 @@ impl Animation {
                         }
                     } else {
                         self.state = AnimState::PassInProgress {
-                            started_on: Instant::now(),
+                            started_on: now,
                             elapsed: Duration::ZERO,
                         }
                     }
@@ -309,6 +525,28 @@ impl Animation {
         }
     }
 
+    /// The values of every animated prop at the frame timestamp `now`, or `None` if the
+    /// animation shouldn't be applied to the style any more.
+    pub(crate) fn values_at(&self, now: Instant) -> Option<Vec<(AnimPropKind, AnimValue)>> {
+        if self.is_completed() && self.is_auto_reverse() {
+            return None;
+        }
+
+        let elapsed = self.elapsed_at(now).unwrap_or(Duration::ZERO);
+        Some(
+            self.animated_props
+                .keys()
+                .map(|kind| (kind.clone(), self.animate_prop(elapsed, kind)))
+                .collect(),
+        )
+    }
+
+    /// Whether any of the animated props changes the size of the view, in which case
+    /// a frame needs a layout pass and not only a repaint.
+    pub(crate) fn affects_layout(&self) -> bool {
+        self.animated_props.keys().any(|kind| kind.is_layout_prop())
+    }
+
     pub(crate) fn props(&self) -> &HashMap<AnimPropKind, AnimatedProp> {
         &self.animated_props
     }
@@ -348,3 +586,141 @@ impl Animation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use vello::peniko::Color;
+
+    use crate::{
+        animate::{AnimPropKind, AnimStateKind, AnimValue, AnimatedProp, AnimationGroup, Clock},
+        context::AppState,
+        id::Id,
+        style::Style,
+        view::ChangeFlags,
+    };
+
+    use super::{animation, Animation};
+
+    fn background_animation() -> Animation {
+        let mut anim = animation().duration(Duration::from_secs(1));
+        anim.props_mut().insert(
+            AnimPropKind::Background,
+            AnimatedProp::Background {
+                from: Color::rgb8(0, 0, 0),
+                to: Color::rgb8(200, 100, 0),
+            },
+        );
+        anim
+    }
+
+    #[test]
+    fn manual_clock_steps_animation() {
+        Clock::manual();
+        let mut anim = background_animation();
+        anim.begin();
+        assert_eq!(anim.elapsed(), Some(Duration::ZERO));
+
+        Clock::advance(Duration::from_millis(250));
+        assert_eq!(anim.elapsed(), Some(Duration::from_millis(250)));
+        anim.advance(Clock::now());
+        assert!(anim.is_in_progress());
+
+        Clock::advance(Duration::from_millis(750));
+        anim.advance(Clock::now());
+        assert!(matches!(anim.state_kind(), AnimStateKind::PassFinished));
+        anim.advance(Clock::now());
+        assert!(anim.is_completed());
+    }
+
+    #[test]
+    fn idle_animation_starts_at_frame_time() {
+        Clock::manual();
+        let frame = Clock::now();
+        Clock::advance(Duration::from_millis(100));
+        let mut anim = background_animation();
+        anim.advance(frame);
+        assert_eq!(
+            anim.elapsed_at(frame + Duration::from_millis(100)),
+            Some(Duration::from_millis(100))
+        );
+    }
+
+    #[test]
+    fn animated_style_at_time() {
+        Clock::manual();
+        let mut app_state = AppState::new();
+        let id = Id::next().new();
+        app_state.animated.insert(id);
+        app_state.view_state(id).style = Style::BASE.background(Color::rgb8(0, 0, 0));
+        app_state.view_state(id).animation = Some(background_animation());
+
+        let _ = app_state.tick_animations(Clock::now());
+        app_state.compute_style(id, None);
+        assert_eq!(
+            app_state.get_computed_style(id).background,
+            Some(Color::rgb8(0, 0, 0))
+        );
+
+        Clock::advance(Duration::from_millis(500));
+        let flags = app_state.tick_animations(Clock::now());
+        assert!(flags.contains(ChangeFlags::PAINT));
+        assert!(!flags.contains(ChangeFlags::LAYOUT));
+        assert_eq!(
+            app_state.get_computed_style(id).background,
+            Some(Color::rgb8(100, 50, 0))
+        );
+    }
+
+    #[test]
+    fn delay_holds_start_values() {
+        Clock::manual();
+        let mut anim = background_animation().delay(Duration::from_millis(200));
+        anim.begin();
+
+        Clock::advance(Duration::from_millis(150));
+        anim.advance(Clock::now());
+        assert_eq!(anim.elapsed(), Some(Duration::ZERO));
+        assert_eq!(
+            anim.values_at(Clock::now()),
+            Some(vec![(
+                AnimPropKind::Background,
+                AnimValue::Color(Color::rgb8(0, 0, 0))
+            )])
+        );
+
+        Clock::advance(Duration::from_millis(550));
+        assert_eq!(anim.elapsed(), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn group_offsets() {
+        let anim = || background_animation().delay(Duration::from_millis(100));
+
+        let stagger = AnimationGroup::stagger(Duration::from_millis(50));
+        let delays: Vec<_> = (0..3).map(|i| stagger.add(i, anim()).delay).collect();
+        assert_eq!(delays, [100, 150, 200].map(Duration::from_millis).to_vec());
+        // an item created again at the same index starts as it did the first time
+        assert_eq!(stagger.add(1, anim()).delay, Duration::from_millis(150));
+
+        let sequence = AnimationGroup::sequence();
+        let delays: Vec<_> = (0..3)
+            .map(|i| sequence.add(i, anim().repeat_times(2)).delay)
+            .collect();
+        assert_eq!(
+            delays,
+            [100, 2200, 4300].map(Duration::from_millis).to_vec()
+        );
+        assert_eq!(
+            sequence.add(2, anim().repeat_times(2)).delay,
+            Duration::from_millis(4300)
+        );
+
+        let parallel = AnimationGroup::parallel();
+        let first = parallel.add(0, anim());
+        let second = parallel.add(1, anim());
+        assert_eq!(first.delay, second.delay);
+        assert_eq!(first.group(), Some(parallel.id()));
+    }
+}
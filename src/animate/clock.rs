@@ -0,0 +1,64 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+thread_local! {
+    static CLOCK: Cell<Clock> = Cell::new(Clock::System);
+}
+
+/// The source of time for animations and other time based interactions, like the drag
+/// release animation.
+///
+/// By default the clock follows the system clock. Tests can switch the current thread to a
+/// [`Clock::Manual`] clock which stands still until it's advanced by an exact duration, so
+/// animation progress can be stepped and asserted deterministically.
+///
+/// ```ignore
+/// Clock::manual();
+/// let mut anim = animation().duration(Duration::from_secs(1));
+/// anim.begin();
+/// Clock::advance(Duration::from_millis(250));
+/// assert_eq!(anim.elapsed(), Some(Duration::from_millis(250)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub enum Clock {
+    /// Follows [`Instant::now`].
+    System,
+    /// Frozen at the contained instant until moved by [`Clock::advance`].
+    Manual(Instant),
+}
+
+impl Clock {
+    /// The current time according to the clock of the current thread.
+    pub fn now() -> Instant {
+        match CLOCK.with(|clock| clock.get()) {
+            Clock::System => Instant::now(),
+            Clock::Manual(now) => now,
+        }
+    }
+
+    /// How much time passed since `earlier`, according to the clock of the current thread.
+    pub fn elapsed_since(earlier: Instant) -> Duration {
+        Self::now().saturating_duration_since(earlier)
+    }
+
+    /// Replace the clock of the current thread.
+    pub fn set(clock: Clock) {
+        CLOCK.with(|c| c.set(clock));
+    }
+
+    /// Switch the current thread to a manual clock, frozen at the current time.
+    pub fn manual() {
+        Self::set(Clock::Manual(Self::now()));
+    }
+
+    /// Move a manual clock forward by `duration`. Does nothing when following the system clock.
+    pub fn advance(duration: Duration) {
+        CLOCK.with(|clock| {
+            if let Clock::Manual(now) = clock.get() {
+                clock.set(Clock::Manual(now + duration));
+            }
+        });
+    }
+}
@@ -0,0 +1,128 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use crate::app_handle::ANIM_UPDATE_MESSAGES;
+
+use super::{AnimUpdateMsg, Animation};
+
+static ANIM_GROUP_ID_GEN: AtomicUsize = AtomicUsize::new(1);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AnimGroupId(usize);
+
+impl AnimGroupId {
+    pub fn next() -> Self {
+        AnimGroupId(ANIM_GROUP_ID_GEN.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// How the animations of an [`AnimationGroup`] are laid out in time.
+#[derive(Debug, Clone, Copy)]
+pub enum GroupMode {
+    /// Every animation starts at the same time.
+    Parallel,
+    /// Every animation starts when the previous one has finished all of its passes.
+    Sequence,
+    /// Every animation starts the given delay after the previous one started.
+    Stagger(Duration),
+}
+
+/// Coordinates the timing of several [`Animation`]s, usually attached to different views.
+///
+/// Animations are added with [`AnimationGroup::add`] at their index in the group, usually the
+/// index of their item in a list, and the group offsets each one's delay according to its
+/// index and the [`GroupMode`]. An item that's removed and created again at the same index gets
+/// the same delay. The group is also the shared controller: [`AnimationGroup::begin`] restarts
+/// all of its animations from the same instant and [`AnimationGroup::stop`] stops them.
+///
+/// ```ignore
+/// let group = AnimationGroup::stagger(Duration::from_millis(50));
+/// list(
+///     move || cards.get().into_iter().enumerate(),
+///     |(_, card)| card.id,
+///     move |(index, card)| {
+///         card_view(card).animation(group.add(index, animation().background(|| Color::WHITE)))
+///     },
+/// )
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnimationGroup {
+    id: AnimGroupId,
+    mode: GroupMode,
+    /// How long the animation at each index runs, delay included, which is what the
+    /// animations after it wait for in a sequence
+    lengths: Rc<RefCell<Vec<Duration>>>,
+}
+
+impl AnimationGroup {
+    pub fn new(mode: GroupMode) -> Self {
+        Self {
+            id: AnimGroupId::next(),
+            mode,
+            lengths: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    pub fn parallel() -> Self {
+        Self::new(GroupMode::Parallel)
+    }
+
+    pub fn sequence() -> Self {
+        Self::new(GroupMode::Sequence)
+    }
+
+    pub fn stagger(delay: Duration) -> Self {
+        Self::new(GroupMode::Stagger(delay))
+    }
+
+    pub fn id(&self) -> AnimGroupId {
+        self.id
+    }
+
+    pub fn mode(&self) -> GroupMode {
+        self.mode
+    }
+
+    /// Adds `anim` as the animation at `index` in the group. The returned animation keeps its
+    /// own delay, with the group's offset for `index` added on top. In a sequence, the offset is
+    /// the time the animations at the lower indices take, as far as they were added.
+    pub fn add(&self, index: usize, anim: Animation) -> Animation {
+        let offset = match self.mode {
+            GroupMode::Parallel => Duration::ZERO,
+            GroupMode::Stagger(delay) => delay * index as u32,
+            GroupMode::Sequence => {
+                let mut lengths = self.lengths.borrow_mut();
+                if lengths.len() <= index {
+                    lengths.resize(index + 1, Duration::ZERO);
+                }
+                lengths[index] = anim.delay + anim.active_duration();
+                lengths[..index].iter().sum()
+            }
+        };
+
+        let mut anim = anim;
+        anim.delay += offset;
+        anim.group = Some(self.id);
+        anim
+    }
+
+    /// Restarts every animation of the group, all from the same instant.
+    pub fn begin(&self) {
+        self.send(AnimUpdateMsg::GroupBegin { id: self.id });
+    }
+
+    /// Stops every animation of the group that is still running.
+    pub fn stop(&self) {
+        self.send(AnimUpdateMsg::GroupStop { id: self.id });
+    }
+
+    fn send(&self, msg: AnimUpdateMsg) {
+        ANIM_UPDATE_MESSAGES.with(|msgs| {
+            msgs.borrow_mut().push(msg);
+        });
+    }
+}
@@ -7,6 +7,12 @@ pub use anim_state::*;
 mod animation;
 pub use animation::*;
 
+mod clock;
+pub use clock::*;
+
+mod group;
+pub use group::*;
+
 mod anim_val;
 pub use anim_val::*;
 
@@ -17,15 +17,26 @@ pub enum AnimatedProp {
     BorderWidth { from: f64, to: f64 },
     BorderColor { from: Color, to: Color },
     Color { from: Color, to: Color },
+    Padding { from: f64, to: f64 },
+    Margin { from: f64, to: f64 },
+    Inset { from: f64, to: f64 },
+    Gap { from: f64, to: f64 },
+    FontSize { from: f64, to: f64 },
 }
 
 impl AnimatedProp {
     pub(crate) fn from(&self) -> AnimValue {
         match self {
-            AnimatedProp::Width { from, .. }
-            | AnimatedProp::Height { from, .. }
-            | AnimatedProp::BorderWidth { from, .. }
-            | AnimatedProp::BorderRadius { from, .. } => AnimValue::Float(*from),
+            AnimatedProp::Width { from, unit, .. } | AnimatedProp::Height { from, unit, .. } => {
+                unit.value(*from)
+            }
+            AnimatedProp::BorderWidth { from, .. }
+            | AnimatedProp::BorderRadius { from, .. }
+            | AnimatedProp::Padding { from, .. }
+            | AnimatedProp::Margin { from, .. }
+            | AnimatedProp::Inset { from, .. }
+            | AnimatedProp::Gap { from, .. }
+            | AnimatedProp::FontSize { from, .. } => AnimValue::Float(*from),
             AnimatedProp::Scale { .. } => todo!(),
             AnimatedProp::Background { from, .. }
             | AnimatedProp::BorderColor { from, .. }
@@ -109,7 +120,7 @@ impl AnimatedProp {
     pub(crate) fn animate(&self, time: f64, direction: AnimDirection) -> AnimValue {
         match self {
             AnimatedProp::Width { from, to, unit } | AnimatedProp::Height { from, to, unit } => {
-                AnimValue::Float(self.animate_float(*from, *to, time, direction))
+                unit.value(self.animate_float(*from, *to, time, direction))
             }
             AnimatedProp::Background { from, to }
             | AnimatedProp::BorderColor { from, to }
@@ -117,7 +128,13 @@ impl AnimatedProp {
                 AnimValue::Color(self.animate_color(*from, *to, time, direction))
             }
             AnimatedProp::Scale { .. } => todo!(),
-            AnimatedProp::BorderRadius { from, to } | AnimatedProp::BorderWidth { from, to } => {
+            AnimatedProp::BorderRadius { from, to }
+            | AnimatedProp::BorderWidth { from, to }
+            | AnimatedProp::Padding { from, to }
+            | AnimatedProp::Margin { from, to }
+            | AnimatedProp::Inset { from, to }
+            | AnimatedProp::Gap { from, to }
+            | AnimatedProp::FontSize { from, to } => {
                 AnimValue::Float(self.animate_float(*from, *to, time, direction))
             }
         }
@@ -135,4 +152,59 @@ pub enum AnimPropKind {
     Height,
     BorderRadius,
     BorderColor,
+    BorderLeft,
+    BorderTop,
+    BorderRight,
+    BorderBottom,
+    PaddingLeft,
+    PaddingTop,
+    PaddingRight,
+    PaddingBottom,
+    MarginLeft,
+    MarginTop,
+    MarginRight,
+    MarginBottom,
+    InsetLeft,
+    InsetTop,
+    InsetRight,
+    InsetBottom,
+    ColumnGap,
+    RowGap,
+    FontSize,
+}
+
+impl AnimPropKind {
+    /// Layout props change the size or position of the view, so animating them requires a
+    /// layout pass. Everything else can be applied on top of the existing layout and only
+    /// needs a repaint.
+    pub(crate) fn is_layout_prop(&self) -> bool {
+        match self {
+            AnimPropKind::Scale
+            | AnimPropKind::Background
+            | AnimPropKind::Color
+            | AnimPropKind::BorderRadius
+            | AnimPropKind::BorderColor => false,
+            AnimPropKind::Width
+            | AnimPropKind::Height
+            | AnimPropKind::BorderLeft
+            | AnimPropKind::BorderTop
+            | AnimPropKind::BorderRight
+            | AnimPropKind::BorderBottom
+            | AnimPropKind::PaddingLeft
+            | AnimPropKind::PaddingTop
+            | AnimPropKind::PaddingRight
+            | AnimPropKind::PaddingBottom
+            | AnimPropKind::MarginLeft
+            | AnimPropKind::MarginTop
+            | AnimPropKind::MarginRight
+            | AnimPropKind::MarginBottom
+            | AnimPropKind::InsetLeft
+            | AnimPropKind::InsetTop
+            | AnimPropKind::InsetRight
+            | AnimPropKind::InsetBottom
+            | AnimPropKind::ColumnGap
+            | AnimPropKind::RowGap
+            | AnimPropKind::FontSize => true,
+        }
+    }
 }
@@ -62,11 +62,14 @@ impl Application {
     pub fn window<V: View + 'static>(
         self,
         app_view: impl FnOnce() -> V + 'static,
-        config: Option<WindowConfig>,
+        mut config: Option<WindowConfig>,
     ) -> Self {
         let application = self.application.clone();
         let _ = self.scope.child_scope(move |cx| {
-            let app = AppHandle::new(cx, app_view);
+            let mut app = AppHandle::new(cx, app_view);
+            if let Some(keymap) = config.as_mut().and_then(|c| c.keymap.take()) {
+                app.app_state.keymap.extend(keymap);
+            }
             let mut builder = WindowBuilder::new(application).size(
                 config
                     .as_ref()
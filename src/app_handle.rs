@@ -1,26 +1,43 @@
-use std::time::Duration;
-use std::{any::Any, collections::HashMap};
+use std::{
+    any::Any,
+    collections::{HashMap, HashSet},
+};
 
 use crate::animate::AnimValue;
-use crate::view::{view_debug_tree, view_tab_navigation};
+use crate::view::{
+    view_arrow_navigation, view_debug_tree, view_first_focus_in, view_next_focus,
+    view_tab_navigation,
+};
 use floem_renderer::Renderer;
 use glazier::kurbo::{Affine, Point, Rect, Vec2};
-use glazier::{FileDialogOptions, FileDialogToken, FileInfo, Scale, TimerToken, WinHandler};
+use glazier::{
+    FileDialogOptions, FileDialogToken, FileInfo, PointerId, Scale, TimerToken, WinHandler,
+};
 use leptos_reactive::Scope;
+use taffy::{
+    prelude::Layout,
+    style::{LengthPercentage, LengthPercentageAuto},
+};
 
 use crate::menu::Menu;
 use crate::{
-    animate::{AnimPropKind, AnimUpdateMsg, AnimatedProp, Animation, SizeUnit},
+    animate::{AnimPropKind, AnimUpdateMsg, AnimatedProp, Animation, Clock, SizeUnit},
     context::{
-        AppState, EventCallback, EventCx, LayoutCx, PaintCx, PaintState, ResizeCallback,
-        ResizeListener, UpdateCx, ViewContextStore, VIEW_CONTEXT_STORE,
+        AppState, CaptureCallback, CommandCallback, EventCallback, EventCx, LayoutCx, PaintCx,
+        PaintState, ResizeCallback, ResizeListener, UpdateCx, ViewContextStore, VIEW_CONTEXT_STORE,
     },
-    event::{Event, EventListener},
+    dnd::{DragPayload, DragPreview, DropTarget},
+    event::{Event, EventListener, EventPropagation, ListenerKey},
     ext_event::EXT_EVENT_HANDLER,
+    gesture::{GestureConfig, GestureKind, GestureRecognizer},
     id::{Id, ID_PATHS},
+    keymap::{self, Keymap, KeymapMatch},
+    overlay::WindowRoot,
     responsive::ScreenSize,
-    style::{CursorStyle, Style},
-    view::{ChangeFlags, View},
+    style::{ComputedStyle, CursorStyle, Style, DEFAULT_FONT_SIZE},
+    view::{ChangeFlags, FocusScope, View},
+    view_signals::ViewSignals,
+    views::{scroll_into_view, tooltip::Tooltip},
 };
 
 thread_local! {
@@ -120,10 +137,16 @@ pub enum StyleSelector {
     Disabled,
     Active,
     Dragging,
+    DropAccept,
 }
 
 pub enum UpdateMessage {
     Focus(Id),
+    MoveFocus {
+        from: Id,
+        backwards: bool,
+    },
+    FocusFirstIn(Id),
     Active(Id),
     WindowScale(f64),
     Disabled {
@@ -159,18 +182,90 @@ pub enum UpdateMessage {
     KeyboardNavigable {
         id: Id,
     },
+    TabIndex {
+        id: Id,
+        index: i32,
+    },
+    FocusScope {
+        id: Id,
+        scope: FocusScope,
+    },
+    Signals {
+        id: Id,
+        signals: ViewSignals,
+    },
     Draggable {
         id: Id,
     },
+    DragPayload {
+        id: Id,
+        payload: Box<dyn Fn() -> DragPayload>,
+    },
+    DragPreview {
+        id: Id,
+        preview: DragPreview,
+    },
+    DropTarget {
+        id: Id,
+        target: DropTarget,
+    },
     EventListener {
         id: Id,
         listener: EventListener,
+        key: ListenerKey,
         action: Box<EventCallback>,
     },
+    CaptureListener {
+        id: Id,
+        listener: EventListener,
+        key: ListenerKey,
+        action: Box<CaptureCallback>,
+    },
+    RemoveListener {
+        id: Id,
+        key: ListenerKey,
+    },
+    CapturePointer {
+        id: Id,
+        pointer: PointerId,
+    },
+    ReleasePointer {
+        id: Id,
+        pointer: PointerId,
+    },
     ResizeListener {
         id: Id,
         action: Box<ResizeCallback>,
     },
+    Gesture {
+        id: Id,
+        kind: GestureKind,
+    },
+    GestureConfig {
+        id: Id,
+        config: GestureConfig,
+    },
+    LongPressTimer {
+        id: Id,
+    },
+    Tooltip {
+        id: Id,
+        tooltip: Tooltip,
+    },
+    TooltipDelay {
+        id: Id,
+        delay: std::time::Duration,
+    },
+    KeyContext {
+        id: Id,
+        context: String,
+    },
+    CommandHandler {
+        id: Id,
+        command: String,
+        action: Box<CommandCallback>,
+    },
+    Keymap(Keymap),
     HandleTitleBar(bool),
     SetWindowDelta(Vec2),
     OpenFile {
@@ -189,6 +284,247 @@ pub enum UpdateMessage {
         menu: Menu,
         pos: Point,
     },
+    FileDrag(Event),
+}
+
+/// Sends `FileDragEnter` and `FileDragOver` to the views under the drag while it moves over the
+/// window, `FileDragLeave` to the views it leaves, and `FileDragDrop` to the view it's dropped
+/// on.
+fn dispatch_file_drag<V: View>(view: &mut WindowRoot<V>, app_state: &mut AppState, event: Event) {
+    let drag_event = match event.file_drag_event() {
+        Some(drag_event) => drag_event.clone(),
+        None => return,
+    };
+    let mut cx = EventCx {
+        app_state,
+        propagation: EventPropagation::default(),
+    };
+    let was_dragging_over = std::mem::take(&mut cx.app_state.dragging_over);
+
+    let is_drop = matches!(event, Event::FileDragDrop(_));
+    match event {
+        Event::FileDragEnter(_) | Event::FileDragOver(_) => {
+            cx.app_state.file_dragging = true;
+            // the views under the pointer add themselves to `dragging_over`
+            view.event_main(&mut cx, None, Event::FileDragOver(drag_event.clone()));
+        }
+        Event::FileDragDrop(_) => {
+            view.event_main(&mut cx, None, event);
+            cx.app_state.file_dragging = false;
+        }
+        _ => {
+            cx.app_state.file_dragging = false;
+        }
+    }
+
+    let dragging_over = cx.app_state.dragging_over.clone();
+    for id in was_dragging_over.symmetric_difference(&dragging_over) {
+        if cx
+            .app_state
+            .has_style_for_sel(*id, StyleSelector::DropAccept)
+        {
+            cx.app_state.request_layout(*id);
+        }
+        if is_drop {
+            // the views the files were dropped on aren't left
+            continue;
+        }
+        if dragging_over.contains(id) {
+            if let Some(action) = cx.get_event_listener(*id, &EventListener::FileDragEnter) {
+                action(&Event::FileDragEnter(drag_event.clone()));
+            }
+        } else if let Some(action) = cx.get_event_listener(*id, &EventListener::FileDragLeave) {
+            action(&Event::FileDragLeave(drag_event.clone()));
+        }
+    }
+}
+
+/// Converts a size in points to a percentage of `parent`, or 0 if the parent isn't laid out
+fn size_pct(size: f32, parent: Option<f32>) -> f64 {
+    match parent {
+        Some(parent) if parent > 0.0 => (size / parent * 100.0) as f64,
+        _ => 0.0,
+    }
+}
+
+/// Resolves a length to points, with percentages relative to `basis`
+fn resolve_length(length: LengthPercentage, basis: f32) -> f64 {
+    match length {
+        LengthPercentage::Points(points) => points as f64,
+        LengthPercentage::Percent(pct) => (pct * basis) as f64,
+    }
+}
+
+/// Resolves a length to points, with percentages relative to `basis` and `auto` resolved to `auto`
+fn resolve_length_auto(length: LengthPercentageAuto, basis: f32, auto: f32) -> f64 {
+    match length {
+        LengthPercentageAuto::Points(points) => points as f64,
+        LengthPercentageAuto::Percent(pct) => (pct * basis) as f64,
+        LengthPercentageAuto::Auto => auto as f64,
+    }
+}
+
+/// The animation of `kind` from the current style and `layout` of a view to `val`. Percentages
+/// resolve like the layout does: padding and margin against the width of the parent, insets
+/// against its width or height, and gaps against the view itself. The text of the view has
+/// `font_size`, which may come from an ancestor.
+fn animated_prop(
+    kind: AnimPropKind,
+    val: AnimValue,
+    style: &ComputedStyle,
+    layout: &Layout,
+    parent_size: Option<taffy::geometry::Size<f32>>,
+    font_size: f32,
+) -> AnimatedProp {
+    let parent_width = parent_size.map(|size| size.width).unwrap_or(0.0);
+    match kind {
+        AnimPropKind::Scale => todo!(),
+        AnimPropKind::Width => {
+            let width = layout.size.width;
+            match val {
+                AnimValue::Pct(to) => AnimatedProp::Width {
+                    from: size_pct(width, parent_size.map(|size| size.width)),
+                    to,
+                    unit: SizeUnit::Pct,
+                },
+                val => AnimatedProp::Width {
+                    from: width as f64,
+                    to: val.get_f64(),
+                    unit: SizeUnit::Px,
+                },
+            }
+        }
+        AnimPropKind::Height => {
+            let height = layout.size.height;
+            match val {
+                AnimValue::Pct(to) => AnimatedProp::Height {
+                    from: size_pct(height, parent_size.map(|size| size.height)),
+                    to,
+                    unit: SizeUnit::Pct,
+                },
+                val => AnimatedProp::Height {
+                    from: height as f64,
+                    to: val.get_f64(),
+                    unit: SizeUnit::Px,
+                },
+            }
+        }
+        AnimPropKind::BorderRadius => {
+            let border_radius = style.border_radius;
+            AnimatedProp::BorderRadius {
+                from: border_radius as f64,
+                to: val.get_f64(),
+            }
+        }
+        AnimPropKind::BorderColor => {
+            let border_color = style.border_color;
+            AnimatedProp::BorderColor {
+                from: border_color,
+                to: val.get_color(),
+            }
+        }
+        AnimPropKind::Background => {
+            //TODO:  get from cx
+            let bg = style.background.expect("Bg must be set in the styles");
+            AnimatedProp::Background {
+                from: bg,
+                to: val.get_color(),
+            }
+        }
+        AnimPropKind::Color => {
+            //TODO:  get from cx
+            let color = style
+                .color
+                .expect("Color must be set in the animated view's style");
+            AnimatedProp::Color {
+                from: color,
+                to: val.get_color(),
+            }
+        }
+        AnimPropKind::BorderLeft
+        | AnimPropKind::BorderTop
+        | AnimPropKind::BorderRight
+        | AnimPropKind::BorderBottom => {
+            let border = match kind {
+                AnimPropKind::BorderLeft => style.border_left,
+                AnimPropKind::BorderTop => style.border_top,
+                AnimPropKind::BorderRight => style.border_right,
+                _ => style.border_bottom,
+            };
+            AnimatedProp::BorderWidth {
+                from: border as f64,
+                to: val.get_f64(),
+            }
+        }
+        AnimPropKind::PaddingLeft
+        | AnimPropKind::PaddingTop
+        | AnimPropKind::PaddingRight
+        | AnimPropKind::PaddingBottom => {
+            let padding = match kind {
+                AnimPropKind::PaddingLeft => style.padding_left,
+                AnimPropKind::PaddingTop => style.padding_top,
+                AnimPropKind::PaddingRight => style.padding_right,
+                _ => style.padding_bottom,
+            };
+            AnimatedProp::Padding {
+                from: resolve_length(padding, parent_width),
+                to: val.get_f64(),
+            }
+        }
+        AnimPropKind::MarginLeft
+        | AnimPropKind::MarginTop
+        | AnimPropKind::MarginRight
+        | AnimPropKind::MarginBottom => {
+            let margin = match kind {
+                AnimPropKind::MarginLeft => style.margin_left,
+                AnimPropKind::MarginTop => style.margin_top,
+                AnimPropKind::MarginRight => style.margin_right,
+                _ => style.margin_bottom,
+            };
+            AnimatedProp::Margin {
+                from: resolve_length_auto(margin, parent_width, 0.0),
+                to: val.get_f64(),
+            }
+        }
+        AnimPropKind::InsetLeft
+        | AnimPropKind::InsetTop
+        | AnimPropKind::InsetRight
+        | AnimPropKind::InsetBottom => {
+            // an `auto` inset starts from wherever the layout put the view
+            let (inset, auto, basis) = match kind {
+                AnimPropKind::InsetLeft => (
+                    style.inset_left,
+                    layout.location.x,
+                    parent_size.map(|size| size.width),
+                ),
+                AnimPropKind::InsetTop => (
+                    style.inset_top,
+                    layout.location.y,
+                    parent_size.map(|size| size.height),
+                ),
+                AnimPropKind::InsetRight => {
+                    (style.inset_right, 0.0, parent_size.map(|size| size.width))
+                }
+                _ => (style.inset_bottom, 0.0, parent_size.map(|size| size.height)),
+            };
+            AnimatedProp::Inset {
+                from: resolve_length_auto(inset, basis.unwrap_or(0.0), auto),
+                to: val.get_f64(),
+            }
+        }
+        AnimPropKind::ColumnGap => AnimatedProp::Gap {
+            from: resolve_length(style.gap.width, layout.size.width),
+            to: val.get_f64(),
+        },
+        AnimPropKind::RowGap => AnimatedProp::Gap {
+            from: resolve_length(style.gap.height, layout.size.height),
+            to: val.get_f64(),
+        },
+        AnimPropKind::FontSize => AnimatedProp::FontSize {
+            from: font_size as f64,
+            to: val.get_f64(),
+        },
+    }
 }
 
 /// The top-level handle that is passed into the backend interface (e.g. `glazier`) to interact to window events.
@@ -199,9 +535,9 @@ pub enum UpdateMessage {
 /// - requesting a new animation frame from the backend
 pub struct AppHandle<V: View> {
     scope: Scope,
-    view: V,
+    view: WindowRoot<V>,
     handle: glazier::WindowHandle,
-    app_state: AppState,
+    pub(crate) app_state: AppState,
     paint_state: PaintState,
 
     file_dialogs: FileDialogs,
@@ -222,7 +558,7 @@ impl<V: View> AppHandle<V> {
 
         ViewContext::set_current(cx);
 
-        let view = app_logic();
+        let view = WindowRoot::new(app_logic);
         Self {
             scope,
             view,
@@ -241,16 +577,13 @@ impl<V: View> AppHandle<V> {
 
         cx.clear();
         self.view.compute_layout_main(&mut cx);
+    }
 
-        // Currently we only need one ID with animation in progress to request layout, which will
-        // advance the all the animations in progress.
-        // This will be reworked once we change from request_layout to request_paint
-        let id = self.app_state.ids_with_anim_in_progress().get(0).cloned();
-
-        if let Some(id) = id {
-            id.exec_after(Duration::from_millis(1), move || {
-                id.request_layout();
-            });
+    /// Requests the next animation frame from the backend while anything is still animating.
+    /// Once every animation has settled, no frame is requested and the window goes idle.
+    fn schedule_anim_frame(&mut self) {
+        if self.app_state.is_animating() {
+            self.handle.request_anim_frame();
         }
     }
 
@@ -280,6 +613,7 @@ impl<V: View> AppHandle<V> {
         cx.paint_state.renderer.as_mut().unwrap().begin();
         self.view.paint_main(&mut cx);
         cx.paint_state.renderer.as_mut().unwrap().finish();
+        self.schedule_anim_frame();
     }
 
     fn process_anim_update_messages(&mut self) -> ChangeFlags {
@@ -300,6 +634,28 @@ impl<V: View> AppHandle<V> {
                     let view_id = self.app_state.get_view_id_by_anim_id(anim_id);
                     flags |= self.process_update_anim_prop(view_id, kind, val);
                 }
+                AnimUpdateMsg::GroupBegin { id } => {
+                    // the whole group shares one start time so the offsets stay exact
+                    let now = Clock::now();
+                    for view_id in self.app_state.ids_in_anim_group(id) {
+                        let view_state = self.app_state.view_state(view_id);
+                        if let Some(anim) = view_state.animation.as_mut() {
+                            anim.begin_at(now);
+                        }
+                        self.app_state.request_layout(view_id);
+                    }
+                    flags |= ChangeFlags::LAYOUT;
+                }
+                AnimUpdateMsg::GroupStop { id } => {
+                    for view_id in self.app_state.ids_in_anim_group(id) {
+                        let view_state = self.app_state.view_state(view_id);
+                        if let Some(anim) = view_state.animation.as_mut() {
+                            anim.stop();
+                        }
+                        self.app_state.request_layout(view_id);
+                    }
+                    flags |= ChangeFlags::LAYOUT;
+                }
             }
         }
 
@@ -313,63 +669,24 @@ impl<V: View> AppHandle<V> {
         val: AnimValue,
     ) -> ChangeFlags {
         let layout = self.app_state.get_layout(view_id).unwrap();
+        let parent_size = view_id
+            .parent()
+            .and_then(|parent| self.app_state.get_layout(parent))
+            .map(|layout| layout.size);
+        let font_size = self
+            .app_state
+            .font_size(view_id)
+            .unwrap_or(DEFAULT_FONT_SIZE);
         let view_state = self.app_state.view_state(view_id);
+        let prop = animated_prop(
+            kind,
+            val,
+            &view_state.computed_style,
+            &layout,
+            parent_size,
+            font_size,
+        );
         let anim = view_state.animation.as_mut().unwrap();
-        let prop = match kind {
-            AnimPropKind::Scale => todo!(),
-            AnimPropKind::Width => {
-                let width = layout.size.width;
-                AnimatedProp::Width {
-                    from: width as f64,
-                    to: val.get_f64(),
-                    unit: SizeUnit::Px,
-                }
-            }
-            AnimPropKind::Height => {
-                let height = layout.size.height;
-                AnimatedProp::Width {
-                    from: height as f64,
-                    to: val.get_f64(),
-                    unit: SizeUnit::Px,
-                }
-            }
-            AnimPropKind::BorderRadius => {
-                let border_radius = view_state.computed_style.border_radius;
-                AnimatedProp::BorderRadius {
-                    from: border_radius as f64,
-                    to: val.get_f64(),
-                }
-            }
-            AnimPropKind::BorderColor => {
-                let border_color = view_state.computed_style.border_color;
-                AnimatedProp::BorderColor {
-                    from: border_color,
-                    to: val.get_color(),
-                }
-            }
-            AnimPropKind::Background => {
-                //TODO:  get from cx
-                let bg = view_state
-                    .computed_style
-                    .background
-                    .expect("Bg must be set in the styles");
-                AnimatedProp::Background {
-                    from: bg,
-                    to: val.get_color(),
-                }
-            }
-            AnimPropKind::Color => {
-                //TODO:  get from cx
-                let color = view_state
-                    .computed_style
-                    .color
-                    .expect("Color must be set in the animated view's style");
-                AnimatedProp::Color {
-                    from: color,
-                    to: val.get_color(),
-                }
-            }
-        };
 
         // Overrides the old value
         // TODO: logic based on the old val to make the animation smoother when overriding an old
@@ -441,6 +758,20 @@ impl<V: View> AppHandle<V> {
                         if cx.app_state.has_style_for_sel(id, StyleSelector::Focus) {
                             cx.app_state.request_layout(id);
                         }
+                        scroll_into_view(cx.app_state, id);
+                    }
+                    UpdateMessage::MoveFocus { from, backwards } => {
+                        let root = self.view.focus_scope().unwrap_or(&self.view);
+                        if let Some(id) = view_next_focus(root, cx.app_state, Some(from), backwards)
+                        {
+                            cx.app_state.move_focus(id);
+                        }
+                    }
+                    UpdateMessage::FocusFirstIn(id) => {
+                        let root = self.view.focus_scope().unwrap_or(&self.view);
+                        if let Some(id) = view_first_focus_in(root, cx.app_state, id) {
+                            cx.app_state.move_focus(id);
+                        }
                     }
                     UpdateMessage::Active(id) => {
                         let old = cx.app_state.active;
@@ -504,15 +835,63 @@ impl<V: View> AppHandle<V> {
                             StyleSelector::Disabled => state.disabled_style = style,
                             StyleSelector::Active => state.active_style = style,
                             StyleSelector::Dragging => state.dragging_style = style,
+                            StyleSelector::DropAccept => state.drop_accept_style = style,
                         }
                         cx.request_layout(id);
                     }
                     UpdateMessage::KeyboardNavigable { id } => {
                         cx.app_state.keyboard_navigable.insert(id);
                     }
+                    UpdateMessage::TabIndex { id, index } => {
+                        let state = cx.app_state.view_state(id);
+                        state.tab_index = index;
+                    }
+                    UpdateMessage::FocusScope { id, scope } => {
+                        let state = cx.app_state.view_state(id);
+                        state.focus_scope = Some(scope);
+                    }
+                    UpdateMessage::Signals { id, signals } => {
+                        cx.app_state.set_view_signals(id, signals);
+                    }
                     UpdateMessage::Draggable { id } => {
                         cx.app_state.draggable.insert(id);
                     }
+                    UpdateMessage::DragPayload { id, payload } => {
+                        let state = cx.app_state.view_state(id);
+                        state.drag_payload = Some(payload);
+                    }
+                    UpdateMessage::DragPreview { id, preview } => {
+                        let state = cx.app_state.view_state(id);
+                        state.drag_preview = preview;
+                    }
+                    UpdateMessage::DropTarget { id, target } => {
+                        let state = cx.app_state.view_state(id);
+                        state.drop_target = Some(target);
+                    }
+                    UpdateMessage::Tooltip { id, tooltip } => {
+                        let state = cx.app_state.view_state(id);
+                        state.tooltip = Some(tooltip);
+                    }
+                    UpdateMessage::TooltipDelay { id, delay } => {
+                        let state = cx.app_state.view_state(id);
+                        state.tooltip_delay = delay;
+                    }
+                    UpdateMessage::KeyContext { id, context } => {
+                        let state = cx.app_state.view_state(id);
+                        state.key_context = Some(context);
+                    }
+                    UpdateMessage::CommandHandler {
+                        id,
+                        command,
+                        action,
+                    } => {
+                        let state = cx.app_state.view_state(id);
+                        state.command_handlers.insert(command, action);
+                    }
+                    UpdateMessage::Keymap(keymap) => {
+                        cx.app_state.keymap = keymap;
+                        cx.app_state.pending_keys.clear();
+                    }
                     UpdateMessage::HandleTitleBar(val) => {
                         self.handle.handle_titlebar(val);
                     }
@@ -522,10 +901,67 @@ impl<V: View> AppHandle<V> {
                     UpdateMessage::EventListener {
                         id,
                         listener,
+                        key,
                         action,
                     } => {
                         let state = cx.app_state.view_state(id);
-                        state.event_listeners.insert(listener, action);
+                        state
+                            .event_listeners
+                            .entry(listener)
+                            .or_default()
+                            .push((key, action));
+                    }
+                    UpdateMessage::CaptureListener {
+                        id,
+                        listener,
+                        key,
+                        action,
+                    } => {
+                        let state = cx.app_state.view_state(id);
+                        state
+                            .capture_listeners
+                            .entry(listener)
+                            .or_default()
+                            .push((key, action));
+                    }
+                    UpdateMessage::CapturePointer { id, pointer } => {
+                        cx.app_state.capture_pointer(id, pointer);
+                    }
+                    UpdateMessage::ReleasePointer { id, pointer } => {
+                        if cx.app_state.has_pointer_capture(id, pointer) {
+                            cx.app_state.release_pointer(pointer);
+                        }
+                    }
+                    UpdateMessage::Gesture { id, kind } => {
+                        let state = cx.app_state.view_state(id);
+                        state
+                            .gestures
+                            .get_or_insert_with(GestureRecognizer::new)
+                            .enable(kind);
+                    }
+                    UpdateMessage::GestureConfig { id, config } => {
+                        let state = cx.app_state.view_state(id);
+                        state
+                            .gestures
+                            .get_or_insert_with(GestureRecognizer::new)
+                            .set_config(config);
+                    }
+                    UpdateMessage::LongPressTimer { id } => {
+                        let mut cx = EventCx {
+                            app_state: &mut *cx.app_state,
+                            propagation: EventPropagation::default(),
+                        };
+                        cx.long_press_due(id);
+                    }
+                    UpdateMessage::RemoveListener { id, key } => {
+                        if let Some(state) = cx.app_state.view_states.get_mut(&id) {
+                            for listeners in state.event_listeners.values_mut() {
+                                listeners.retain(|(k, _)| *k != key);
+                            }
+                            for listeners in state.capture_listeners.values_mut() {
+                                listeners.retain(|(k, _)| *k != key);
+                            }
+                        }
                     }
                     UpdateMessage::ResizeListener { id, action } => {
                         let state = cx.app_state.view_state(id);
@@ -569,6 +1005,9 @@ impl<V: View> AppHandle<V> {
                         cx.app_state.update_context_menu(menu);
                         self.handle.show_context_menu(platform_menu, pos);
                     }
+                    UpdateMessage::FileDrag(event) => {
+                        dispatch_file_drag(&mut self.view, cx.app_state, event);
+                    }
                 }
             }
         }
@@ -596,6 +1035,10 @@ impl<V: View> AppHandle<V> {
         let mut flags = ChangeFlags::empty();
         loop {
             flags |= self.process_update_messages();
+            // effects of the signals that changed can send more messages
+            if self.app_state.update_view_signals() {
+                continue;
+            }
             if !self.needs_layout()
                 && !self.has_deferred_update_messages()
                 && !self.has_anim_update_messages()
@@ -613,6 +1056,8 @@ impl<V: View> AppHandle<V> {
             Some(CursorStyle::Default) => glazier::Cursor::Arrow,
             Some(CursorStyle::Pointer) => glazier::Cursor::Pointer,
             Some(CursorStyle::Text) => glazier::Cursor::IBeam,
+            Some(CursorStyle::ColResize) => glazier::Cursor::ResizeLeftRight,
+            Some(CursorStyle::RowResize) => glazier::Cursor::ResizeUpDown,
             None => glazier::Cursor::Arrow,
         };
         self.handle.set_cursor(&glazier_cursor);
@@ -620,36 +1065,89 @@ impl<V: View> AppHandle<V> {
         if !flags.is_empty() {
             self.handle.invalidate();
         }
+
+        self.schedule_anim_frame();
     }
 
     pub fn event(&mut self, event: Event) {
         let event = event.scale(self.app_state.scale);
+        if event.file_drag_event().is_some() {
+            self.file_drag_event(event);
+            return;
+        }
 
         let mut cx = EventCx {
             app_state: &mut self.app_state,
+            propagation: EventPropagation::default(),
         };
 
         let is_pointer_move = matches!(&event, Event::PointerMove(_));
-        let (was_hovered, was_dragging_over) = if is_pointer_move {
-            cx.app_state.cursor = None;
+        let is_pointer_down = matches!(&event, Event::PointerDown(_));
+        let is_pointer_end = matches!(&event, Event::PointerUp(_) | Event::PointerCancel(_));
+        // touch pointers track the views under them separately from the mouse
+        let touch_pointer = event.pointer_id().filter(|_| event.is_touch());
+        let tracks_touch_hover = touch_pointer.is_some() && (is_pointer_move || is_pointer_down);
+
+        let (was_hovered, was_dragging_over) = if is_pointer_move || tracks_touch_hover {
+            if touch_pointer.is_none() {
+                cx.app_state.cursor = None;
+            }
             let was_hovered = std::mem::take(&mut cx.app_state.hovered);
-            let was_dragging_over = std::mem::take(&mut cx.app_state.dragging_over);
+            let was_dragging_over = if is_pointer_move {
+                Some(std::mem::take(&mut cx.app_state.dragging_over))
+            } else {
+                None
+            };
 
-            (Some(was_hovered), Some(was_dragging_over))
+            (Some(was_hovered), was_dragging_over)
         } else {
             (None, None)
         };
+        let was_active = cx.app_state.active;
 
-        let is_pointer_down = matches!(&event, Event::PointerDown(_));
         let was_focused = if is_pointer_down {
             cx.app_state.focus.take()
         } else {
             cx.app_state.focus
         };
 
+        if matches!(
+            event,
+            Event::PointerDown(_) | Event::PointerWheel(_) | Event::KeyDown(_)
+        ) {
+            cx.app_state.hide_tooltip();
+        }
+
+        if is_pointer_down {
+            if let Some(pointer) = event.pointer_id() {
+                cx.app_state.gesture_pointers.remove(&pointer);
+            }
+        } else {
+            // gestures follow their pointers even outside of the views that recognize them
+            cx.recognize_gestures(&event);
+        }
+
         if event.needs_focus() {
             let mut processed = false;
 
+            // the commands handled on the focus path come before the focused view, while the
+            // builtin commands only run for the keys it doesn't handle
+            let mut builtin_command = None;
+            if let Event::KeyDown(key) = &event {
+                let root = self.view.main_id();
+                match cx.app_state.resolve_key_binding(root, key) {
+                    KeymapMatch::None => {}
+                    KeymapMatch::Pending => processed = true,
+                    KeymapMatch::Command(command) => {
+                        if cx.app_state.dispatch_command(root, &command) {
+                            processed = true;
+                        } else {
+                            builtin_command = Some(command);
+                        }
+                    }
+                }
+            }
+
             if !processed {
                 if let Some(id) = cx.app_state.focus {
                     ID_PATHS.with(|paths| {
@@ -659,26 +1157,46 @@ impl<V: View> AppHandle<V> {
                                     .event_main(&mut cx, Some(&id_path.0), event.clone());
                         }
                     });
+                } else if cx.capture_event(self.view.main_id(), &event) {
+                    processed = true;
                 } else if let Some(listener) = event.listener() {
-                    if let Some(action) = cx.get_event_listener(self.view.id(), &listener) {
-                        processed |= (*action)(&event);
+                    if let Some(action) = cx.get_event_listener(self.view.main_id(), &listener) {
+                        processed |= action(&event);
                     }
                 }
 
-                if !processed {
-                    if let Event::KeyDown(glazier::KeyEvent { key, mods, .. }) = &event {
-                        if key == &glazier::KbKey::Tab {
-                            let backwards = mods.contains(glazier::Modifiers::SHIFT);
-                            view_tab_navigation(&self.view, cx.app_state, backwards);
+                if let Some(command) = builtin_command.filter(|_| !processed) {
+                    processed = match command.as_str() {
+                        keymap::FOCUS_NEXT | keymap::FOCUS_PREVIOUS => {
+                            let backwards = command == keymap::FOCUS_PREVIOUS;
+                            let scope = self.view.focus_scope().unwrap_or(&self.view);
+                            view_tab_navigation(scope, cx.app_state, backwards);
+                            true
+                        }
+                        keymap::FOCUS_NEXT_IN_GROUP | keymap::FOCUS_PREVIOUS_IN_GROUP => {
+                            // not handled when the focused view isn't in a group
+                            let backwards = command == keymap::FOCUS_PREVIOUS_IN_GROUP;
+                            let scope = self.view.focus_scope().unwrap_or(&self.view);
+                            view_arrow_navigation(scope, cx.app_state, backwards)
+                        }
+                        keymap::DEBUG_VIEW_TREE => {
                             view_debug_tree(&self.view);
-                        } else if let glazier::KbKey::Character(character) = key {
-                            // 'I' displays some debug information
-                            if character.eq_ignore_ascii_case("i") {
-                                view_debug_tree(&self.view);
-                            }
+                            true
+                        }
+                        _ => false,
+                    };
+                }
+
+                if !processed {
+                    if let Event::KeyDown(key) = &event {
+                        // closes the topmost modal or popup that asked for it
+                        if key.key == glazier::KbKey::Escape {
+                            processed = self.view.escape();
                         }
                     }
+                }
 
+                if !processed {
                     let keyboard_trigger_end = cx.app_state.keyboard_navigation
                         && event.is_keyboard_trigger()
                         && matches!(event, Event::KeyUp(_));
@@ -694,9 +1212,28 @@ impl<V: View> AppHandle<V> {
                     }
                 }
             }
-        } else if cx.app_state.active.is_some() && event.is_pointer() {
+        } else if let Some((pointer, id)) = event.pointer_id().and_then(|pointer| {
+            let id = *cx.app_state.pointer_captures.get(&pointer)?;
+            Some((pointer, id))
+        }) {
+            ID_PATHS.with(|paths| {
+                if let Some(id_path) = paths.borrow().get(&id) {
+                    self.view
+                        .event_main(&mut cx, Some(&id_path.0), event.clone());
+                }
+            });
+            if is_pointer_end {
+                // the capture ends when the pointer is lifted
+                cx.app_state.release_pointer(pointer);
+            }
+        } else if cx.app_state.active.is_some()
+            && event.is_pointer()
+            && (cx.app_state.active_pointer.is_none()
+                || cx.app_state.active_pointer == event.pointer_id())
+        {
             if cx.app_state.is_dragging() {
                 self.view.event_main(&mut cx, None, event.clone());
+                cx.propagation = EventPropagation::default();
             }
 
             let id = cx.app_state.active.unwrap();
@@ -706,24 +1243,85 @@ impl<V: View> AppHandle<V> {
                         .event_main(&mut cx, Some(&id_path.0), event.clone());
                 }
             });
-            if let Event::PointerUp(_) = &event {
+            if is_pointer_end {
                 // To remove the styles applied by the Active selector
                 if cx.app_state.has_style_for_sel(id, StyleSelector::Active) {
                     cx.app_state.request_layout(id);
                 }
 
                 cx.app_state.active = None;
+                cx.app_state.active_pointer = None;
             }
         } else {
             self.view.event_main(&mut cx, None, event.clone());
         }
 
-        if let Event::PointerUp(_) = &event {
+        if let Event::PointerDown(pointer_event) = &event {
+            cx.app_state
+                .anchor_gesture_pointer(pointer_event.pointer_id, pointer_event.pos);
+        }
+        if is_pointer_down && was_active.is_none() && cx.app_state.active.is_some() {
+            cx.app_state.active_pointer = event.pointer_id();
+        }
+        if is_pointer_end {
             cx.app_state.drag_start = None;
+            // the drag is over, so no view accepts its payload any more
+            for id in std::mem::take(&mut cx.app_state.dragging_over) {
+                if cx
+                    .app_state
+                    .has_style_for_sel(id, StyleSelector::DropAccept)
+                {
+                    cx.app_state.request_layout(id);
+                }
+            }
         }
-        if is_pointer_move {
+        if let Event::PointerCancel(_) = &event {
+            // a cancelled drag animates back, as no drop can happen
+            if let Some(dragging) = cx
+                .app_state
+                .dragging
+                .as_mut()
+                .filter(|d| d.released_at.is_none())
+            {
+                dragging.released_at = Some(Clock::now());
+                dragging.id.request_paint();
+            }
+        }
+        if let Some(pointer) = touch_pointer {
+            let pointer_event = event.pointer_event().unwrap();
+            let (was_touch_hovered, touch_hovered) = if tracks_touch_hover {
+                // put the mouse's hover state back
+                let touch_hovered = std::mem::replace(
+                    &mut cx.app_state.hovered,
+                    was_hovered.clone().unwrap_or_default(),
+                );
+                let was_touch_hovered = cx
+                    .app_state
+                    .touch_hovered
+                    .insert(pointer, touch_hovered.clone());
+                (was_touch_hovered.unwrap_or_default(), touch_hovered)
+            } else if is_pointer_end {
+                let was_touch_hovered = cx.app_state.touch_hovered.remove(&pointer);
+                (was_touch_hovered.unwrap_or_default(), HashSet::new())
+            } else {
+                (HashSet::new(), HashSet::new())
+            };
+            for id in was_touch_hovered.symmetric_difference(&touch_hovered) {
+                if touch_hovered.contains(id) {
+                    if let Some(action) = cx.get_event_listener(*id, &EventListener::PointerEnter) {
+                        action(&Event::PointerEnter(pointer_event.clone()));
+                    }
+                } else if let Some(action) =
+                    cx.get_event_listener(*id, &EventListener::PointerLeave)
+                {
+                    action(&Event::PointerLeave(pointer_event.clone()));
+                }
+            }
+        }
+        if is_pointer_move && touch_pointer.is_none() {
             let hovered = &cx.app_state.hovered.clone();
-            for id in was_hovered.unwrap().symmetric_difference(hovered) {
+            let was_hovered = was_hovered.unwrap();
+            for id in was_hovered.symmetric_difference(hovered) {
                 let view_state = cx.app_state.view_state(*id);
                 if view_state.hover_style.is_some()
                     || view_state.active_style.is_some()
@@ -733,25 +1331,36 @@ impl<V: View> AppHandle<V> {
                 }
                 if hovered.contains(id) {
                     if let Some(action) = cx.get_event_listener(*id, &EventListener::PointerEnter) {
-                        (*action)(&event);
+                        action(&event);
                     }
                 } else if let Some(action) =
                     cx.get_event_listener(*id, &EventListener::PointerLeave)
                 {
-                    (*action)(&event);
+                    action(&event);
                 }
             }
+            if let Some(pos) = event.point() {
+                cx.app_state.hover_tooltip(&was_hovered, pos);
+            }
+        }
+        if is_pointer_move {
             let dragging_over = &cx.app_state.dragging_over.clone();
             for id in was_dragging_over
                 .unwrap()
                 .symmetric_difference(dragging_over)
             {
+                if cx
+                    .app_state
+                    .has_style_for_sel(*id, StyleSelector::DropAccept)
+                {
+                    cx.app_state.request_layout(*id);
+                }
                 if dragging_over.contains(id) {
                     if let Some(action) = cx.get_event_listener(*id, &EventListener::DragEnter) {
-                        (*action)(&event);
+                        action(&event);
                     }
                 } else if let Some(action) = cx.get_event_listener(*id, &EventListener::DragLeave) {
-                    (*action)(&event);
+                    action(&event);
                 }
             }
         }
@@ -766,7 +1375,7 @@ impl<V: View> AppHandle<V> {
                     cx.app_state.request_layout(old_id);
                 }
                 if let Some(action) = cx.get_event_listener(old_id, &EventListener::FocusLost) {
-                    (*action)(&event);
+                    action(&event);
                 }
             }
 
@@ -780,14 +1389,23 @@ impl<V: View> AppHandle<V> {
                     cx.app_state.request_layout(id);
                 }
                 if let Some(action) = cx.get_event_listener(id, &EventListener::FocusGained) {
-                    (*action)(&event);
+                    action(&event);
                 }
+                scroll_into_view(cx.app_state, id);
             }
+            cx.app_state.focus_tooltip();
         }
 
         self.process_update();
     }
 
+    /// Delivers files or text dragged from another application, sent to the window with
+    /// [Id::file_drag].
+    fn file_drag_event(&mut self, event: Event) {
+        dispatch_file_drag(&mut self.view, &mut self.app_state, event);
+        self.process_update();
+    }
+
     fn idle(&mut self) {
         while let Some(trigger) = EXT_EVENT_HANDLER.queue.lock().pop_front() {
             trigger.notify();
@@ -837,7 +1455,14 @@ impl<V: View> WinHandler for AppHandle<V> {
         self.event(Event::WindowMoved(point));
     }
 
-    fn prepare_paint(&mut self) {}
+    fn prepare_paint(&mut self) {
+        // Every animation is advanced once per frame with the same timestamp. Frames where only
+        // paint props changed skip the layout pass entirely.
+        let flags = self.app_state.tick_animations(Clock::now());
+        if flags.contains(ChangeFlags::LAYOUT) {
+            self.process_update();
+        }
+    }
 
     fn paint(&mut self, _invalid: &glazier::Region) {
         self.paint();
@@ -870,6 +1495,24 @@ impl<V: View> WinHandler for AppHandle<V> {
         self.event(Event::PointerWheel(event.clone()));
     }
 
+    fn lost_focus(&mut self) {
+        // the window backend doesn't report the pointers the platform takes over, but once the
+        // window loses the focus the pointers it follows won't come back to it
+        let mut pointers: Vec<PointerId> =
+            self.app_state.pointer_captures.keys().copied().collect();
+        if let Some(active) = self.app_state.active_pointer {
+            if !pointers.contains(&active) {
+                pointers.push(active);
+            }
+        }
+        for pointer_id in pointers {
+            self.event(Event::PointerCancel(glazier::PointerEvent {
+                pointer_id,
+                ..Default::default()
+            }));
+        }
+    }
+
     fn idle(&mut self, _token: glazier::IdleToken) {
         self.idle();
     }
@@ -907,3 +1550,424 @@ impl<V: View> WinHandler for AppHandle<V> {
         glazier::Application::global().quit();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        any::Any,
+        cell::{Cell, RefCell},
+        path::PathBuf,
+        rc::Rc,
+    };
+
+    use glazier::{kurbo::Point, KbKey, KeyEvent, PointerButton, PointerEvent, PointerId};
+    use leptos_reactive::{create_runtime, raw_scope_and_disposer};
+
+    use taffy::{
+        prelude::{Layout, Node},
+        style::{LengthPercentage, LengthPercentageAuto},
+    };
+
+    use super::{animated_prop, AppHandle, ViewContext};
+    use crate::{
+        animate::{AnimPropKind, AnimValue},
+        context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+        event::{Event, EventListener, EventPropagation, FileDragEvent},
+        id::Id,
+        style::{ComputedStyle, Style},
+        view::{ChangeFlags, FocusScope, View},
+        views::{container, empty, stack, Decorators},
+    };
+
+    fn pointer(id: u64, x: f64) -> PointerEvent {
+        PointerEvent {
+            pos: Point::new(x, 10.0),
+            button: PointerButton::Left,
+            pointer_id: PointerId(id),
+            ..Default::default()
+        }
+    }
+
+    /// Captures the pointers pressed on it, and records the moves of the pointers it captured.
+    struct Capturing {
+        id: Id,
+        moves: Rc<RefCell<Vec<(PointerId, f64)>>>,
+    }
+
+    impl View for Capturing {
+        fn id(&self) -> Id {
+            self.id
+        }
+
+        fn child(&self, _id: Id) -> Option<&dyn View> {
+            None
+        }
+
+        fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+            None
+        }
+
+        fn children(&self) -> Vec<&dyn View> {
+            Vec::new()
+        }
+
+        fn children_mut(&mut self) -> Vec<&mut dyn View> {
+            Vec::new()
+        }
+
+        fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn Any>) -> ChangeFlags {
+            ChangeFlags::empty()
+        }
+
+        fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+            cx.layout_node(self.id, false, |_| Vec::new())
+        }
+
+        fn event(&mut self, cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+            match event {
+                Event::PointerDown(event) => {
+                    cx.capture_pointer(self.id, event.pointer_id);
+                    true
+                }
+                Event::PointerMove(event) if cx.has_pointer_capture(self.id, event.pointer_id) => {
+                    self.moves
+                        .borrow_mut()
+                        .push((event.pointer_id, event.pos.x));
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        fn paint(&mut self, _cx: &mut PaintCx) {}
+    }
+
+    #[test]
+    fn captured_pointers_go_to_their_own_view() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let first = Rc::new(RefCell::new(Vec::new()));
+        let second = Rc::new(RefCell::new(Vec::new()));
+        // two views side by side, from 0 to 100 and from 100 to 200
+        let mut app = AppHandle::new(scope, {
+            let (first, second) = (first.clone(), second.clone());
+            move || {
+                let capturing = |moves| {
+                    Capturing {
+                        id: ViewContext::get_current().new_id(),
+                        moves,
+                    }
+                    .style(|| Style::BASE.size_px(100.0, 100.0))
+                };
+                stack(move || (capturing(first), capturing(second)))
+            }
+        });
+        app.process_update();
+
+        app.event(Event::PointerDown(pointer(1, 10.0)));
+        app.event(Event::PointerDown(pointer(2, 150.0)));
+
+        // each view follows its own pointer, even over the other view
+        app.event(Event::PointerMove(pointer(1, 150.0)));
+        app.event(Event::PointerMove(pointer(2, 10.0)));
+
+        // lifting or cancelling a pointer only ends its own capture
+        app.event(Event::PointerUp(pointer(1, 150.0)));
+        app.event(Event::PointerMove(pointer(1, 50.0)));
+        app.event(Event::PointerMove(pointer(2, 120.0)));
+        app.event(Event::PointerCancel(pointer(2, 120.0)));
+        app.event(Event::PointerMove(pointer(2, 130.0)));
+
+        assert_eq!(*first.borrow(), [(PointerId(1), 150.0)]);
+        assert_eq!(
+            *second.borrow(),
+            [(PointerId(2), -90.0), (PointerId(2), 20.0)]
+        );
+    }
+
+    #[test]
+    fn file_drags_reach_the_view_under_them() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let target = Rc::new(Cell::new(None));
+        let entered = Rc::new(Cell::new(0));
+        let dropped = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let (target, entered, dropped) = (target.clone(), entered.clone(), dropped.clone());
+            move || {
+                let view = empty()
+                    .style(|| Style::BASE.size_px(100.0, 100.0))
+                    .on_event(EventListener::FileDragEnter, move |_| {
+                        entered.set(entered.get() + 1);
+                        true
+                    })
+                    .on_event(EventListener::FileDragDrop, move |event| {
+                        if let Event::FileDragDrop(drag) = event {
+                            dropped.borrow_mut().extend(drag.paths.iter().cloned());
+                        }
+                        true
+                    });
+                target.set(Some(view.id()));
+                view
+            }
+        });
+        app.process_update();
+        let target = target.get().unwrap();
+        let drag = |x| FileDragEvent {
+            pos: Point::new(x, 50.0),
+            paths: vec![PathBuf::from("notes.txt")],
+            text: None,
+        };
+
+        target.file_drag(Event::FileDragEnter(drag(150.0)));
+        app.process_update();
+        assert_eq!(entered.get(), 0);
+        assert!(!app.app_state.accepts_drop(&target));
+
+        target.file_drag(Event::FileDragOver(drag(50.0)));
+        app.process_update();
+        assert_eq!(entered.get(), 1);
+        assert!(app.app_state.accepts_drop(&target));
+
+        target.file_drag(Event::FileDragDrop(drag(50.0)));
+        app.process_update();
+        assert_eq!(*dropped.borrow(), vec![PathBuf::from("notes.txt")]);
+        assert!(!app.app_state.accepts_drop(&target));
+    }
+
+    #[test]
+    fn animations_start_from_the_resolved_style() {
+        let parent = Some(taffy::geometry::Size {
+            width: 400.0,
+            height: 200.0,
+        });
+        let layout = Layout {
+            order: 0,
+            size: taffy::geometry::Size {
+                width: 100.0,
+                height: 50.0,
+            },
+            location: taffy::geometry::Point { x: 30.0, y: 20.0 },
+        };
+        let style = ComputedStyle::default()
+            .padding_top(LengthPercentage::Percent(0.1))
+            .margin_left(LengthPercentageAuto::Percent(0.05))
+            .inset_top(LengthPercentageAuto::Percent(0.5));
+        let from = |kind, val| {
+            animated_prop(kind, val, &style, &layout, parent, 20.0)
+                .from()
+                .get_f64()
+        };
+        let to = AnimValue::Float(0.0);
+
+        // percentages of padding and margin are of the parent's width, on every side
+        assert_eq!(from(AnimPropKind::PaddingTop, to), 40.0);
+        assert_eq!(from(AnimPropKind::MarginLeft, to), 20.0);
+        // vertical insets are of the parent's height, and `auto` starts where the view is
+        assert_eq!(from(AnimPropKind::InsetTop, to), 100.0);
+        assert_eq!(from(AnimPropKind::InsetLeft, to), 30.0);
+        assert_eq!(from(AnimPropKind::FontSize, to), 20.0);
+        assert_eq!(from(AnimPropKind::Width, AnimValue::Pct(50.0)), 25.0);
+    }
+
+    #[test]
+    fn font_size_is_inherited() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let child = Rc::new(Cell::new(None));
+        let mut app = AppHandle::new(scope, {
+            let child = child.clone();
+            move || {
+                stack(move || {
+                    let view = empty();
+                    child.set(Some(view.id()));
+                    (view,)
+                })
+                .style(|| Style::BASE.font_size(20.0))
+            }
+        });
+        app.process_update();
+        assert_eq!(app.app_state.font_size(child.get().unwrap()), Some(20.0));
+    }
+
+    /// An empty view in a container in a stack, with listeners logging the phases of pointer
+    /// downs in `log`. The empty view handles them, and `capture` is also given to the
+    /// container's capture listener.
+    fn nested_views(
+        log: Rc<RefCell<Vec<&'static str>>>,
+        capture: impl Fn(&mut EventPropagation) + 'static,
+    ) -> impl View {
+        let listen = |name: &'static str| {
+            let log = log.clone();
+            move |_: &Event| {
+                log.borrow_mut().push(name);
+                false
+            }
+        };
+        let capture_listen = |name: &'static str| {
+            let log = log.clone();
+            move |_: &Event, _: &mut EventPropagation| log.borrow_mut().push(name)
+        };
+        let handle = listen("target");
+        let capture_container = capture_listen("capture container");
+        stack(move || {
+            (container(move || {
+                empty()
+                    .style(|| Style::BASE.size_px(50.0, 50.0))
+                    .keyboard_navigatable()
+                    .on_event(EventListener::PointerDown, move |event| {
+                        handle(event);
+                        true
+                    })
+            })
+            .on_event_capture(EventListener::PointerDown, move |event, propagation| {
+                capture_container(event, propagation);
+                capture(propagation);
+            })
+            .on_event(EventListener::PointerDown, listen("bubble container")),)
+        })
+        .on_event_capture(EventListener::PointerDown, capture_listen("capture stack"))
+        .on_event(EventListener::PointerDown, listen("bubble stack"))
+    }
+
+    #[test]
+    fn events_are_captured_down_and_bubble_up_after_being_handled() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let log = log.clone();
+            move || nested_views(log, |_| {})
+        });
+        app.process_update();
+
+        app.event(Event::PointerDown(pointer(1, 10.0)));
+        assert_eq!(
+            *log.borrow(),
+            [
+                "capture stack",
+                "capture container",
+                "target",
+                "bubble container",
+                "bubble stack"
+            ]
+        );
+        assert!(app.app_state.focus.is_some());
+    }
+
+    #[test]
+    fn capture_listeners_stop_propagation_and_prevent_default() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let log = log.clone();
+            move || nested_views(log, |propagation| propagation.stop_propagation())
+        });
+        app.process_update();
+        app.event(Event::PointerDown(pointer(1, 10.0)));
+        assert_eq!(*log.borrow(), ["capture stack", "capture container"]);
+        assert!(app.app_state.focus.is_none());
+
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let log = log.clone();
+            move || nested_views(log, |propagation| propagation.prevent_default())
+        });
+        app.process_update();
+        app.event(Event::PointerDown(pointer(1, 10.0)));
+        assert_eq!(
+            *log.borrow(),
+            [
+                "capture stack",
+                "capture container",
+                "target",
+                "bubble container",
+                "bubble stack"
+            ]
+        );
+        // the target isn't focused on pointer down
+        assert!(app.app_state.focus.is_none());
+    }
+
+    fn key_down(key: KbKey) -> Event {
+        Event::KeyDown(KeyEvent {
+            key,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn focused_view_can_take_tab() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let ids = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let ids = ids.clone();
+            move || {
+                let takes_tab = empty()
+                    .style(|| Style::BASE.size_px(50.0, 50.0))
+                    .keyboard_navigatable()
+                    .on_event(
+                        EventListener::KeyDown,
+                        |event| matches!(event, Event::KeyDown(key) if key.key == KbKey::Tab),
+                    );
+                let other = empty()
+                    .style(|| Style::BASE.size_px(50.0, 50.0))
+                    .keyboard_navigatable();
+                ids.borrow_mut().extend([takes_tab.id(), other.id()]);
+                stack(move || (takes_tab, other))
+            }
+        });
+        app.process_update();
+        let (takes_tab, other) = (ids.borrow()[0], ids.borrow()[1]);
+
+        takes_tab.request_focus();
+        app.process_update();
+        app.event(key_down(KbKey::Tab));
+        assert_eq!(app.app_state.focus, Some(takes_tab));
+
+        // the builtin navigation still runs for a view that doesn't handle Tab
+        other.request_focus();
+        app.process_update();
+        app.event(key_down(KbKey::Tab));
+        assert_ne!(app.app_state.focus, Some(other));
+    }
+
+    #[test]
+    fn focused_view_in_an_arrow_group_can_take_arrows() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let ids = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let ids = ids.clone();
+            move || {
+                let takes_arrows = empty()
+                    .style(|| Style::BASE.size_px(50.0, 50.0))
+                    .keyboard_navigatable()
+                    .on_event(
+                        EventListener::KeyDown,
+                        |event| matches!(event, Event::KeyDown(key) if key.key == KbKey::ArrowDown),
+                    );
+                let other = empty()
+                    .style(|| Style::BASE.size_px(50.0, 50.0))
+                    .keyboard_navigatable();
+                let last = empty()
+                    .style(|| Style::BASE.size_px(50.0, 50.0))
+                    .keyboard_navigatable();
+                ids.borrow_mut()
+                    .extend([takes_arrows.id(), other.id(), last.id()]);
+                stack(move || (takes_arrows, other, last))
+                    .style(|| Style::BASE.flex_col())
+                    .focus_scope(FocusScope::Arrows)
+            }
+        });
+        app.process_update();
+        let (takes_arrows, other, last) = (ids.borrow()[0], ids.borrow()[1], ids.borrow()[2]);
+
+        takes_arrows.request_focus();
+        app.process_update();
+        app.event(key_down(KbKey::ArrowDown));
+        assert_eq!(app.app_state.focus, Some(takes_arrows));
+
+        // the arrow keys still move the focus in the group from a view that doesn't handle them
+        other.request_focus();
+        app.process_update();
+        app.event(key_down(KbKey::ArrowDown));
+        assert_eq!(app.app_state.focus, Some(last));
+    }
+}
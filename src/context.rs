@@ -1,7 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     ops::{Deref, DerefMut},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use floem_renderer::{
@@ -10,7 +10,7 @@ use floem_renderer::{
 };
 use glazier::{
     kurbo::{Affine, Point, Rect, Shape, Size, Vec2},
-    PointerEvent, Scale, TimerToken,
+    KeyEvent, PointerEvent, PointerId, Scale, TimerToken,
 };
 use taffy::{
     prelude::{Layout, Node},
@@ -19,13 +19,23 @@ use taffy::{
 use vello::peniko::Color;
 
 use crate::{
-    animate::{AnimId, AnimPropKind, Animation},
+    animate::{AnimGroupId, AnimId, AnimPropKind, AnimValue, Animation, Clock},
     app_handle::StyleSelector,
-    event::{Event, EventListener},
+    dnd::{DragPayload, DragPreview, DropTarget},
+    event::{Event, EventListener, EventPropagation, ListenerKey},
+    gesture::{GestureEvent, GestureRecognizer},
     id::Id,
+    keymap::{KeyChord, Keymap, KeymapMatch},
     menu::Menu,
+    overlay::{Overlay, OverlayAnchor, OverlayUpdate},
     responsive::{GridBreakpoints, ScreenSize, ScreenSizeBp},
     style::{ComputedStyle, CursorStyle, Style},
+    view::{ChangeFlags, FocusScope, View},
+    view_signals::ViewSignals,
+    views::{
+        scroll_into_view,
+        tooltip::{cursor_anchor, Tooltip, TooltipState, DEFAULT_TOOLTIP_DELAY},
+    },
     ViewContext,
 };
 
@@ -55,7 +65,9 @@ impl ViewContextStore {
 }
 
 pub type EventCallback = dyn Fn(&Event) -> bool;
+pub type CaptureCallback = dyn Fn(&Event, &mut EventPropagation);
 pub type ResizeCallback = dyn Fn(Point, Rect);
+pub type CommandCallback = dyn Fn() -> bool;
 
 pub(crate) struct ResizeListener {
     pub(crate) window_origin: Point,
@@ -73,17 +85,36 @@ pub struct ViewState {
     pub(crate) base_style: Option<Style>,
     pub(crate) style: Style,
     pub(crate) dragging_style: Option<Style>,
+    pub(crate) drop_accept_style: Option<Style>,
     pub(crate) hover_style: Option<Style>,
     pub(crate) disabled_style: Option<Style>,
     pub(crate) focus_style: Option<Style>,
     pub(crate) focus_visible_style: Option<Style>,
     pub(crate) responsive_styles: HashMap<ScreenSizeBp, Vec<Style>>,
     pub(crate) active_style: Option<Style>,
+    /// The style from the view's styles and selectors, before animations are applied
+    pub(crate) static_style: Style,
     pub(crate) combined_style: Style,
     pub(crate) computed_style: ComputedStyle,
-    pub(crate) event_listeners: HashMap<EventListener, Box<EventCallback>>,
+    /// The listeners of each kind, in the order they were added
+    pub(crate) event_listeners: HashMap<EventListener, Vec<(ListenerKey, Box<EventCallback>)>>,
+    pub(crate) capture_listeners: HashMap<EventListener, Vec<(ListenerKey, Box<CaptureCallback>)>>,
     pub(crate) resize_listener: Option<ResizeListener>,
     pub(crate) last_pointer_down: Option<PointerEvent>,
+    /// The key context that scoped key bindings of the window keymap can be limited to
+    pub(crate) key_context: Option<String>,
+    pub(crate) command_handlers: HashMap<String, Box<CommandCallback>>,
+    /// Created when the first gesture listener is added
+    pub(crate) gestures: Option<GestureRecognizer>,
+    /// Makes the payload of a drag of this view
+    pub(crate) drag_payload: Option<Box<dyn Fn() -> DragPayload>>,
+    pub(crate) drag_preview: DragPreview,
+    pub(crate) drop_target: Option<DropTarget>,
+    pub(crate) tooltip: Option<Tooltip>,
+    pub(crate) tooltip_delay: Duration,
+    pub(crate) tab_index: i32,
+    pub(crate) focus_scope: Option<FocusScope>,
+    pub(crate) signals: Option<ViewSignals>,
 }
 
 impl ViewState {
@@ -96,10 +127,12 @@ impl ViewState {
             animation: None,
             base_style: None,
             style: Style::BASE,
+            static_style: Style::BASE,
             combined_style: Style::BASE,
             computed_style: ComputedStyle::default(),
             hover_style: None,
             dragging_style: None,
+            drop_accept_style: None,
             disabled_style: None,
             focus_style: None,
             focus_visible_style: None,
@@ -107,8 +140,20 @@ impl ViewState {
             responsive_styles: HashMap::new(),
             children_nodes: Vec::new(),
             event_listeners: HashMap::new(),
+            capture_listeners: HashMap::new(),
             resize_listener: None,
             last_pointer_down: None,
+            key_context: None,
+            command_handlers: HashMap::new(),
+            gestures: None,
+            drag_payload: None,
+            drag_preview: DragPreview::default(),
+            drop_target: None,
+            tooltip: None,
+            tooltip_delay: DEFAULT_TOOLTIP_DELAY,
+            tab_index: 0,
+            focus_scope: None,
+            signals: None,
         }
     }
 
@@ -117,6 +162,7 @@ impl ViewState {
         view_style: Option<Style>,
         interact_state: InteractionState,
         screen_size_bp: ScreenSizeBp,
+        frame_time: Instant,
     ) {
         let mut computed_style = if let Some(view_style) = view_style {
             if let Some(base_style) = self.base_style.clone() {
@@ -142,6 +188,12 @@ impl ViewState {
             }
         }
 
+        if interact_state.is_drop_accepting {
+            if let Some(drop_accept_style) = self.drop_accept_style.clone() {
+                computed_style = computed_style.apply(drop_accept_style);
+            }
+        }
+
         if interact_state.is_focused {
             if let Some(focus_style) = self.focus_style.clone() {
                 computed_style = computed_style.apply(focus_style);
@@ -169,42 +221,103 @@ impl ViewState {
             }
         }
 
-        'anim: {
-            if let Some(animation) = self.animation.as_mut() {
-                if animation.is_completed() && animation.is_auto_reverse() {
-                    break 'anim;
-                }
+        self.static_style = computed_style;
+        self.apply_animation(frame_time);
+    }
 
-                let props = animation.props();
+    /// Layers the animated values at `frame_time` over the style computed from the view's
+    /// own styles and selectors. Animations are advanced by the frame scheduler, not here.
+    pub(crate) fn apply_animation(&mut self, frame_time: Instant) {
+        let mut computed_style = self.static_style.clone();
 
-                for (kind, _) in props {
-                    let val = animation
-                        .animate_prop(animation.elapsed().unwrap_or(Duration::ZERO), &kind);
-                    match kind {
-                        AnimPropKind::Width => {
-                            computed_style = computed_style.width_px(val.get_f32());
-                        }
-                        AnimPropKind::Height => {
-                            computed_style = computed_style.height_px(val.get_f32());
-                        }
-                        AnimPropKind::Background => {
-                            computed_style = computed_style.background(val.get_color());
-                        }
-                        AnimPropKind::Color => {
-                            computed_style = computed_style.color(val.get_color());
-                        }
-                        AnimPropKind::BorderRadius => {
-                            computed_style = computed_style.border_radius(val.get_f32());
-                        }
-                        AnimPropKind::BorderColor => {
-                            computed_style = computed_style.border_color(val.get_color());
-                        }
-                        AnimPropKind::Scale => todo!(),
-                    }
+        let values = self
+            .animation
+            .as_ref()
+            .and_then(|animation| animation.values_at(frame_time));
+        for (kind, val) in values.unwrap_or_default() {
+            match kind {
+                AnimPropKind::Width => {
+                    computed_style = match val {
+                        AnimValue::Pct(pct) => computed_style.width_pct(pct as f32),
+                        val => computed_style.width_px(val.get_f32()),
+                    };
                 }
-
-                animation.advance();
-                debug_assert!(!animation.is_idle());
+                AnimPropKind::Height => {
+                    computed_style = match val {
+                        AnimValue::Pct(pct) => computed_style.height_pct(pct as f32),
+                        val => computed_style.height_px(val.get_f32()),
+                    };
+                }
+                AnimPropKind::Background => {
+                    computed_style = computed_style.background(val.get_color());
+                }
+                AnimPropKind::Color => {
+                    computed_style = computed_style.color(val.get_color());
+                }
+                AnimPropKind::BorderRadius => {
+                    computed_style = computed_style.border_radius(val.get_f32());
+                }
+                AnimPropKind::BorderColor => {
+                    computed_style = computed_style.border_color(val.get_color());
+                }
+                AnimPropKind::BorderLeft => {
+                    computed_style = computed_style.border_left(val.get_f32());
+                }
+                AnimPropKind::BorderTop => {
+                    computed_style = computed_style.border_top(val.get_f32());
+                }
+                AnimPropKind::BorderRight => {
+                    computed_style = computed_style.border_right(val.get_f32());
+                }
+                AnimPropKind::BorderBottom => {
+                    computed_style = computed_style.border_bottom(val.get_f32());
+                }
+                AnimPropKind::PaddingLeft => {
+                    computed_style = computed_style.padding_left_px(val.get_f32());
+                }
+                AnimPropKind::PaddingTop => {
+                    computed_style = computed_style.padding_top_px(val.get_f32());
+                }
+                AnimPropKind::PaddingRight => {
+                    computed_style = computed_style.padding_right_px(val.get_f32());
+                }
+                AnimPropKind::PaddingBottom => {
+                    computed_style = computed_style.padding_bottom_px(val.get_f32());
+                }
+                AnimPropKind::MarginLeft => {
+                    computed_style = computed_style.margin_left_px(val.get_f32());
+                }
+                AnimPropKind::MarginTop => {
+                    computed_style = computed_style.margin_top_px(val.get_f32());
+                }
+                AnimPropKind::MarginRight => {
+                    computed_style = computed_style.margin_right_px(val.get_f32());
+                }
+                AnimPropKind::MarginBottom => {
+                    computed_style = computed_style.margin_bottom_px(val.get_f32());
+                }
+                AnimPropKind::InsetLeft => {
+                    computed_style = computed_style.inset_left_px(val.get_f32());
+                }
+                AnimPropKind::InsetTop => {
+                    computed_style = computed_style.inset_top_px(val.get_f32());
+                }
+                AnimPropKind::InsetRight => {
+                    computed_style = computed_style.inset_right_px(val.get_f32());
+                }
+                AnimPropKind::InsetBottom => {
+                    computed_style = computed_style.inset_bottom_px(val.get_f32());
+                }
+                AnimPropKind::ColumnGap => {
+                    computed_style = computed_style.column_gap_px(val.get_f32());
+                }
+                AnimPropKind::RowGap => {
+                    computed_style = computed_style.row_gap_px(val.get_f32());
+                }
+                AnimPropKind::FontSize => {
+                    computed_style = computed_style.font_size(val.get_f32());
+                }
+                AnimPropKind::Scale => todo!(),
             }
         }
 
@@ -227,7 +340,8 @@ impl ViewState {
 pub struct DragState {
     pub(crate) id: Id,
     pub(crate) offset: Vec2,
-    pub(crate) released_at: Option<std::time::Instant>,
+    pub(crate) released_at: Option<Instant>,
+    pub(crate) payload: Option<DragPayload>,
 }
 
 /// Encapsulates and owns the global state of the application,
@@ -239,7 +353,18 @@ pub struct AppState {
     /// when a view is active, it gets mouse event even when the mouse is
     /// not on it
     pub(crate) active: Option<Id>,
+    /// The pointer that made the active view active, or `None` if the keyboard did
+    pub(crate) active_pointer: Option<PointerId>,
+    /// The view that gets all the events of a pointer, for each captured pointer
+    pub(crate) pointer_captures: HashMap<PointerId, Id>,
+    /// The views under each touch pointer, which don't share the hover state of the mouse
+    pub(crate) touch_hovered: HashMap<PointerId, HashSet<Id>>,
+    /// The views whose gesture recognizers follow each pointer, innermost first, with the
+    /// offset from window positions to the positions in the view when the pointer went down
+    pub(crate) gesture_pointers: HashMap<PointerId, Vec<(Id, Vec2)>>,
     pub(crate) root: Option<Node>,
+    /// The overlay layer, laid out separately from the root
+    pub(crate) overlay_root: Option<Node>,
     pub(crate) root_size: Size,
     pub(crate) scale: f64,
     pub taffy: taffy::Taffy,
@@ -251,16 +376,28 @@ pub struct AppState {
     pub(crate) dragging: Option<DragState>,
     pub(crate) drag_start: Option<(Id, Point)>,
     pub(crate) dragging_over: HashSet<Id>,
+    /// The views given [ViewSignals], which are the only ones [`Self::update_view_signals`]
+    /// looks at
+    pub(crate) signal_views: HashSet<Id>,
+    /// Files or text from another application are dragged over the window
+    pub(crate) file_dragging: bool,
     pub(crate) screen_size_bp: ScreenSizeBp,
     pub(crate) grid_bps: GridBreakpoints,
     pub(crate) hovered: HashSet<Id>,
+    pub(crate) tooltip: TooltipState,
     /// This keeps track of all views that have an animation,
     /// regardless of the status of the animation
     pub(crate) animated: HashSet<Id>,
+    /// The timestamp of the current animation frame. Every animation is advanced with it,
+    /// and styles computed between frames are sampled at it.
+    pub(crate) frame_time: Instant,
     pub(crate) cursor: Option<CursorStyle>,
     pub(crate) keyboard_navigation: bool,
     pub(crate) context_menu: HashMap<u32, Box<dyn Fn()>>,
     pub(crate) timers: HashMap<TimerToken, Box<dyn FnOnce()>>,
+    pub(crate) keymap: Keymap,
+    /// The chords of a multi-stroke key binding typed so far
+    pub(crate) pending_keys: Vec<KeyChord>,
 }
 
 impl Default for AppState {
@@ -276,8 +413,13 @@ impl AppState {
         Self {
             handle: Default::default(),
             root: None,
+            overlay_root: None,
             focus: None,
             active: None,
+            active_pointer: None,
+            pointer_captures: HashMap::new(),
+            touch_hovered: HashMap::new(),
+            gesture_pointers: HashMap::new(),
             scale: 1.0,
             root_size: Size::ZERO,
             screen_size_bp: ScreenSizeBp::Xs,
@@ -285,18 +427,24 @@ impl AppState {
             taffy,
             view_states: HashMap::new(),
             animated: HashSet::new(),
+            frame_time: Clock::now(),
             disabled: HashSet::new(),
             keyboard_navigable: HashSet::new(),
             draggable: HashSet::new(),
             dragging: None,
             drag_start: None,
             dragging_over: HashSet::new(),
+            signal_views: HashSet::new(),
+            file_dragging: false,
             hovered: HashSet::new(),
+            tooltip: TooltipState::default(),
             cursor: None,
             keyboard_navigation: false,
             grid_bps: GridBreakpoints::default(),
             context_menu: HashMap::new(),
             timers: HashMap::new(),
+            keymap: Keymap::builtin(),
+            pending_keys: Vec::new(),
         }
     }
 
@@ -325,6 +473,122 @@ impl AppState {
             .collect()
     }
 
+    /// The ids from the root to the focused view, or only the root if nothing is focused.
+    pub(crate) fn focus_path(&self, root: Id) -> Vec<Id> {
+        self.focus
+            .and_then(|id| id.id_path())
+            .map(|path| path.0)
+            .unwrap_or_else(|| vec![root])
+    }
+
+    /// Feeds a key press to the window keymap, keeping track of multi-stroke bindings.
+    pub(crate) fn resolve_key_binding(&mut self, root: Id, key: &KeyEvent) -> KeymapMatch {
+        let Some(chord) = KeyChord::from_event(key) else {
+            return KeymapMatch::None;
+        };
+        let was_pending = !self.pending_keys.is_empty();
+        self.pending_keys.push(chord);
+
+        let path = self.focus_path(root);
+        let contexts: Vec<&str> = path
+            .iter()
+            .filter_map(|id| self.view_states.get(id)?.key_context.as_deref())
+            .collect();
+        let matched = self.keymap.resolve(&self.pending_keys, &contexts);
+        match matched {
+            KeymapMatch::Pending => {}
+            // a sequence that went nowhere swallows the key that broke it
+            KeymapMatch::None if was_pending => {
+                self.pending_keys.clear();
+                return KeymapMatch::Pending;
+            }
+            _ => self.pending_keys.clear(),
+        }
+        matched
+    }
+
+    /// Offers `command` to the command handlers on the focus path, from the focused view up to
+    /// the root, until one of them handles it.
+    pub(crate) fn dispatch_command(&self, root: Id, command: &str) -> bool {
+        self.focus_path(root).iter().rev().any(|id| {
+            self.view_states
+                .get(id)
+                .and_then(|state| state.command_handlers.get(command))
+                .map(|handler| handler())
+                .unwrap_or(false)
+        })
+    }
+
+    /// The views whose animation was added to the animation group `group`.
+    pub(crate) fn ids_in_anim_group(&self, group: AnimGroupId) -> Vec<Id> {
+        self.animated
+            .iter()
+            .filter(|id| {
+                self.view_states
+                    .get(id)
+                    .and_then(|state| state.animation.as_ref())
+                    .map(|anim| anim.group == Some(group))
+                    .unwrap_or(false)
+            })
+            .copied()
+            .collect()
+    }
+
+    /// Whether anything needs another animation frame: a running animation, or a released
+    /// drag animating back to its origin.
+    pub fn is_animating(&mut self) -> bool {
+        let drag_released = self
+            .dragging
+            .as_ref()
+            .map(|d| d.released_at.is_some())
+            .unwrap_or(false);
+        drag_released || !self.ids_with_anim_in_progress().is_empty()
+    }
+
+    /// Advances every running animation to `now`, the timestamp shared by the whole frame.
+    ///
+    /// Views are only touched when their animated values actually changed. Animations of
+    /// paint-only props are restyled in place, so a layout is requested only when a size is
+    /// being animated.
+    pub(crate) fn tick_animations(&mut self, now: Instant) -> ChangeFlags {
+        let last_frame = self.frame_time;
+        self.frame_time = now;
+
+        let mut flags = ChangeFlags::empty();
+        for id in self.ids_with_anim_in_progress() {
+            let view_state = self.view_state(id);
+            let animation = match view_state.animation.as_mut() {
+                Some(animation) => animation,
+                None => continue,
+            };
+
+            let before = animation.values_at(last_frame);
+            animation.advance(now);
+            if animation.values_at(now) == before {
+                continue;
+            }
+
+            if animation.affects_layout() {
+                self.request_layout(id);
+                flags |= ChangeFlags::LAYOUT;
+            } else {
+                view_state.apply_animation(now);
+                flags |= ChangeFlags::PAINT;
+            }
+        }
+
+        let drag_released = self
+            .dragging
+            .as_ref()
+            .map(|d| d.released_at.is_some())
+            .unwrap_or(false);
+        if drag_released {
+            flags |= ChangeFlags::PAINT;
+        }
+
+        flags
+    }
+
     pub fn is_hidden(&self, id: Id) -> bool {
         self.view_states
             .get(&id)
@@ -354,6 +618,55 @@ impl AppState {
         self.disabled.contains(id)
     }
 
+    pub(crate) fn tab_index(&self, id: Id) -> i32 {
+        self.view_states
+            .get(&id)
+            .map(|state| state.tab_index)
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn focus_scope(&self, id: Id) -> Option<FocusScope> {
+        self.view_states
+            .get(&id)
+            .and_then(|state| state.focus_scope)
+    }
+
+    /// Brings the [ViewSignals] of the views up to date, and returns whether any of them
+    /// changed.
+    pub(crate) fn update_view_signals(&self) -> bool {
+        let mut changed = false;
+        for id in &self.signal_views {
+            if let Some(state) = self.view_states.get(id) {
+                if let Some(signals) = state.signals {
+                    changed |= signals.update(self, *id, state.layout_rect);
+                }
+            }
+        }
+        changed
+    }
+
+    /// The font size of the text of the view: its own, or else the one it inherits from the
+    /// nearest ancestor that sets one.
+    pub(crate) fn font_size(&self, id: Id) -> Option<f32> {
+        let mut id = Some(id);
+        while let Some(current) = id {
+            let font_size = self
+                .view_states
+                .get(&current)
+                .and_then(|state| state.computed_style.font_size);
+            if font_size.is_some() {
+                return font_size;
+            }
+            id = current.parent();
+        }
+        None
+    }
+
+    pub(crate) fn set_view_signals(&mut self, id: Id, signals: ViewSignals) {
+        self.view_state(id).signals = Some(signals);
+        self.signal_views.insert(id);
+    }
+
     pub fn is_focused(&self, id: &Id) -> bool {
         self.focus.map(|f| &f == id).unwrap_or(false)
     }
@@ -362,6 +675,33 @@ impl AppState {
         self.active.map(|a| &a == id).unwrap_or(false)
     }
 
+    pub fn has_pointer_capture(&self, id: Id, pointer: PointerId) -> bool {
+        self.pointer_captures.get(&pointer) == Some(&id)
+    }
+
+    /// Sends all further events of `pointer` to `id`, whether the pointer is over it or not,
+    /// until the pointer is lifted, cancelled or released with [`Self::release_pointer`].
+    pub(crate) fn capture_pointer(&mut self, id: Id, pointer: PointerId) {
+        self.pointer_captures.insert(pointer, id);
+    }
+
+    pub(crate) fn release_pointer(&mut self, pointer: PointerId) {
+        self.pointer_captures.remove(&pointer);
+    }
+
+    /// Turns the view positions recorded by [`EventCx::gesture_pointer_down`] into offsets
+    /// from `window_pos`, the position of the pointer in the window.
+    pub(crate) fn anchor_gesture_pointer(&mut self, pointer: PointerId, window_pos: Point) {
+        for (_, offset) in self
+            .gesture_pointers
+            .get_mut(&pointer)
+            .into_iter()
+            .flatten()
+        {
+            *offset = window_pos.to_vec2() - *offset;
+        }
+    }
+
     pub fn is_dragging(&self) -> bool {
         self.dragging
             .as_ref()
@@ -369,12 +709,43 @@ impl AppState {
             .unwrap_or(false)
     }
 
+    /// Whether the payload of the drag in progress is over the view, and the view accepts it.
+    /// Files and text dragged from other applications are accepted by views listening to
+    /// `FileDragDrop`.
+    pub fn accepts_drop(&self, id: &Id) -> bool {
+        if self.file_dragging {
+            return self.dragging_over.contains(id)
+                && self
+                    .view_states
+                    .get(id)
+                    .and_then(|s| s.event_listeners.get(&EventListener::FileDragDrop))
+                    .map(|listeners| !listeners.is_empty())
+                    .unwrap_or(false);
+        }
+        let Some(payload) = self
+            .dragging
+            .as_ref()
+            .filter(|d| d.released_at.is_none())
+            .and_then(|d| d.payload.as_ref())
+        else {
+            return false;
+        };
+        self.dragging_over.contains(id)
+            && self
+                .view_states
+                .get(id)
+                .and_then(|s| s.drop_target.as_ref())
+                .map(|target| target.accepts(payload))
+                .unwrap_or(false)
+    }
+
     pub fn get_interact_state(&self, id: &Id) -> InteractionState {
         InteractionState {
             is_hovered: self.is_hovered(id),
             is_disabled: self.is_disabled(id),
             is_focused: self.is_focused(id),
             is_active: self.is_active(id),
+            is_drop_accepting: self.accepts_drop(id),
             using_keyboard_navigation: self.keyboard_navigation,
         }
     }
@@ -387,8 +758,9 @@ impl AppState {
     pub(crate) fn compute_style(&mut self, id: Id, view_style: Option<Style>) {
         let interact_state = self.get_interact_state(&id);
         let screen_size_bp = self.screen_size_bp;
+        let frame_time = self.frame_time;
         let view_state = self.view_state(id);
-        view_state.compute_style(view_style, interact_state, screen_size_bp);
+        view_state.compute_style(view_style, interact_state, screen_size_bp, frame_time);
     }
 
     pub(crate) fn get_computed_style(&mut self, id: Id) -> &ComputedStyle {
@@ -397,14 +769,114 @@ impl AppState {
     }
 
     pub fn compute_layout(&mut self) {
-        if let Some(root) = self.root {
-            let _ = self.taffy.compute_layout(
-                root,
-                taffy::prelude::Size {
-                    width: AvailableSpace::Definite((self.root_size.width / self.scale) as f32),
-                    height: AvailableSpace::Definite((self.root_size.height / self.scale) as f32),
-                },
-            );
+        let available_space = taffy::prelude::Size {
+            width: AvailableSpace::Definite((self.root_size.width / self.scale) as f32),
+            height: AvailableSpace::Definite((self.root_size.height / self.scale) as f32),
+        };
+        for root in self.root.into_iter().chain(self.overlay_root) {
+            let _ = self.taffy.compute_layout(root, available_space);
+        }
+    }
+
+    /// The size of the window, in the coordinates views are laid out in.
+    pub(crate) fn window_size(&self) -> Size {
+        self.root_size / self.scale
+    }
+
+    /// Starts the delay of the tooltip of `id`, which then shows next to `anchor`.
+    pub(crate) fn schedule_tooltip(&mut self, id: Id, anchor: Rect, from_focus: bool) {
+        let Some(delay) = self
+            .view_states
+            .get(&id)
+            .filter(|state| state.tooltip.is_some())
+            .map(|state| state.tooltip_delay)
+        else {
+            return;
+        };
+        let Some(root) = id.root_id() else {
+            return;
+        };
+        self.hide_tooltip();
+        self.tooltip.owner = Some(id);
+        self.tooltip.from_focus = from_focus;
+        self.tooltip.anchor = anchor;
+        let generation = self.tooltip.generation;
+        self.request_timer(
+            delay,
+            Box::new(move || {
+                root.update_state(OverlayUpdate::ShowTooltip { generation }, false);
+            }),
+        );
+    }
+
+    /// Hides the tooltip, or stops it from showing if its delay isn't over. It shows again
+    /// once its view is entered or focused again.
+    pub(crate) fn hide_tooltip(&mut self) {
+        self.tooltip.owner = None;
+        self.tooltip.generation += 1;
+        if let Some(overlay) = self.tooltip.shown.take() {
+            if let Some(root) = overlay.root_id() {
+                root.update_state(OverlayUpdate::Unmount(overlay), false);
+            }
+        }
+    }
+
+    /// Creates the overlay of the tooltip whose delay started at `generation`, unless the
+    /// tooltip was hidden since.
+    pub(crate) fn tooltip_due(&mut self, root: Id, generation: u64) -> Option<Overlay> {
+        if generation != self.tooltip.generation || self.tooltip.shown.is_some() {
+            return None;
+        }
+        let owner = self.tooltip.owner?;
+        let tooltip = self.view_states.get(&owner)?.tooltip.as_ref()?;
+        let anchor = OverlayAnchor::Rect(self.tooltip.anchor);
+        let overlay = Overlay::new(root, tooltip.scope, anchor, || (tooltip.view)())
+            .owner(owner)
+            .passthrough();
+        self.tooltip.shown = Some(overlay.id());
+        Some(overlay)
+    }
+
+    /// Keeps the tooltip in step with the views under the pointer at `pos`. The tooltip of
+    /// the innermost view the pointer entered starts its delay, and the tooltip of a view the
+    /// pointer left hides.
+    pub(crate) fn hover_tooltip(&mut self, was_hovered: &HashSet<Id>, pos: Point) {
+        if let Some(owner) = self.tooltip.owner.filter(|_| !self.tooltip.from_focus) {
+            if !self.hovered.contains(&owner) {
+                self.hide_tooltip();
+            } else if self.tooltip.shown.is_none() {
+                // the tooltip shows where the pointer rests
+                self.tooltip.anchor = cursor_anchor(pos);
+            }
+        }
+        let entered = self
+            .hovered
+            .difference(was_hovered)
+            .filter(|id| {
+                self.view_states
+                    .get(id)
+                    .map(|state| state.tooltip.is_some())
+                    .unwrap_or(false)
+            })
+            .max_by_key(|id| id.id_path().map(|path| path.0.len()).unwrap_or(0))
+            .copied();
+        if let Some(id) = entered {
+            self.schedule_tooltip(id, cursor_anchor(pos), false);
+        }
+    }
+
+    /// Shows the tooltip of the view focused with the keyboard below it, and hides the
+    /// tooltip of the view that lost the focus.
+    pub(crate) fn focus_tooltip(&mut self) {
+        if self.tooltip.from_focus
+            && self.tooltip.owner.is_some()
+            && self.tooltip.owner != self.focus
+        {
+            self.hide_tooltip();
+        }
+        if let Some(id) = self.focus.filter(|_| self.keyboard_navigation) {
+            let anchor = self.get_layout_rect(id);
+            self.schedule_tooltip(id, anchor, true);
         }
     }
 
@@ -471,6 +943,22 @@ impl AppState {
         self.focus = None;
     }
 
+    /// Moves the focus to `id` as keyboard navigation does, outside of an event.
+    pub(crate) fn move_focus(&mut self, id: Id) {
+        let old = self.focus;
+        self.clear_focus();
+        self.update_focus(id, true);
+        for id in old.into_iter().chain([id]) {
+            // To apply or remove the styles of the Focus selectors
+            if self.has_style_for_sel(id, StyleSelector::Focus)
+                || self.has_style_for_sel(id, StyleSelector::FocusVisible)
+            {
+                self.request_layout(id);
+            }
+        }
+        scroll_into_view(self, id);
+    }
+
     pub(crate) fn update_focus(&mut self, id: Id, keyboard_navigation: bool) {
         if self.focus.is_some() {
             return;
@@ -490,6 +978,7 @@ impl AppState {
             StyleSelector::Disabled => view_state.disabled_style.is_some(),
             StyleSelector::Active => view_state.active_style.is_some(),
             StyleSelector::Dragging => view_state.dragging_style.is_some(),
+            StyleSelector::DropAccept => view_state.drop_accept_style.is_some(),
         }
     }
 
@@ -533,9 +1022,16 @@ impl AppState {
 /// A bundle of helper methods to be used by `View::event` handlers
 pub struct EventCx<'a> {
     pub(crate) app_state: &'a mut AppState,
+    pub(crate) propagation: EventPropagation,
 }
 
 impl<'a> EventCx<'a> {
+    /// Keeps the event being handled from the listeners of the ancestors of the view, which
+    /// otherwise observe it as it bubbles up.
+    pub fn stop_propagation(&mut self) {
+        self.propagation.stop_propagation();
+    }
+
     pub fn update_active(&mut self, id: Id) {
         self.app_state.update_active(id);
     }
@@ -544,6 +1040,20 @@ impl<'a> EventCx<'a> {
         self.app_state.is_active(&id)
     }
 
+    /// Sends all further events of `pointer` to the view `id`. Several pointers can be
+    /// captured by different views at once, e.g. two fingers dragging two sliders.
+    pub fn capture_pointer(&mut self, id: Id, pointer: PointerId) {
+        self.app_state.capture_pointer(id, pointer);
+    }
+
+    pub fn release_pointer(&mut self, pointer: PointerId) {
+        self.app_state.release_pointer(pointer);
+    }
+
+    pub fn has_pointer_capture(&self, id: Id, pointer: PointerId) -> bool {
+        self.app_state.has_pointer_capture(id, pointer)
+    }
+
     #[allow(unused)]
     pub(crate) fn update_focus(&mut self, id: Id, keyboard_navigation: bool) {
         self.app_state.update_focus(id, keyboard_navigation);
@@ -582,19 +1092,230 @@ impl<'a> EventCx<'a> {
         self.app_state
             .view_states
             .get(&id)
-            .map(|s| s.event_listeners.contains_key(&listener))
+            .and_then(|s| s.event_listeners.get(&listener))
+            .map(|listeners| !listeners.is_empty())
             .unwrap_or(false)
     }
 
+    /// All listeners of the view for `listener` combined into one. Every listener runs, and the
+    /// event counts as handled if any of them handled it.
     pub(crate) fn get_event_listener(
         &self,
         id: Id,
         listener: &EventListener,
-    ) -> Option<&impl Fn(&Event) -> bool> {
+    ) -> Option<impl Fn(&Event) -> bool + '_> {
+        let listeners = self
+            .app_state
+            .view_states
+            .get(&id)?
+            .event_listeners
+            .get(listener)
+            .filter(|listeners| !listeners.is_empty())?;
+        Some(move |event: &Event| {
+            listeners
+                .iter()
+                .fold(false, |handled, (_, action)| action(event) || handled)
+        })
+    }
+
+    /// Runs the capture listeners of the view for `event`, if the event is meant for it.
+    /// Returns `true` if a listener stopped the propagation of the event.
+    pub(crate) fn capture_event(&mut self, id: Id, event: &Event) -> bool {
+        let Some(listener) = event.listener() else {
+            return false;
+        };
+        if let Some(pos) = event.point() {
+            let rect = self.get_size(id).unwrap_or_default().to_rect();
+            if !rect.contains(pos) {
+                return false;
+            }
+        }
+        let listeners = self
+            .app_state
+            .view_states
+            .get(&id)
+            .and_then(|s| s.capture_listeners.get(&listener));
+        // the other listeners of the view still run when one stops the propagation
+        for (_, action) in listeners.into_iter().flatten() {
+            action(event, &mut self.propagation);
+        }
+        self.propagation.is_propagation_stopped()
+    }
+
+    /// Lets the bubble phase listeners of the view observe `event`, which the view or one of
+    /// its children already handled, unless its propagation was stopped.
+    pub(crate) fn bubble_handled_event(&self, id: Id, event: &Event) {
+        if !self.propagation.is_propagation_stopped() {
+            self.bubble_event(id, event);
+        }
+    }
+
+    /// Runs the bubble phase listeners of the view for `event`, if the event is meant for it.
+    /// Returns `true` if a listener handled the event.
+    pub(crate) fn bubble_event(&self, id: Id, event: &Event) -> bool {
+        if let Some(listener) = event.listener() {
+            if let Some(action) = self.get_event_listener(id, &listener) {
+                let should_run = if let Some(pos) = event.point() {
+                    let rect = self.get_size(id).unwrap_or_default().to_rect();
+                    rect.contains(pos)
+                } else {
+                    true
+                };
+                if should_run && action(event) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Starts following a pointer pressed on the view with its gesture recognizer, if it has one.
+    /// The pointer's later events are fed to the recognizer by [`Self::recognize_gestures`],
+    /// wherever the pointer goes.
+    pub(crate) fn gesture_pointer_down(&mut self, id: Id, pointer_event: &PointerEvent) {
+        let pointer = pointer_event.pointer_id;
+        let pos = pointer_event.pos;
+        let now = Clock::now();
+        let Some(recognizer) = self
+            .app_state
+            .view_states
+            .get_mut(&id)
+            .and_then(|s| s.gestures.as_mut())
+        else {
+            return;
+        };
+        let events = recognizer.pointer_down(pointer, pos, now);
+        let long_press = recognizer.long_press_deadline();
+
+        // the position becomes an offset from the window once the event has been dispatched
         self.app_state
+            .gesture_pointers
+            .entry(pointer)
+            .or_default()
+            .push((id, pos.to_vec2()));
+        if let Some(deadline) = long_press {
+            self.app_state.request_timer(
+                deadline.saturating_duration_since(now),
+                Box::new(move || id.long_press_timer()),
+            );
+        }
+        self.finish_gestures(id, events);
+    }
+
+    /// Feeds a pointer event to the recognizers following the pointer. `event` is positioned
+    /// in the window.
+    pub(crate) fn recognize_gestures(&mut self, event: &Event) {
+        let Some(pointer) = event.pointer_id() else {
+            return;
+        };
+        let views = self
+            .app_state
+            .gesture_pointers
+            .get(&pointer)
+            .cloned()
+            .unwrap_or_default();
+        let now = Clock::now();
+        for (id, offset) in views {
+            let Some(pos) = event.point().map(|pos| pos - offset) else {
+                continue;
+            };
+            let Some(recognizer) = self
+                .app_state
+                .view_states
+                .get_mut(&id)
+                .and_then(|s| s.gestures.as_mut())
+            else {
+                continue;
+            };
+            let events = match event {
+                Event::PointerMove(_) => recognizer.pointer_move(pointer, pos, now),
+                Event::PointerUp(_) => recognizer.pointer_up(pointer, pos, now),
+                Event::PointerCancel(_) => recognizer.cancel(),
+                _ => Vec::new(),
+            };
+            self.finish_gestures(id, events);
+        }
+        if matches!(event, Event::PointerUp(_) | Event::PointerCancel(_)) {
+            self.app_state.gesture_pointers.remove(&pointer);
+        }
+    }
+
+    /// Recognizes a long press on the view if its pointer has been held down long enough.
+    pub(crate) fn long_press_due(&mut self, id: Id) {
+        let events = self
+            .app_state
+            .view_states
+            .get_mut(&id)
+            .and_then(|s| s.gestures.as_mut())
+            .map(|recognizer| recognizer.long_press_due(Clock::now()))
+            .unwrap_or_default();
+        self.finish_gestures(id, events);
+    }
+
+    /// Lets the view claim its pointers if it recognized a gesture, and runs the listeners
+    /// of the gestures.
+    fn finish_gestures(&mut self, id: Id, events: Vec<GestureEvent>) {
+        let claimed = self
+            .app_state
             .view_states
             .get(&id)
-            .and_then(|s| s.event_listeners.get(listener))
+            .and_then(|s| s.gestures.as_ref())
+            .filter(|recognizer| recognizer.is_claiming())
+            .map(|recognizer| recognizer.pointers())
+            .unwrap_or_default();
+        for pointer in claimed {
+            let views = self
+                .app_state
+                .gesture_pointers
+                .get_mut(&pointer)
+                .map(std::mem::take)
+                .unwrap_or_default();
+            for (other, offset) in views {
+                if other == id {
+                    self.app_state
+                        .gesture_pointers
+                        .entry(pointer)
+                        .or_default()
+                        .push((other, offset));
+                    continue;
+                }
+                // the other recognizers give up on every pointer they were following
+                let events = self
+                    .app_state
+                    .view_states
+                    .get_mut(&other)
+                    .and_then(|s| s.gestures.as_mut())
+                    .map(|recognizer| recognizer.cancel())
+                    .unwrap_or_default();
+                for views in self.app_state.gesture_pointers.values_mut() {
+                    views.retain(|(view, _)| *view != other);
+                }
+                self.dispatch_gestures(other, events);
+            }
+            if !self.app_state.has_pointer_capture(id, pointer) {
+                self.app_state.capture_pointer(id, pointer);
+                if self.app_state.active_pointer == Some(pointer) {
+                    // the gesture wins over a click of the active view
+                    if let Some(active) = self.app_state.active.take() {
+                        if self
+                            .app_state
+                            .has_style_for_sel(active, StyleSelector::Active)
+                        {
+                            self.app_state.request_layout(active);
+                        }
+                    }
+                    self.app_state.active_pointer = None;
+                }
+            }
+        }
+        self.dispatch_gestures(id, events);
+    }
+
+    fn dispatch_gestures(&mut self, id: Id, events: Vec<GestureEvent>) {
+        for event in events {
+            self.bubble_event(id, &Event::Gesture(event));
+        }
     }
 
     /// translate a window-positioned event to the local coordinate system of a view
@@ -649,6 +1370,8 @@ pub struct InteractionState {
     pub(crate) is_disabled: bool,
     pub(crate) is_focused: bool,
     pub(crate) is_active: bool,
+    /// A payload the view accepts is dragged over it
+    pub(crate) is_drop_accepting: bool,
     pub(crate) using_keyboard_navigation: bool,
 }
 
@@ -0,0 +1,147 @@
+//! # Drag and drop
+//!
+//! A [draggable](crate::views::Decorators::draggable) view can carry a [DragPayload], set with
+//! [Decorators::drag_payload](crate::views::Decorators::drag_payload). A payload has a kind,
+//! a MIME-like string such as `"text/plain"` or `"app/todo-item"`, and any data.
+//!
+//! Views receive payloads with [Decorators::on_drop](crate::views::Decorators::on_drop), which
+//! names the kinds the view accepts. `"text/*"` accepts every kind starting with `text/` and
+//! `"*"` accepts every kind. While an accepted payload is dragged over the view, the view gets its
+//! [drop_accept_style](crate::views::Decorators::drop_accept_style).
+//!
+//! Files and text dragged from other applications are meant to arrive as
+//! [FileDragEvent](crate::event::FileDragEvent)s instead, with the `FileDragEnter`,
+//! `FileDragOver`, `FileDragLeave` and `FileDragDrop` listeners. A view listening to
+//! `FileDragDrop` gets its drop accept style while they're dragged over it.
+//!
+//! The window backend doesn't report these drags yet, so the window never produces them by
+//! itself. Until it does, they only come from [Id::file_drag](crate::id::Id::file_drag).
+
+use std::{any::Any, fmt};
+
+use glazier::kurbo::{Point, Size};
+
+use crate::context::PaintCx;
+
+pub type DropCallback = dyn Fn(&DragPayload, Point) -> bool;
+pub type DragPreviewPaint = dyn Fn(&mut PaintCx, Size);
+
+/// The data carried by a drag.
+pub struct DragPayload {
+    kind: String,
+    data: Box<dyn Any>,
+}
+
+impl DragPayload {
+    pub fn new(kind: impl Into<String>, data: impl Any) -> Self {
+        Self {
+            kind: kind.into(),
+            data: Box::new(data),
+        }
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// The data of the payload, if it's a `T`.
+    pub fn get<T: Any>(&self) -> Option<&T> {
+        self.data.downcast_ref()
+    }
+}
+
+impl fmt::Debug for DragPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DragPayload")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Whether the kind `pattern` of a drop target accepts a payload of `kind`.
+pub(crate) fn kind_matches(pattern: &str, kind: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => kind.starts_with(prefix),
+        None => pattern == kind,
+    }
+}
+
+pub(crate) struct DropTarget {
+    pub(crate) kinds: Vec<String>,
+    pub(crate) action: Box<DropCallback>,
+}
+
+impl DropTarget {
+    pub(crate) fn accepts(&self, payload: &DragPayload) -> bool {
+        self.kinds
+            .iter()
+            .any(|pattern| kind_matches(pattern, payload.kind()))
+    }
+}
+
+/// What follows the pointer while a view is dragged.
+pub struct DragPreview {
+    pub(crate) visible: bool,
+    pub(crate) z_index: i32,
+    pub(crate) paint: Option<Box<DragPreviewPaint>>,
+}
+
+impl Default for DragPreview {
+    fn default() -> Self {
+        Self::source()
+    }
+}
+
+impl DragPreview {
+    /// The dragged view itself, with its dragging style applied.
+    pub fn source() -> Self {
+        Self {
+            visible: true,
+            z_index: 1000,
+            paint: None,
+        }
+    }
+
+    /// Nothing, e.g. for drop targets that show where the payload will land instead.
+    pub fn none() -> Self {
+        Self {
+            visible: false,
+            ..Self::source()
+        }
+    }
+
+    /// Paints the preview with `paint`, given the size of the dragged view.
+    pub fn paint(paint: impl Fn(&mut PaintCx, Size) + 'static) -> Self {
+        Self {
+            paint: Some(Box::new(paint)),
+            ..Self::source()
+        }
+    }
+
+    pub fn z_index(mut self, z_index: i32) -> Self {
+        self.z_index = z_index;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{kind_matches, DragPayload};
+
+    #[test]
+    fn kinds_match_wildcards() {
+        assert!(kind_matches("text/plain", "text/plain"));
+        assert!(!kind_matches("text/plain", "text/html"));
+        assert!(kind_matches("text/*", "text/html"));
+        assert!(!kind_matches("text/*", "image/png"));
+        assert!(kind_matches("*", "app/todo-item"));
+    }
+
+    #[test]
+    fn payload_downcasts() {
+        let payload = DragPayload::new("app/index", 3usize);
+        assert_eq!(payload.kind(), "app/index");
+        assert_eq!(payload.get::<usize>(), Some(&3));
+        assert_eq!(payload.get::<String>(), None);
+    }
+}
@@ -1,9 +1,16 @@
+use std::path::PathBuf;
+
 use glazier::{
     kurbo::{Point, Size},
-    KeyEvent, PointerEvent,
+    KeyEvent, PointerEvent, PointerId, PointerType,
+};
+
+use crate::{
+    gesture::{GestureEvent, GestureKind},
+    id::Id,
 };
 
-#[derive(Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum EventListener {
     KeyDown,
     KeyUp,
@@ -15,12 +22,22 @@ pub enum EventListener {
     DragEnter,
     DragLeave,
     Drop,
+    FileDragEnter,
+    FileDragOver,
+    FileDragLeave,
+    FileDragDrop,
     PointerDown,
     PointerMove,
     PointerUp,
     PointerEnter,
     PointerLeave,
     PointerWheel,
+    PointerCancel,
+    Tap,
+    LongPress,
+    Pan,
+    Swipe,
+    Pinch,
     FocusGained,
     FocusLost,
     WindowClosed,
@@ -28,12 +45,134 @@ pub enum EventListener {
     WindowMoved,
 }
 
+impl From<GestureKind> for EventListener {
+    fn from(kind: GestureKind) -> Self {
+        match kind {
+            GestureKind::Tap => EventListener::Tap,
+            GestureKind::LongPress => EventListener::LongPress,
+            GestureKind::Pan => EventListener::Pan,
+            GestureKind::Swipe => EventListener::Swipe,
+            GestureKind::Pinch => EventListener::Pinch,
+        }
+    }
+}
+
+/// Identifies one event listener among all the listeners of a view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ListenerKey(u64);
+
+impl ListenerKey {
+    pub(crate) fn next() -> Self {
+        use glazier::Counter;
+        static LISTENER_KEY_COUNTER: Counter = Counter::new();
+        ListenerKey(LISTENER_KEY_COUNTER.next())
+    }
+}
+
+/// Keeps an event listener registered on a view. Dropping the handle removes the listener.
+#[must_use = "dropping the handle removes the listener right away"]
+#[derive(Debug)]
+pub struct ListenerHandle {
+    id: Id,
+    key: ListenerKey,
+}
+
+impl ListenerHandle {
+    pub(crate) fn new(id: Id, key: ListenerKey) -> Self {
+        Self { id, key }
+    }
+
+    pub fn key(&self) -> ListenerKey {
+        self.key
+    }
+
+    /// Keeps the listener for as long as the view exists.
+    pub fn forget(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl Drop for ListenerHandle {
+    fn drop(&mut self) {
+        self.id.remove_listener(self.key);
+    }
+}
+
+/// Controls how far an event travels, passed to the capture listeners of
+/// [Decorators::on_event_capture](crate::views::Decorators::on_event_capture).
+///
+/// An event first travels down from the root towards the views it's for in the capture phase,
+/// running the capture listeners of the views on the way, outermost first. Then the views it
+/// reached handle it innermost first with [View::event](crate::view::View::event), their built
+/// in handling and the listeners of [Decorators::on_event](crate::views::Decorators::on_event).
+/// A view or listener handling the event keeps it from the other views under it, but the event
+/// still bubbles: the listeners of the ancestors of the view observe it on the way back up,
+/// without their built in handling, until the root or a view that stopped its propagation.
+///
+/// The state belongs to a single path from the root: what's set on the way to a view that
+/// doesn't handle the event is forgotten before the event is offered to the next one.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EventPropagation {
+    stopped: bool,
+    default_prevented: bool,
+}
+
+impl EventPropagation {
+    /// Don't deliver the event to any other view, nor to the listeners of the ancestors on the
+    /// way back up. The event counts as handled.
+    pub fn stop_propagation(&mut self) {
+        self.stopped = true;
+    }
+
+    /// Skip the built in handling of the event, like focusing a view on pointer down or
+    /// activating a click, while still delivering it to the views and their listeners.
+    pub fn prevent_default(&mut self) {
+        self.default_prevented = true;
+    }
+
+    pub fn is_propagation_stopped(&self) -> bool {
+        self.stopped
+    }
+
+    pub fn is_default_prevented(&self) -> bool {
+        self.default_prevented
+    }
+}
+
+/// Files or text dragged into the window from another application. The window backend doesn't
+/// report these drags yet, see [crate::dnd].
+#[derive(Debug, Clone)]
+pub struct FileDragEvent {
+    pub pos: Point,
+    pub paths: Vec<PathBuf>,
+    pub text: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub enum Event {
     PointerDown(PointerEvent),
     PointerUp(PointerEvent),
     PointerMove(PointerEvent),
     PointerWheel(PointerEvent),
+    /// The platform took over the pointer, e.g. a touch became a scroll gesture. Anything the
+    /// pointer started, like a drag, should be abandoned. The window backend doesn't report
+    /// this, so the window sends it for the pointers it follows when it loses the focus.
+    PointerCancel(PointerEvent),
+    /// A pointer entered a view. Sent to the `PointerEnter` listeners only.
+    PointerEnter(PointerEvent),
+    /// A pointer left a view. Sent to the `PointerLeave` listeners only.
+    PointerLeave(PointerEvent),
+    /// Files or text dragged from another application entered a view. Sent to the
+    /// `FileDragEnter` listeners only.
+    FileDragEnter(FileDragEvent),
+    FileDragOver(FileDragEvent),
+    /// The drag left a view, or the window. Sent to the `FileDragLeave` listeners only.
+    FileDragLeave(FileDragEvent),
+    /// The files or text were dropped on the view under the pointer.
+    FileDragDrop(FileDragEvent),
+    /// A gesture recognized from the pointers pressed on a view. Sent to the gesture listeners
+    /// of that view only.
+    Gesture(GestureEvent),
     KeyDown(KeyEvent),
     KeyUp(KeyEvent),
     WindowClosed,
@@ -48,6 +187,14 @@ impl Event {
             | Event::PointerUp(_)
             | Event::PointerMove(_)
             | Event::PointerWheel(_)
+            | Event::PointerCancel(_)
+            | Event::PointerEnter(_)
+            | Event::PointerLeave(_)
+            | Event::Gesture(_)
+            | Event::FileDragEnter(_)
+            | Event::FileDragOver(_)
+            | Event::FileDragLeave(_)
+            | Event::FileDragDrop(_)
             | Event::WindowClosed
             | Event::WindowResized(_)
             | Event::WindowMoved(_) => false,
@@ -56,16 +203,64 @@ impl Event {
     }
 
     pub(crate) fn is_pointer(&self) -> bool {
+        self.pointer_event().is_some()
+    }
+
+    /// Whether this is an event of a pointer other than a mouse, like a finger or a pen.
+    /// These pointers can be down at the same time, and each one tracks its own hover state.
+    pub fn is_touch(&self) -> bool {
+        self.pointer_event()
+            .map(|pointer_event| !matches!(pointer_event.pointer_type, PointerType::Mouse(_)))
+            .unwrap_or(false)
+    }
+
+    pub fn pointer_event(&self) -> Option<&PointerEvent> {
         match self {
-            Event::PointerDown(_)
-            | Event::PointerUp(_)
-            | Event::PointerMove(_)
-            | Event::PointerWheel(_) => true,
-            Event::KeyDown(_)
+            Event::PointerDown(pointer_event)
+            | Event::PointerUp(pointer_event)
+            | Event::PointerMove(pointer_event)
+            | Event::PointerWheel(pointer_event)
+            | Event::PointerCancel(pointer_event)
+            | Event::PointerEnter(pointer_event)
+            | Event::PointerLeave(pointer_event) => Some(pointer_event),
+            Event::Gesture(_)
+            | Event::FileDragEnter(_)
+            | Event::FileDragOver(_)
+            | Event::FileDragLeave(_)
+            | Event::FileDragDrop(_)
+            | Event::KeyDown(_)
             | Event::KeyUp(_)
             | Event::WindowClosed
             | Event::WindowResized(_)
-            | Event::WindowMoved(_) => false,
+            | Event::WindowMoved(_) => None,
+        }
+    }
+
+    /// The pointer that caused the event, for routing to the view that captured it.
+    pub fn pointer_id(&self) -> Option<PointerId> {
+        self.pointer_event()
+            .map(|pointer_event| pointer_event.pointer_id)
+    }
+
+    fn pointer_event_mut(&mut self) -> Option<&mut PointerEvent> {
+        match self {
+            Event::PointerDown(pointer_event)
+            | Event::PointerUp(pointer_event)
+            | Event::PointerMove(pointer_event)
+            | Event::PointerWheel(pointer_event)
+            | Event::PointerCancel(pointer_event)
+            | Event::PointerEnter(pointer_event)
+            | Event::PointerLeave(pointer_event) => Some(pointer_event),
+            Event::Gesture(_)
+            | Event::FileDragEnter(_)
+            | Event::FileDragOver(_)
+            | Event::FileDragLeave(_)
+            | Event::FileDragDrop(_)
+            | Event::KeyDown(_)
+            | Event::KeyUp(_)
+            | Event::WindowClosed
+            | Event::WindowResized(_)
+            | Event::WindowMoved(_) => None,
         }
     }
 
@@ -88,57 +283,59 @@ impl Event {
             | Event::KeyDown(_)
             | Event::KeyUp(_) => false,
             Event::PointerMove(_)
+            | Event::PointerCancel(_)
+            | Event::PointerEnter(_)
+            | Event::PointerLeave(_)
+            | Event::Gesture(_)
+            | Event::FileDragEnter(_)
+            | Event::FileDragOver(_)
+            | Event::FileDragLeave(_)
+            | Event::FileDragDrop(_)
             | Event::WindowClosed
             | Event::WindowResized(_)
             | Event::WindowMoved(_) => true,
         }
     }
 
-    pub fn point(&self) -> Option<Point> {
+    pub fn file_drag_event(&self) -> Option<&FileDragEvent> {
         match self {
-            Event::PointerDown(pointer_event)
-            | Event::PointerUp(pointer_event)
-            | Event::PointerMove(pointer_event)
-            | Event::PointerWheel(pointer_event) => Some(pointer_event.pos),
-            Event::KeyDown(_)
-            | Event::KeyUp(_)
-            | Event::WindowClosed
-            | Event::WindowResized(_)
-            | Event::WindowMoved(_) => None,
+            Event::FileDragEnter(drag_event)
+            | Event::FileDragOver(drag_event)
+            | Event::FileDragLeave(drag_event)
+            | Event::FileDragDrop(drag_event) => Some(drag_event),
+            _ => None,
         }
     }
 
+    fn pos_mut(&mut self) -> Option<&mut Point> {
+        match self {
+            Event::FileDragEnter(drag_event)
+            | Event::FileDragOver(drag_event)
+            | Event::FileDragLeave(drag_event)
+            | Event::FileDragDrop(drag_event) => Some(&mut drag_event.pos),
+            _ => self
+                .pointer_event_mut()
+                .map(|pointer_event| &mut pointer_event.pos),
+        }
+    }
+
+    pub fn point(&self) -> Option<Point> {
+        self.pointer_event()
+            .map(|pointer_event| pointer_event.pos)
+            .or_else(|| self.file_drag_event().map(|drag_event| drag_event.pos))
+    }
+
     pub fn scale(mut self, scale: f64) -> Event {
-        match &mut self {
-            Event::PointerDown(pointer_event)
-            | Event::PointerUp(pointer_event)
-            | Event::PointerMove(pointer_event)
-            | Event::PointerWheel(pointer_event) => {
-                pointer_event.pos.x /= scale;
-                pointer_event.pos.y /= scale;
-            }
-            Event::KeyDown(_)
-            | Event::KeyUp(_)
-            | Event::WindowClosed
-            | Event::WindowResized(_)
-            | Event::WindowMoved(_) => {}
+        if let Some(pos) = self.pos_mut() {
+            pos.x /= scale;
+            pos.y /= scale;
         }
         self
     }
 
     pub fn offset(mut self, offset: (f64, f64)) -> Event {
-        match &mut self {
-            Event::PointerDown(pointer_event)
-            | Event::PointerUp(pointer_event)
-            | Event::PointerMove(pointer_event)
-            | Event::PointerWheel(pointer_event) => {
-                pointer_event.pos -= offset;
-            }
-            Event::KeyDown(_)
-            | Event::KeyUp(_)
-            | Event::WindowClosed
-            | Event::WindowResized(_)
-            | Event::WindowMoved(_) => {}
+        if let Some(pos) = self.pos_mut() {
+            *pos -= offset;
         }
         self
     }
@@ -149,6 +346,14 @@ impl Event {
             Event::PointerUp(_) => Some(EventListener::PointerUp),
             Event::PointerMove(_) => Some(EventListener::PointerMove),
             Event::PointerWheel(_) => Some(EventListener::PointerWheel),
+            Event::PointerCancel(_) => Some(EventListener::PointerCancel),
+            Event::PointerEnter(_) => Some(EventListener::PointerEnter),
+            Event::PointerLeave(_) => Some(EventListener::PointerLeave),
+            Event::Gesture(gesture) => Some(gesture.kind().into()),
+            Event::FileDragEnter(_) => Some(EventListener::FileDragEnter),
+            Event::FileDragOver(_) => Some(EventListener::FileDragOver),
+            Event::FileDragLeave(_) => Some(EventListener::FileDragLeave),
+            Event::FileDragDrop(_) => Some(EventListener::FileDragDrop),
             Event::KeyDown(_) => Some(EventListener::KeyDown),
             Event::KeyUp(_) => Some(EventListener::KeyDown),
             Event::WindowClosed => Some(EventListener::WindowClosed),
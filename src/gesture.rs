@@ -0,0 +1,616 @@
+//! # Gestures
+//!
+//! Views can listen to gestures instead of raw pointer events, with
+//! [Decorators::on_tap](crate::views::Decorators::on_tap),
+//! [on_long_press](crate::views::Decorators::on_long_press),
+//! [on_pan](crate::views::Decorators::on_pan), [on_swipe](crate::views::Decorators::on_swipe)
+//! and [on_pinch](crate::views::Decorators::on_pinch).
+//!
+//! Every view with gesture listeners gets a recognizer that follows the pointers pressed on it.
+//! The recognizers of one view compete with each other: moving a pointer further than
+//! [GestureConfig::slop] turns a possible tap or long press into a pan, and a second pointer
+//! turns it into a pinch. Recognizers of different views compete too. The first view to
+//! recognize a pan, pinch or long press claims the pointers for itself, starting with the
+//! innermost view, and every other recognizer gives up on them.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use glazier::{
+    kurbo::{Point, Vec2},
+    PointerId,
+};
+
+/// How far back the velocity of a pan is measured
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GestureKind {
+    Tap,
+    LongPress,
+    Pan,
+    Swipe,
+    Pinch,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GesturePhase {
+    Began,
+    Changed,
+    Ended,
+    /// Another view won the pointers, or the platform cancelled them
+    Cancelled,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl SwipeDirection {
+    fn from_velocity(velocity: Vec2) -> Self {
+        if velocity.x.abs() >= velocity.y.abs() {
+            if velocity.x > 0.0 {
+                SwipeDirection::Right
+            } else {
+                SwipeDirection::Left
+            }
+        } else if velocity.y > 0.0 {
+            SwipeDirection::Down
+        } else {
+            SwipeDirection::Up
+        }
+    }
+}
+
+/// A recognized gesture. Positions are relative to the view that recognized it.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GestureEvent {
+    /// A pointer was pressed and lifted without moving. `count` goes up for taps in quick
+    /// succession, so a double tap is a tap with a count of 2.
+    Tap { pos: Point, count: u32 },
+    /// A pointer was held down without moving for [GestureConfig::long_press_delay].
+    LongPress { pos: Point },
+    /// A pointer is being dragged. `translation` is the distance from where it was pressed, and
+    /// `velocity` is in points per second.
+    Pan {
+        phase: GesturePhase,
+        pos: Point,
+        translation: Vec2,
+        velocity: Vec2,
+    },
+    /// A pan ended faster than [GestureConfig::swipe_velocity].
+    Swipe {
+        direction: SwipeDirection,
+        velocity: Vec2,
+    },
+    /// Two pointers are moving relative to each other. `scale` is the ratio of their distance to
+    /// their distance when the pinch began, and `rotation` is their angle since then, in radians.
+    Pinch {
+        phase: GesturePhase,
+        center: Point,
+        scale: f64,
+        rotation: f64,
+    },
+}
+
+impl GestureEvent {
+    pub fn kind(&self) -> GestureKind {
+        match self {
+            GestureEvent::Tap { .. } => GestureKind::Tap,
+            GestureEvent::LongPress { .. } => GestureKind::LongPress,
+            GestureEvent::Pan { .. } => GestureKind::Pan,
+            GestureEvent::Swipe { .. } => GestureKind::Swipe,
+            GestureEvent::Pinch { .. } => GestureKind::Pinch,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GestureConfig {
+    /// How far a pointer can move before it's no longer a tap or long press
+    pub slop: f64,
+    /// How long a pointer is held down before it's a long press
+    pub long_press_delay: Duration,
+    /// How soon after a tap the next one counts as a repeated tap
+    pub multi_tap_interval: Duration,
+    /// How fast a pan has to end, in points per second, to be a swipe
+    pub swipe_velocity: f64,
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            slop: 8.0,
+            long_press_delay: Duration::from_millis(500),
+            multi_tap_interval: Duration::from_millis(300),
+            swipe_velocity: 500.0,
+        }
+    }
+}
+
+impl GestureConfig {
+    pub fn slop(mut self, slop: f64) -> Self {
+        self.slop = slop;
+        self
+    }
+
+    pub fn long_press_delay(mut self, delay: Duration) -> Self {
+        self.long_press_delay = delay;
+        self
+    }
+
+    pub fn multi_tap_interval(mut self, interval: Duration) -> Self {
+        self.multi_tap_interval = interval;
+        self
+    }
+
+    pub fn swipe_velocity(mut self, velocity: f64) -> Self {
+        self.swipe_velocity = velocity;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+enum State {
+    Idle,
+    /// One pointer is down and no gesture has been recognized yet
+    Possible {
+        start: Point,
+        started_at: Instant,
+    },
+    /// A long press was recognized and the pointer is still down
+    LongPressed,
+    Panning {
+        start: Point,
+    },
+    Pinching {
+        start_distance: f64,
+        start_angle: f64,
+    },
+    /// The pointers that are still down can't make a gesture any more
+    Failed,
+}
+
+/// Follows the pointers pressed on one view and turns them into [GestureEvent]s.
+pub(crate) struct GestureRecognizer<P = PointerId> {
+    config: GestureConfig,
+    kinds: Vec<GestureKind>,
+    /// The pointers that are down, the first one is the one that started the gesture
+    pointers: Vec<(P, Point)>,
+    state: State,
+    /// Recent positions of the first pointer, for the pan velocity
+    samples: VecDeque<(Instant, Point)>,
+    last_tap: Option<(Instant, Point, u32)>,
+}
+
+impl<P: Copy + PartialEq> GestureRecognizer<P> {
+    pub(crate) fn new() -> Self {
+        Self {
+            config: GestureConfig::default(),
+            kinds: Vec::new(),
+            pointers: Vec::new(),
+            state: State::Idle,
+            samples: VecDeque::new(),
+            last_tap: None,
+        }
+    }
+
+    pub(crate) fn set_config(&mut self, config: GestureConfig) {
+        self.config = config;
+    }
+
+    pub(crate) fn enable(&mut self, kind: GestureKind) {
+        if !self.wants(kind) {
+            self.kinds.push(kind);
+        }
+    }
+
+    fn wants(&self, kind: GestureKind) -> bool {
+        self.kinds.contains(&kind)
+    }
+
+    /// The pointers this recognizer is following.
+    pub(crate) fn pointers(&self) -> Vec<P> {
+        self.pointers.iter().map(|(pointer, _)| *pointer).collect()
+    }
+
+    /// Whether the recognizer recognized a gesture that the pointers should belong to.
+    pub(crate) fn is_claiming(&self) -> bool {
+        matches!(
+            self.state,
+            State::LongPressed | State::Panning { .. } | State::Pinching { .. }
+        )
+    }
+
+    /// When a long press will be recognized if the pointer stays where it is.
+    pub(crate) fn long_press_deadline(&self) -> Option<Instant> {
+        match self.state {
+            State::Possible { started_at, .. } if self.wants(GestureKind::LongPress) => {
+                Some(started_at + self.config.long_press_delay)
+            }
+            _ => None,
+        }
+    }
+
+    pub(crate) fn long_press_due(&mut self, now: Instant) -> Vec<GestureEvent> {
+        match self.state {
+            State::Possible { start, .. }
+                if self
+                    .long_press_deadline()
+                    .map(|d| now >= d)
+                    .unwrap_or(false) =>
+            {
+                self.state = State::LongPressed;
+                vec![GestureEvent::LongPress { pos: start }]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn pointer_down(
+        &mut self,
+        pointer: P,
+        pos: Point,
+        now: Instant,
+    ) -> Vec<GestureEvent> {
+        if self.pointers.iter().any(|(p, _)| *p == pointer) {
+            return Vec::new();
+        }
+        self.pointers.push((pointer, pos));
+
+        match self.state {
+            State::Idle => {
+                self.state = State::Possible {
+                    start: pos,
+                    started_at: now,
+                };
+                self.samples.clear();
+                self.samples.push_back((now, pos));
+                Vec::new()
+            }
+            State::Possible { .. } | State::Panning { .. }
+                if self.pointers.len() == 2 && self.wants(GestureKind::Pinch) =>
+            {
+                let mut events = self.end_pan(GesturePhase::Cancelled);
+                let (a, b) = (self.pointers[0].1, self.pointers[1].1);
+                self.state = State::Pinching {
+                    start_distance: a.distance(b).max(f64::EPSILON),
+                    start_angle: (b - a).atan2(),
+                };
+                events.push(self.pinch(GesturePhase::Began));
+                events
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn pointer_move(
+        &mut self,
+        pointer: P,
+        pos: Point,
+        now: Instant,
+    ) -> Vec<GestureEvent> {
+        let Some(index) = self.pointers.iter().position(|(p, _)| *p == pointer) else {
+            return Vec::new();
+        };
+        self.pointers[index].1 = pos;
+
+        match self.state {
+            State::Possible { start, .. } if index == 0 => {
+                self.record(now, pos);
+                if (pos - start).hypot() <= self.config.slop {
+                    return Vec::new();
+                }
+                if self.wants(GestureKind::Pan) || self.wants(GestureKind::Swipe) {
+                    self.state = State::Panning { start };
+                    self.pan(GesturePhase::Began)
+                } else {
+                    self.state = State::Failed;
+                    Vec::new()
+                }
+            }
+            State::Panning { .. } if index == 0 => {
+                self.record(now, pos);
+                self.pan(GesturePhase::Changed)
+            }
+            State::Pinching { .. } if index < 2 => vec![self.pinch(GesturePhase::Changed)],
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn pointer_up(&mut self, pointer: P, pos: Point, now: Instant) -> Vec<GestureEvent> {
+        let Some(index) = self.pointers.iter().position(|(p, _)| *p == pointer) else {
+            return Vec::new();
+        };
+        self.pointers[index].1 = pos;
+
+        let (events, ends_gesture) = match self.state {
+            State::Possible { .. } if index == 0 => (self.tap(pos, now), true),
+            State::Panning { .. } if index == 0 => {
+                self.record(now, pos);
+                let velocity = self.velocity();
+                let mut events = self.end_pan(GesturePhase::Ended);
+                if self.wants(GestureKind::Swipe) && velocity.hypot() >= self.config.swipe_velocity
+                {
+                    events.push(GestureEvent::Swipe {
+                        direction: SwipeDirection::from_velocity(velocity),
+                        velocity,
+                    });
+                }
+                (events, true)
+            }
+            State::Pinching { .. } if index < 2 => (vec![self.pinch(GesturePhase::Ended)], true),
+            _ => (Vec::new(), index == 0),
+        };
+
+        self.pointers.remove(index);
+        if self.pointers.is_empty() {
+            self.state = State::Idle;
+        } else if ends_gesture {
+            self.state = State::Failed;
+        }
+        events
+    }
+
+    /// Abandons the pointers, e.g. because another view claimed them.
+    pub(crate) fn cancel(&mut self) -> Vec<GestureEvent> {
+        let events = match self.state {
+            State::Panning { .. } => self.end_pan(GesturePhase::Cancelled),
+            State::Pinching { .. } => vec![self.pinch(GesturePhase::Cancelled)],
+            _ => Vec::new(),
+        };
+        self.pointers.clear();
+        self.state = State::Idle;
+        events
+    }
+
+    fn tap(&mut self, pos: Point, now: Instant) -> Vec<GestureEvent> {
+        if !self.wants(GestureKind::Tap) {
+            return Vec::new();
+        }
+        let count = match self.last_tap {
+            Some((at, last_pos, count))
+                if now.saturating_duration_since(at) <= self.config.multi_tap_interval
+                    && (pos - last_pos).hypot() <= self.config.slop =>
+            {
+                count + 1
+            }
+            _ => 1,
+        };
+        self.last_tap = Some((now, pos, count));
+        vec![GestureEvent::Tap { pos, count }]
+    }
+
+    fn pan(&self, phase: GesturePhase) -> Vec<GestureEvent> {
+        match self.state {
+            State::Panning { start } if self.wants(GestureKind::Pan) => {
+                let pos = self.pointers[0].1;
+                vec![GestureEvent::Pan {
+                    phase,
+                    pos,
+                    translation: pos - start,
+                    velocity: self.velocity(),
+                }]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    fn end_pan(&mut self, phase: GesturePhase) -> Vec<GestureEvent> {
+        let events = self.pan(phase);
+        if matches!(self.state, State::Panning { .. }) {
+            self.state = State::Failed;
+        }
+        events
+    }
+
+    fn pinch(&self, phase: GesturePhase) -> GestureEvent {
+        let (a, b) = (self.pointers[0].1, self.pointers[1].1);
+        let (scale, rotation) = match self.state {
+            State::Pinching {
+                start_distance,
+                start_angle,
+            } => (
+                a.distance(b) / start_distance,
+                (b - a).atan2() - start_angle,
+            ),
+            _ => (1.0, 0.0),
+        };
+        GestureEvent::Pinch {
+            phase,
+            center: a.midpoint(b),
+            scale,
+            rotation,
+        }
+    }
+
+    fn record(&mut self, now: Instant, pos: Point) {
+        self.samples.push_back((now, pos));
+        while self.samples.len() > 2
+            && now.saturating_duration_since(self.samples[0].0) > VELOCITY_WINDOW
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    fn velocity(&self) -> Vec2 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some((first_at, first)), Some((last_at, last))) => {
+                let dt = last_at.saturating_duration_since(*first_at).as_secs_f64();
+                if dt > 0.0 {
+                    (*last - *first) / dt
+                } else {
+                    Vec2::ZERO
+                }
+            }
+            _ => Vec2::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use glazier::kurbo::{Point, Vec2};
+
+    use super::{
+        GestureConfig, GestureEvent, GestureKind, GesturePhase, GestureRecognizer, SwipeDirection,
+    };
+
+    fn recognizer(kinds: &[GestureKind]) -> GestureRecognizer<u32> {
+        let mut recognizer = GestureRecognizer::new();
+        for kind in kinds {
+            recognizer.enable(*kind);
+        }
+        recognizer
+    }
+
+    fn ms(ms: u64) -> Duration {
+        Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn taps_count_up() {
+        let mut r = recognizer(&[GestureKind::Tap]);
+        let t = Instant::now();
+        let pos = Point::new(10.0, 10.0);
+
+        r.pointer_down(0, pos, t);
+        assert_eq!(
+            r.pointer_up(0, pos, t + ms(50)),
+            vec![GestureEvent::Tap { pos, count: 1 }]
+        );
+        r.pointer_down(0, pos, t + ms(150));
+        assert_eq!(
+            r.pointer_up(0, pos, t + ms(200)),
+            vec![GestureEvent::Tap { pos, count: 2 }]
+        );
+        r.pointer_down(0, pos, t + ms(1000));
+        assert_eq!(
+            r.pointer_up(0, pos, t + ms(1050)),
+            vec![GestureEvent::Tap { pos, count: 1 }]
+        );
+    }
+
+    #[test]
+    fn long_press_beats_tap() {
+        let mut r = recognizer(&[GestureKind::Tap, GestureKind::LongPress]);
+        r.set_config(GestureConfig::default().long_press_delay(ms(400)));
+        let t = Instant::now();
+        let pos = Point::new(5.0, 5.0);
+
+        r.pointer_down(0, pos, t);
+        assert_eq!(r.long_press_deadline(), Some(t + ms(400)));
+        assert!(r.long_press_due(t + ms(300)).is_empty());
+        assert_eq!(
+            r.long_press_due(t + ms(400)),
+            vec![GestureEvent::LongPress { pos }]
+        );
+        assert!(r.is_claiming());
+        assert!(r.pointer_up(0, pos, t + ms(600)).is_empty());
+    }
+
+    #[test]
+    fn pan_then_swipe() {
+        let mut r = recognizer(&[GestureKind::Tap, GestureKind::Pan, GestureKind::Swipe]);
+        let t = Instant::now();
+
+        r.pointer_down(0, Point::ZERO, t);
+        assert!(r
+            .pointer_move(0, Point::new(4.0, 0.0), t + ms(10))
+            .is_empty());
+        let events = r.pointer_move(0, Point::new(20.0, 0.0), t + ms(20));
+        assert!(matches!(
+            events[..],
+            [GestureEvent::Pan {
+                phase: GesturePhase::Began,
+                translation,
+                ..
+            }] if translation == Vec2::new(20.0, 0.0)
+        ));
+        assert!(r.is_claiming());
+
+        let events = r.pointer_up(0, Point::new(60.0, 0.0), t + ms(60));
+        assert!(matches!(
+            events[0],
+            GestureEvent::Pan {
+                phase: GesturePhase::Ended,
+                ..
+            }
+        ));
+        assert!(matches!(
+            events[1],
+            GestureEvent::Swipe {
+                direction: SwipeDirection::Right,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn pinch_scales_and_rotates() {
+        let mut r = recognizer(&[GestureKind::Pan, GestureKind::Pinch]);
+        let t = Instant::now();
+
+        r.pointer_down(0, Point::new(0.0, 0.0), t);
+        let events = r.pointer_down(1, Point::new(10.0, 0.0), t);
+        assert_eq!(
+            events,
+            vec![GestureEvent::Pinch {
+                phase: GesturePhase::Began,
+                center: Point::new(5.0, 0.0),
+                scale: 1.0,
+                rotation: 0.0,
+            }]
+        );
+
+        let events = r.pointer_move(1, Point::new(0.0, 20.0), t + ms(10));
+        let GestureEvent::Pinch {
+            scale, rotation, ..
+        } = events[0]
+        else {
+            panic!("expected a pinch");
+        };
+        assert!((scale - 2.0).abs() < 1e-9);
+        assert!((rotation - std::f64::consts::FRAC_PI_2).abs() < 1e-9);
+
+        // lifting one finger ends the pinch without starting a pan
+        assert!(matches!(
+            r.pointer_up(0, Point::ZERO, t + ms(20))[..],
+            [GestureEvent::Pinch {
+                phase: GesturePhase::Ended,
+                ..
+            }]
+        ));
+        assert!(r
+            .pointer_move(1, Point::new(0.0, 90.0), t + ms(30))
+            .is_empty());
+    }
+
+    #[test]
+    fn cancel_abandons_pan() {
+        let mut r = recognizer(&[GestureKind::Pan]);
+        let t = Instant::now();
+
+        r.pointer_down(0, Point::ZERO, t);
+        r.pointer_move(0, Point::new(0.0, 30.0), t + ms(10));
+        assert!(matches!(
+            r.cancel()[..],
+            [GestureEvent::Pan {
+                phase: GesturePhase::Cancelled,
+                ..
+            }]
+        ));
+        assert!(r.pointers().is_empty());
+        assert!(r
+            .pointer_move(0, Point::new(0.0, 60.0), t + ms(20))
+            .is_empty());
+    }
+}
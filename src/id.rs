@@ -12,17 +12,23 @@ use std::{any::Any, cell::RefCell, collections::HashMap, num::NonZeroU64, time::
 
 use glazier::{
     kurbo::{Point, Vec2},
-    FileDialogOptions, FileInfo,
+    FileDialogOptions, FileInfo, PointerId,
 };
 
 use crate::{
     animate::Animation,
     app_handle::{StyleSelector, UpdateMessage, DEFERRED_UPDATE_MESSAGES, UPDATE_MESSAGES},
-    context::{EventCallback, ResizeCallback},
-    event::EventListener,
+    context::{CaptureCallback, CommandCallback, EventCallback, ResizeCallback},
+    dnd::{DragPayload, DragPreview, DropCallback, DropTarget},
+    event::{Event, EventListener, ListenerHandle, ListenerKey},
+    gesture::{GestureConfig, GestureKind},
+    keymap::Keymap,
     menu::Menu,
     responsive::ScreenSize,
     style::Style,
+    view::FocusScope,
+    view_signals::ViewSignals,
+    views::tooltip::Tooltip,
 };
 
 thread_local! {
@@ -109,6 +115,40 @@ impl Id {
         }
     }
 
+    /// Moves the focus to the view after this one in tab order.
+    pub fn focus_next(&self) {
+        self.move_focus(false);
+    }
+
+    /// Moves the focus to the view before this one in tab order.
+    pub fn focus_previous(&self) {
+        self.move_focus(true);
+    }
+
+    fn move_focus(&self, backwards: bool) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::MoveFocus {
+                    from: *self,
+                    backwards,
+                });
+            });
+        }
+    }
+
+    /// Focuses the first view Tab stops at inside this view.
+    pub fn focus_first_in(&self) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::FocusFirstIn(*self));
+            });
+        }
+    }
+
     pub fn request_active(&self) {
         if let Some(root) = self.root_id() {
             UPDATE_MESSAGES.with(|msgs| {
@@ -227,6 +267,36 @@ impl Id {
         }
     }
 
+    pub fn update_tab_index(&self, index: i32) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::TabIndex { id: *self, index })
+            })
+        }
+    }
+
+    pub fn update_focus_scope(&self, scope: FocusScope) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::FocusScope { id: *self, scope })
+            })
+        }
+    }
+
+    pub fn update_signals(&self, signals: ViewSignals) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::Signals { id: *self, signals })
+            })
+        }
+    }
+
     pub fn draggable(&self) {
         if let Some(root) = self.root_id() {
             UPDATE_MESSAGES.with(|msgs| {
@@ -237,6 +307,63 @@ impl Id {
         }
     }
 
+    pub fn update_drag_payload(&self, payload: impl Fn() -> DragPayload + 'static) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::DragPayload {
+                    id: *self,
+                    payload: Box::new(payload),
+                })
+            })
+        }
+    }
+
+    pub fn update_drag_preview(&self, preview: DragPreview) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::DragPreview { id: *self, preview })
+            })
+        }
+    }
+
+    /// Makes the view a drop target for payloads of `kinds`.
+    pub fn update_drop_target(&self, kinds: Vec<String>, action: Box<DropCallback>) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::DropTarget {
+                    id: *self,
+                    target: DropTarget { kinds, action },
+                })
+            })
+        }
+    }
+
+    pub(crate) fn update_tooltip(&self, tooltip: Tooltip) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::Tooltip { id: *self, tooltip })
+            })
+        }
+    }
+
+    pub fn update_tooltip_delay(&self, delay: Duration) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::TooltipDelay { id: *self, delay })
+            })
+        }
+    }
+
     pub fn update_responsive_style(&self, style: Style, size: ScreenSize) {
         if let Some(root) = self.root_id() {
             UPDATE_MESSAGES.with(|msgs| {
@@ -271,7 +398,14 @@ impl Id {
         }
     }
 
-    pub fn update_event_listener(&self, listener: EventListener, action: Box<EventCallback>) {
+    /// Adds a listener next to the ones the view already has. The listener is removed when the
+    /// returned handle is dropped.
+    pub fn update_event_listener(
+        &self,
+        listener: EventListener,
+        action: Box<EventCallback>,
+    ) -> ListenerHandle {
+        let key = ListenerKey::next();
         if let Some(root) = self.root_id() {
             UPDATE_MESSAGES.with(|msgs| {
                 let mut msgs = msgs.borrow_mut();
@@ -279,10 +413,97 @@ impl Id {
                 msgs.push(UpdateMessage::EventListener {
                     id: *self,
                     listener,
+                    key,
+                    action,
+                })
+            });
+        }
+        ListenerHandle::new(*self, key)
+    }
+
+    /// Adds a capture phase listener next to the ones the view already has. The listener is
+    /// removed when the returned handle is dropped.
+    pub fn update_capture_listener(
+        &self,
+        listener: EventListener,
+        action: Box<CaptureCallback>,
+    ) -> ListenerHandle {
+        let key = ListenerKey::next();
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::CaptureListener {
+                    id: *self,
+                    listener,
+                    key,
                     action,
                 })
             });
         }
+        ListenerHandle::new(*self, key)
+    }
+
+    /// Sends all further events of `pointer` to this view, until the pointer is lifted.
+    pub fn capture_pointer(&self, pointer: PointerId) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::CapturePointer { id: *self, pointer })
+            });
+        }
+    }
+
+    /// Releases `pointer` if this view captured it.
+    pub fn release_pointer(&self, pointer: PointerId) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::ReleasePointer { id: *self, pointer })
+            });
+        }
+    }
+
+    pub(crate) fn update_gesture(&self, kind: GestureKind) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::Gesture { id: *self, kind })
+            });
+        }
+    }
+
+    pub fn update_gesture_config(&self, config: GestureConfig) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::GestureConfig { id: *self, config })
+            });
+        }
+    }
+
+    pub(crate) fn long_press_timer(&self) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::LongPressTimer { id: *self })
+            });
+        }
+    }
+
+    pub(crate) fn remove_listener(&self, key: ListenerKey) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::RemoveListener { id: *self, key })
+            });
+        }
     }
 
     pub fn update_resize_listener(&self, action: Box<ResizeCallback>) {
@@ -295,6 +516,44 @@ impl Id {
         }
     }
 
+    pub fn update_key_context(&self, context: impl Into<String>) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::KeyContext {
+                    id: *self,
+                    context: context.into(),
+                })
+            });
+        }
+    }
+
+    pub fn update_command_handler(&self, command: impl Into<String>, action: Box<CommandCallback>) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::CommandHandler {
+                    id: *self,
+                    command: command.into(),
+                    action,
+                })
+            });
+        }
+    }
+
+    /// Replaces the keymap of the window this view is in.
+    pub fn update_keymap(&self, keymap: Keymap) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::Keymap(keymap))
+            });
+        }
+    }
+
     pub fn update_animation(&self, animation: Animation) {
         if let Some(root) = self.root_id() {
             UPDATE_MESSAGES.with(|msgs| {
@@ -347,4 +606,17 @@ impl Id {
             });
         }
     }
+
+    /// Delivers a `FileDragEnter`, `FileDragOver`, `FileDragLeave` or `FileDragDrop` event to the
+    /// window this view is in. The window backend doesn't report drags coming from other
+    /// applications, so the platform integration forwards them through here.
+    pub fn file_drag(&self, event: Event) {
+        if let Some(root) = self.root_id() {
+            UPDATE_MESSAGES.with(|msgs| {
+                let mut msgs = msgs.borrow_mut();
+                let msgs = msgs.entry(root).or_default();
+                msgs.push(UpdateMessage::FileDrag(event))
+            });
+        }
+    }
 }
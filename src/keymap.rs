@@ -0,0 +1,538 @@
+//! # Keymaps and commands
+//!
+//! Instead of matching raw [KeyEvent]s, views can react to named commands. A [Keymap] binds
+//! key sequences to commands, and views handle commands with
+//! [Decorators::on_command](crate::views::Decorators::on_command).
+//!
+//! A key sequence is one or more chords separated by spaces, like `ctrl+s` or `ctrl+k ctrl+s`.
+//! A chord is any number of modifiers (`ctrl`, `shift`, `alt` and `meta`, or `primary` for
+//! `meta` on macOS and `ctrl` elsewhere) and a key, joined with `+`.
+//!
+//! ## Scopes and precedence
+//!
+//! A binding without a context applies to the whole window. A binding in a context only applies
+//! while the focused view is inside a view that declared that context with
+//! [Decorators::key_context](crate::views::Decorators::key_context). When several bindings
+//! match, the one whose context is closest to the focused view wins, and among equally close
+//! bindings the one added last wins. The resolved command is then offered to the command
+//! handlers on the focus path, from the focused view up to the root, until one handles it.
+//!
+//! Key bindings are resolved before the focused view sees the key event, and a command handled
+//! on the focus path takes the key from it. If no handler takes the command, the key event is
+//! delivered as usual, and the built in commands, like moving the focus with Tab, only run when
+//! the focused view didn't handle the key.
+//!
+//! ## Config files
+//!
+//! [Keymap::parse] and [Keymap::load] read bindings from text, one per line:
+//!
+//! ```text
+//! # save everything
+//! ctrl+k ctrl+s = save_all
+//! # only while focus is inside a view with the "editor" key context
+//! ctrl+d = duplicate_line @editor
+//! # an empty command unbinds the keys
+//! tab =
+//! ```
+//!
+//! Loaded keymaps are usually layered on top of the defaults with [Keymap::extend], so user
+//! bindings override the built in ones.
+
+use std::{fmt, path::Path, str::FromStr};
+
+use glazier::{KbKey, KeyEvent, Modifiers};
+
+/// Moves the focus to the next keyboard navigable view.
+pub const FOCUS_NEXT: &str = "floem.focus_next";
+/// Moves the focus to the previous keyboard navigable view.
+pub const FOCUS_PREVIOUS: &str = "floem.focus_previous";
+/// Moves the focus to the next view of the group navigated with the arrow keys that has it.
+pub const FOCUS_NEXT_IN_GROUP: &str = "floem.focus_next_in_group";
+/// Moves the focus to the previous view of the group navigated with the arrow keys that has it.
+pub const FOCUS_PREVIOUS_IN_GROUP: &str = "floem.focus_previous_in_group";
+/// Prints the view tree to stdout.
+pub const DEBUG_VIEW_TREE: &str = "floem.debug_view_tree";
+
+/// Named keys, as they are written in key sequences.
+const NAMED_KEYS: &[(&str, KbKey)] = &[
+    ("tab", KbKey::Tab),
+    ("enter", KbKey::Enter),
+    ("escape", KbKey::Escape),
+    ("backspace", KbKey::Backspace),
+    ("delete", KbKey::Delete),
+    ("insert", KbKey::Insert),
+    ("up", KbKey::ArrowUp),
+    ("down", KbKey::ArrowDown),
+    ("left", KbKey::ArrowLeft),
+    ("right", KbKey::ArrowRight),
+    ("home", KbKey::Home),
+    ("end", KbKey::End),
+    ("pageup", KbKey::PageUp),
+    ("pagedown", KbKey::PageDown),
+    ("f1", KbKey::F1),
+    ("f2", KbKey::F2),
+    ("f3", KbKey::F3),
+    ("f4", KbKey::F4),
+    ("f5", KbKey::F5),
+    ("f6", KbKey::F6),
+    ("f7", KbKey::F7),
+    ("f8", KbKey::F8),
+    ("f9", KbKey::F9),
+    ("f10", KbKey::F10),
+    ("f11", KbKey::F11),
+    ("f12", KbKey::F12),
+];
+
+fn chord_modifiers() -> Modifiers {
+    Modifiers::SHIFT | Modifiers::CONTROL | Modifiers::ALT | Modifiers::META
+}
+
+/// A single key press together with the modifiers held down.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub mods: Modifiers,
+    pub key: KbKey,
+}
+
+impl KeyChord {
+    pub fn new(mods: Modifiers, key: KbKey) -> Self {
+        Self {
+            mods: mods & chord_modifiers(),
+            key: match key {
+                KbKey::Character(c) => KbKey::Character(c.to_lowercase()),
+                key => key,
+            },
+        }
+    }
+
+    /// The chord of a key press, or `None` if only a modifier key was pressed.
+    pub fn from_event(event: &KeyEvent) -> Option<Self> {
+        match event.key {
+            KbKey::Shift
+            | KbKey::Control
+            | KbKey::Alt
+            | KbKey::AltGraph
+            | KbKey::Meta
+            | KbKey::Super
+            | KbKey::Hyper
+            | KbKey::Fn
+            | KbKey::FnLock
+            | KbKey::CapsLock
+            | KbKey::NumLock
+            | KbKey::ScrollLock
+            | KbKey::Symbol
+            | KbKey::SymbolLock => None,
+            _ => Some(Self::new(event.mods, event.key.clone())),
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut mods = Modifiers::empty();
+        let mut parts = s.split('+').peekable();
+        let mut key = None;
+        while let Some(part) = parts.next() {
+            // a trailing empty part means the key itself is `+`, like in `ctrl++`
+            let (part, is_last) = if part.is_empty() && parts.peek() == Some(&"") {
+                parts.next();
+                ("+", parts.peek().is_none())
+            } else {
+                (part, parts.peek().is_none())
+            };
+            let lower = part.to_lowercase();
+            if !is_last {
+                mods |= match lower.as_str() {
+                    "ctrl" | "control" => Modifiers::CONTROL,
+                    "shift" => Modifiers::SHIFT,
+                    "alt" | "option" => Modifiers::ALT,
+                    "meta" | "cmd" | "super" => Modifiers::META,
+                    "primary" if cfg!(target_os = "macos") => Modifiers::META,
+                    "primary" => Modifiers::CONTROL,
+                    _ => return Err(format!("unknown modifier `{part}` in `{s}`")),
+                };
+                continue;
+            }
+
+            key = if let Some((_, named)) = NAMED_KEYS.iter().find(|(name, _)| *name == lower) {
+                Some(named.clone())
+            } else if lower == "space" {
+                Some(KbKey::Character(" ".to_string()))
+            } else if lower.chars().count() == 1 {
+                Some(KbKey::Character(lower))
+            } else {
+                return Err(format!("unknown key `{part}` in `{s}`"));
+            };
+        }
+
+        match key {
+            Some(key) => Ok(Self::new(mods, key)),
+            None => Err(format!("missing key in `{s}`")),
+        }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (Modifiers::CONTROL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::META, "Meta"),
+        ] {
+            if self.mods.contains(modifier) {
+                write!(f, "{name}+")?;
+            }
+        }
+        match &self.key {
+            KbKey::Character(c) if c == " " => write!(f, "Space"),
+            KbKey::Character(c) => write!(f, "{}", c.to_uppercase()),
+            key => write!(f, "{key}"),
+        }
+    }
+}
+
+/// One or more chords that have to be pressed one after the other.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeySequence(pub(crate) Vec<KeyChord>);
+
+impl KeySequence {
+    pub fn chords(&self) -> &[KeyChord] {
+        &self.0
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chords = s
+            .split_whitespace()
+            .map(KeyChord::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err("empty key sequence".to_string());
+        }
+        Ok(Self(chords))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, chord) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{chord}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct KeyBinding {
+    pub keys: KeySequence,
+    /// The command to run. An empty command unbinds the keys.
+    pub command: String,
+    /// The key context the binding is limited to, or `None` for the whole window
+    pub context: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum KeymapError {
+    Io(std::io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::Io(err) => write!(f, "failed to read keymap: {err}"),
+            KeymapError::Parse { line, message } => write!(f, "keymap line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+impl From<std::io::Error> for KeymapError {
+    fn from(err: std::io::Error) -> Self {
+        KeymapError::Io(err)
+    }
+}
+
+/// The outcome of feeding the keys pressed so far to a [Keymap].
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum KeymapMatch {
+    /// No binding starts with the keys
+    None,
+    /// The keys are the start of a longer binding
+    Pending,
+    Command(String),
+}
+
+/// A list of key bindings. See the [module docs](self) for how bindings are resolved.
+#[derive(Clone, Debug, Default)]
+pub struct Keymap {
+    bindings: Vec<KeyBinding>,
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The bindings every window starts with.
+    pub fn builtin() -> Self {
+        Self::new()
+            .bind("tab", FOCUS_NEXT)
+            .bind("shift+tab", FOCUS_PREVIOUS)
+            .bind("right", FOCUS_NEXT_IN_GROUP)
+            .bind("down", FOCUS_NEXT_IN_GROUP)
+            .bind("left", FOCUS_PREVIOUS_IN_GROUP)
+            .bind("up", FOCUS_PREVIOUS_IN_GROUP)
+            .bind("primary+shift+i", DEBUG_VIEW_TREE)
+    }
+
+    /// Binds `keys` to `command` in the whole window.
+    ///
+    /// Panics if `keys` isn't a valid key sequence.
+    pub fn bind(self, keys: &str, command: impl Into<String>) -> Self {
+        self.bind_binding(keys, command.into(), None)
+    }
+
+    /// Binds `keys` to `command` while the focus is inside a view with the key context `context`.
+    ///
+    /// Panics if `keys` isn't a valid key sequence.
+    pub fn bind_in(
+        self,
+        context: impl Into<String>,
+        keys: &str,
+        command: impl Into<String>,
+    ) -> Self {
+        self.bind_binding(keys, command.into(), Some(context.into()))
+    }
+
+    fn bind_binding(mut self, keys: &str, command: String, context: Option<String>) -> Self {
+        let keys = keys
+            .parse()
+            .unwrap_or_else(|err| panic!("invalid key binding: {err}"));
+        self.bindings.push(KeyBinding {
+            keys,
+            command,
+            context,
+        });
+        self
+    }
+
+    /// Adds the bindings of `other` after the existing ones, so they take precedence.
+    pub fn extend(&mut self, other: Keymap) {
+        self.bindings.extend(other.bindings);
+    }
+
+    pub fn bindings(&self) -> &[KeyBinding] {
+        &self.bindings
+    }
+
+    /// Parses a keymap in the config format described in the [module docs](self).
+    pub fn parse(source: &str) -> Result<Self, KeymapError> {
+        let mut bindings = Vec::new();
+        for (i, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parse_err = |message: String| KeymapError::Parse {
+                line: i + 1,
+                message,
+            };
+
+            let (keys, rest) = line
+                .split_once('=')
+                .ok_or_else(|| parse_err("expected `keys = command`".to_string()))?;
+            let keys = keys.trim().parse().map_err(parse_err)?;
+            let (command, context) = match rest.trim().split_once('@') {
+                Some((command, context)) => (command.trim(), Some(context.trim().to_string())),
+                None => (rest.trim(), None),
+            };
+            if command.contains(char::is_whitespace) {
+                return Err(parse_err(format!("invalid command `{command}`")));
+            }
+            if context.as_deref() == Some("") {
+                return Err(parse_err("missing context after `@`".to_string()));
+            }
+            bindings.push(KeyBinding {
+                keys,
+                command: command.to_string(),
+                context,
+            });
+        }
+        Ok(Self { bindings })
+    }
+
+    /// Reads and parses a keymap config file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeymapError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Resolves the chords pressed so far against the bindings.
+    ///
+    /// `contexts` are the key contexts on the focus path, from the root to the focused view.
+    pub(crate) fn resolve(&self, keys: &[KeyChord], contexts: &[&str]) -> KeymapMatch {
+        // window bindings have depth 0, context bindings the depth of the closest view with
+        // their context, and inactive bindings none
+        let binding_depth = |binding: &KeyBinding| match &binding.context {
+            None => Some(0),
+            Some(context) => contexts
+                .iter()
+                .rposition(|c| *c == context.as_str())
+                .map(|pos| pos + 1),
+        };
+
+        let mut exact: Option<(usize, &KeyBinding)> = None;
+        let mut pending: Option<usize> = None;
+        for binding in &self.bindings {
+            let Some(depth) = binding_depth(binding) else {
+                continue;
+            };
+            let chords = binding.keys.chords();
+            if chords == keys {
+                if exact.map(|(d, _)| depth >= d).unwrap_or(true) {
+                    exact = Some((depth, binding));
+                }
+            } else if chords.starts_with(keys) && pending.map(|d| depth > d).unwrap_or(true) {
+                pending = Some(depth);
+            }
+        }
+
+        match (exact, pending) {
+            (Some((exact_depth, _)), Some(pending_depth)) if pending_depth > exact_depth => {
+                KeymapMatch::Pending
+            }
+            (Some((_, binding)), _) if binding.command.is_empty() => KeymapMatch::None,
+            (Some((_, binding)), _) => KeymapMatch::Command(binding.command.clone()),
+            (None, Some(_)) => KeymapMatch::Pending,
+            (None, None) => KeymapMatch::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glazier::{KbKey, Modifiers};
+
+    use super::{KeyChord, KeySequence, Keymap, KeymapError, KeymapMatch};
+
+    fn keys(s: &str) -> Vec<KeyChord> {
+        s.parse::<KeySequence>().unwrap().0
+    }
+
+    #[test]
+    fn parse_chords() {
+        let chord: KeyChord = "Ctrl+Shift+K".parse().unwrap();
+        assert_eq!(
+            chord,
+            KeyChord::new(
+                Modifiers::CONTROL | Modifiers::SHIFT,
+                KbKey::Character("k".to_string())
+            )
+        );
+        assert_eq!(chord.to_string(), "Ctrl+Shift+K");
+
+        let chord: KeyChord = "ctrl++".parse().unwrap();
+        assert_eq!(chord.key, KbKey::Character("+".to_string()));
+        assert_eq!(
+            "alt+pagedown".parse::<KeyChord>().unwrap().key,
+            KbKey::PageDown
+        );
+
+        assert!("hyper+k".parse::<KeyChord>().is_err());
+        assert!("ctrl+".parse::<KeyChord>().is_err());
+        assert!("ctrl+nope".parse::<KeyChord>().is_err());
+        assert!("".parse::<KeySequence>().is_err());
+    }
+
+    #[test]
+    fn parse_config() {
+        let keymap = Keymap::parse(
+            "# comment\n\
+             ctrl+k ctrl+s = save_all\n\
+             \n\
+             ctrl+d = duplicate @ editor\n\
+             tab =\n",
+        )
+        .unwrap();
+        let bindings = keymap.bindings();
+        assert_eq!(bindings.len(), 3);
+        assert_eq!(bindings[0].keys.to_string(), "Ctrl+K Ctrl+S");
+        assert_eq!(bindings[0].command, "save_all");
+        assert_eq!(bindings[1].context.as_deref(), Some("editor"));
+        assert_eq!(bindings[2].command, "");
+
+        let err = Keymap::parse("ctrl+s = save\nctrl+s save").unwrap_err();
+        assert!(matches!(err, KeymapError::Parse { line: 2, .. }));
+        assert!(Keymap::parse("ctrl+s = save all").is_err());
+        assert!(Keymap::parse("ctrl+s = save @").is_err());
+    }
+
+    #[test]
+    fn resolve_sequences() {
+        let keymap = Keymap::new()
+            .bind("ctrl+k ctrl+s", "save_all")
+            .bind("ctrl+s", "save");
+
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+s"), &[]),
+            KeymapMatch::Command("save".into())
+        );
+        assert_eq!(keymap.resolve(&keys("ctrl+k"), &[]), KeymapMatch::Pending);
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+k ctrl+s"), &[]),
+            KeymapMatch::Command("save_all".into())
+        );
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+k ctrl+x"), &[]),
+            KeymapMatch::None
+        );
+    }
+
+    #[test]
+    fn resolve_precedence() {
+        let mut keymap = Keymap::new()
+            .bind("ctrl+d", "window")
+            .bind_in("panel", "ctrl+d", "panel")
+            .bind_in("editor", "ctrl+d", "editor")
+            .bind_in("editor", "ctrl+g", "goto");
+
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+d"), &[]),
+            KeymapMatch::Command("window".into())
+        );
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+d"), &["panel"]),
+            KeymapMatch::Command("panel".into())
+        );
+        // the context closest to the focused view wins, whatever the binding order
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+d"), &["editor", "panel"]),
+            KeymapMatch::Command("panel".into())
+        );
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+g"), &["panel"]),
+            KeymapMatch::None
+        );
+
+        // later bindings override earlier ones, and empty commands unbind
+        keymap.extend(Keymap::parse("ctrl+d = remapped\nctrl+g = @editor").unwrap());
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+d"), &[]),
+            KeymapMatch::Command("remapped".into())
+        );
+        assert_eq!(
+            keymap.resolve(&keys("ctrl+g"), &["editor"]),
+            KeymapMatch::None
+        );
+    }
+}
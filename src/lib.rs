@@ -95,6 +95,9 @@
 //! Affects pointer events. Pointer events will only be sent to the active View. The View will continue to receive pointer events even if the mouse is outside its bounds.
 //! It is useful when you drag things, e.g. the scroll bar, you set the scroll bar active after pointer down, then when you drag, the `PointerMove` will always be sent to the View, even if your mouse is outside of the view.
 //!
+//! With several pointers down at once, like two fingers on a touch screen, each pointer can be captured by a different view
+//! with [EventCx::capture_pointer](context::EventCx::capture_pointer). All events of a captured pointer go to the view that captured it, until the pointer is lifted.
+//!
 //! #### Focused view
 //! Affects keyboard events. Keyboard events will only be sent to the focused View. The View will continue to receive keyboard events even if it's not the active View.
 //!
@@ -116,18 +119,24 @@
 //! See the [Style module](style) for more info.
 //!
 //!
+pub mod accessibility;
 pub mod animate;
 mod app;
 mod app_handle;
 pub mod context;
+pub mod dnd;
 pub mod event;
 pub mod ext_event;
+pub mod gesture;
 pub mod id;
+pub mod keymap;
 pub mod menu;
+pub mod overlay;
 pub mod renderer;
 pub mod responsive;
 pub mod style;
 pub mod view;
+pub mod view_signals;
 pub mod view_tuple;
 pub mod views;
 pub mod window;
@@ -0,0 +1,666 @@
+//! # Overlays
+//!
+//! The overlay layer of a window floats above the view of the window. Views in it are laid out
+//! on their own, next to an anchor in window coordinates, so they aren't clipped by a
+//! [Scroll](crate::views::Scroll) or any other ancestor of the view that opened them.
+//!
+//! A [portal](crate::views::portal) shows a view in the overlay layer while it's open, placed
+//! next to the portal with a [Placement]. [Tooltips](crate::views::Decorators::tooltip) are
+//! shown there too, and so is the dialog of a [modal](crate::views::modal), over a backdrop
+//! covering the window.
+//!
+//! Overlays get pointer events before the rest of the window, and the views beneath an overlay
+//! don't get the events of pointers over it.
+
+use std::{any::Any, rc::Rc};
+
+use glazier::kurbo::{Point, Rect, Size};
+use leptos_reactive::{Scope, ScopeDisposer};
+use taffy::prelude::Node;
+use vello::peniko::Color;
+
+use crate::{
+    app_handle::{StyleSelector, ViewContext},
+    context::{AppState, EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::Style,
+    view::{view_first_focus_in, ChangeFlags, View},
+};
+
+/// Overlays are painted above everything in the window, drag previews included.
+pub(crate) const OVERLAY_Z_INDEX: i32 = 2000;
+
+/// The side of its anchor an overlay goes on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Side {
+    fn opposite(self) -> Self {
+        match self {
+            Side::Top => Side::Bottom,
+            Side::Bottom => Side::Top,
+            Side::Left => Side::Right,
+            Side::Right => Side::Left,
+        }
+    }
+}
+
+/// How an overlay lines up with its anchor along the side it's on. `Start` lines up the left
+/// or top edges.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+}
+
+/// Where an overlay goes next to its anchor.
+///
+/// An overlay that doesn't fit on its side flips to the opposite side if it fits there, and
+/// it's shifted to stay inside the window. Both can be turned off.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Placement {
+    side: Side,
+    align: Align,
+    gap: f64,
+    flip: bool,
+    shift: bool,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Self::new(Side::Bottom, Align::Start)
+    }
+}
+
+impl Placement {
+    pub fn new(side: Side, align: Align) -> Self {
+        Self {
+            side,
+            align,
+            gap: 0.0,
+            flip: true,
+            shift: true,
+        }
+    }
+
+    /// The space between the anchor and the overlay.
+    pub fn gap(mut self, gap: f64) -> Self {
+        self.gap = gap;
+        self
+    }
+
+    pub fn flip(mut self, flip: bool) -> Self {
+        self.flip = flip;
+        self
+    }
+
+    pub fn shift(mut self, shift: bool) -> Self {
+        self.shift = shift;
+        self
+    }
+
+    /// The origin of a box of `size` next to `anchor`, in a window of `bounds`.
+    pub fn place(&self, anchor: Rect, size: Size, bounds: Size) -> Point {
+        let fits = |side: Side| match side {
+            Side::Top => anchor.y0 - self.gap - size.height >= 0.0,
+            Side::Bottom => anchor.y1 + self.gap + size.height <= bounds.height,
+            Side::Left => anchor.x0 - self.gap - size.width >= 0.0,
+            Side::Right => anchor.x1 + self.gap + size.width <= bounds.width,
+        };
+        let side = if self.flip && !fits(self.side) && fits(self.side.opposite()) {
+            self.side.opposite()
+        } else {
+            self.side
+        };
+        let align = |start: f64, end: f64, length: f64| match self.align {
+            Align::Start => start,
+            Align::Center => (start + end - length) / 2.0,
+            Align::End => end - length,
+        };
+        let origin = match side {
+            Side::Top => Point::new(
+                align(anchor.x0, anchor.x1, size.width),
+                anchor.y0 - self.gap - size.height,
+            ),
+            Side::Bottom => Point::new(
+                align(anchor.x0, anchor.x1, size.width),
+                anchor.y1 + self.gap,
+            ),
+            Side::Left => Point::new(
+                anchor.x0 - self.gap - size.width,
+                align(anchor.y0, anchor.y1, size.height),
+            ),
+            Side::Right => Point::new(
+                anchor.x1 + self.gap,
+                align(anchor.y0, anchor.y1, size.height),
+            ),
+        };
+        if self.shift {
+            Point::new(
+                origin.x.min(bounds.width - size.width).max(0.0),
+                origin.y.min(bounds.height - size.height).max(0.0),
+            )
+        } else {
+            origin
+        }
+    }
+}
+
+/// What an overlay is placed next to.
+pub(crate) enum OverlayAnchor {
+    /// A rect in window coordinates
+    Rect(Rect),
+    /// A view, followed wherever it's laid out
+    View(Id),
+}
+
+/// A view in the overlay layer, with the reactive scope it was created in.
+pub(crate) struct Overlay {
+    id: Id,
+    anchor: OverlayAnchor,
+    /// The rect of the anchor from the last layout
+    anchor_rect: Rect,
+    placement: Placement,
+    origin: Point,
+    /// The overlay is removed along with this view
+    owner: Option<Id>,
+    /// Pointer events go through to the views beneath
+    passthrough: bool,
+    on_click_outside: Option<Rc<dyn Fn()>>,
+    /// The overlay covers the window with this color, with its content in the middle
+    backdrop: Option<Color>,
+    on_backdrop_click: Option<Rc<dyn Fn()>>,
+    on_escape: Option<Rc<dyn Fn()>>,
+    /// Tab navigation stays inside the overlay
+    traps_focus: bool,
+    /// The focus from before the overlay trapped it, given back when it's removed
+    restore_focus: Option<Id>,
+    child: Box<dyn View>,
+    disposer: Option<ScopeDisposer>,
+}
+
+impl Overlay {
+    /// Creates `child` in a child scope of `scope`, as an overlay in the window of `root`.
+    pub(crate) fn new(
+        root: Id,
+        scope: Scope,
+        anchor: OverlayAnchor,
+        child: impl FnOnce() -> Box<dyn View>,
+    ) -> Self {
+        let ((id, child), disposer) = scope.run_child_scope(|scope| {
+            ViewContext::save();
+            ViewContext::set_current(ViewContext { scope, id: root });
+            let overlay = ViewContext::new_id_with_child(child);
+            ViewContext::restore();
+            overlay
+        });
+        let anchor_rect = match anchor {
+            OverlayAnchor::Rect(rect) => rect,
+            OverlayAnchor::View(_) => Rect::ZERO,
+        };
+        Self {
+            id,
+            anchor,
+            anchor_rect,
+            placement: Placement::default(),
+            origin: anchor_rect.origin(),
+            owner: None,
+            passthrough: false,
+            on_click_outside: None,
+            backdrop: None,
+            on_backdrop_click: None,
+            on_escape: None,
+            traps_focus: false,
+            restore_focus: None,
+            child,
+            disposer: Some(disposer),
+        }
+    }
+
+    pub(crate) fn placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub(crate) fn owner(mut self, owner: Id) -> Self {
+        self.owner = Some(owner);
+        self
+    }
+
+    pub(crate) fn passthrough(mut self) -> Self {
+        self.passthrough = true;
+        self
+    }
+
+    pub(crate) fn on_click_outside(mut self, action: Option<Rc<dyn Fn()>>) -> Self {
+        self.on_click_outside = action;
+        self
+    }
+
+    pub(crate) fn backdrop(mut self, color: Color) -> Self {
+        self.backdrop = Some(color);
+        self
+    }
+
+    pub(crate) fn on_backdrop_click(mut self, action: Option<Rc<dyn Fn()>>) -> Self {
+        self.on_backdrop_click = action;
+        self
+    }
+
+    /// Called when Escape isn't handled by the focused view, if this is the topmost overlay
+    /// with an Escape action.
+    pub(crate) fn on_escape(mut self, action: Option<Rc<dyn Fn()>>) -> Self {
+        self.on_escape = action;
+        self
+    }
+
+    pub(crate) fn trap_focus(mut self) -> Self {
+        self.traps_focus = true;
+        self
+    }
+
+    fn dispose(mut self, app_state: &mut AppState) {
+        self.cleanup(app_state);
+        if let Some(disposer) = self.disposer.take() {
+            disposer.dispose();
+        }
+    }
+}
+
+impl View for Overlay {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        if let Some(backdrop) = self.backdrop {
+            return Some(
+                Style::BASE
+                    .absolute()
+                    .inset_px(0.0)
+                    .background(backdrop)
+                    .items_center()
+                    .justify_center(),
+            );
+        }
+        Some(
+            Style::BASE
+                .absolute()
+                .inset_left_px(self.origin.x as f32)
+                .inset_top_px(self.origin.y as f32),
+        )
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.child.id() == id {
+            Some(&*self.child)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.child.id() == id {
+            Some(&mut *self.child)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&*self.child]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut *self.child]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Overlay".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, true, |cx| vec![self.child.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        Some(self.child.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        if cx.should_send(self.child.id(), &event) {
+            self.child.event_main(cx, id_path, event)
+        } else if let (Event::PointerDown(_), Some(action)) = (&event, &self.on_backdrop_click) {
+            action();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.child.paint_main(cx);
+    }
+}
+
+/// Sent to the [WindowRoot] of a window to change its overlays.
+pub(crate) enum OverlayUpdate {
+    Mount(Overlay),
+    Unmount(Id),
+    /// The hover delay of a tooltip is over, see
+    /// [TooltipState](crate::views::tooltip::TooltipState).
+    ShowTooltip {
+        generation: u64,
+    },
+}
+
+/// The view of a window, which holds the view of the app and the overlay layer.
+///
+/// The overlay layer is laid out as its own tree the size of the window. The app view is laid
+/// out exactly as if it were the root.
+pub(crate) struct WindowRoot<V: View> {
+    id: Id,
+    main: V,
+    overlays: Vec<Overlay>,
+}
+
+impl<V: View> WindowRoot<V> {
+    pub(crate) fn new(main: impl FnOnce() -> V) -> Self {
+        let (id, main) = ViewContext::new_id_with_child(main);
+        Self {
+            id,
+            main,
+            overlays: Vec::new(),
+        }
+    }
+
+    /// The root of the app view.
+    pub(crate) fn main_id(&self) -> Id {
+        self.main.id()
+    }
+
+    fn mount(&mut self, app_state: &mut AppState, mut overlay: Overlay) {
+        if overlay.traps_focus {
+            overlay.restore_focus = app_state.focus;
+            let focus = view_first_focus_in(&overlay, app_state, overlay.id);
+            move_focus(app_state, focus);
+        }
+        self.overlays.push(overlay);
+    }
+
+    fn unmount(&mut self, app_state: &mut AppState, id: Id) {
+        let Some(index) = self.overlays.iter().position(|overlay| overlay.id == id) else {
+            return;
+        };
+        let overlay = self.overlays.remove(index);
+        let focus_inside = app_state
+            .focus
+            .and_then(|focus| focus.id_path())
+            .map(|path| path.0.contains(&id))
+            .unwrap_or(true);
+        if overlay.traps_focus && focus_inside {
+            let focus = overlay.restore_focus.filter(|id| id.has_id_path());
+            move_focus(app_state, focus);
+        }
+        overlay.dispose(app_state);
+    }
+
+    /// The topmost overlay that keeps tab navigation inside it.
+    pub(crate) fn focus_scope(&self) -> Option<&dyn View> {
+        self.overlays
+            .iter()
+            .rev()
+            .find(|overlay| overlay.traps_focus)
+            .map(|overlay| overlay as &dyn View)
+    }
+
+    /// Gives an Escape no view handled to the topmost overlay with an Escape action.
+    pub(crate) fn escape(&self) -> bool {
+        let action = self
+            .overlays
+            .iter()
+            .rev()
+            .find_map(|overlay| overlay.on_escape.as_ref());
+        if let Some(action) = action {
+            action();
+        }
+        action.is_some()
+    }
+}
+
+fn move_focus(app_state: &mut AppState, focus: Option<Id>) {
+    app_state.clear_focus();
+    if let Some(id) = focus {
+        let keyboard_navigation = app_state.keyboard_navigation;
+        app_state.update_focus(id, keyboard_navigation);
+        // To apply the styles of the Focus selector
+        if app_state.has_style_for_sel(id, StyleSelector::Focus) {
+            app_state.request_layout(id);
+        }
+    }
+}
+
+impl<V: View> View for WindowRoot<V> {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::BASE.size_pct(100.0, 100.0))
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.main.id() == id {
+            return Some(&self.main);
+        }
+        self.overlays
+            .iter()
+            .find(|overlay| overlay.id == id)
+            .map(|overlay| overlay as &dyn View)
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.main.id() == id {
+            return Some(&mut self.main);
+        }
+        self.overlays
+            .iter_mut()
+            .find(|overlay| overlay.id == id)
+            .map(|overlay| overlay as &mut dyn View)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        let mut children = vec![&self.main as &dyn View];
+        children.extend(self.overlays.iter().map(|overlay| overlay as &dyn View));
+        children
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        let mut children = vec![&mut self.main as &mut dyn View];
+        children.extend(
+            self.overlays
+                .iter_mut()
+                .map(|overlay| overlay as &mut dyn View),
+        );
+        children
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "WindowRoot".into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) -> ChangeFlags {
+        let Ok(update) = state.downcast::<OverlayUpdate>() else {
+            return ChangeFlags::empty();
+        };
+        match *update {
+            OverlayUpdate::Mount(overlay) => {
+                self.mount(cx.app_state, overlay);
+            }
+            OverlayUpdate::Unmount(id) => {
+                self.unmount(cx.app_state, id);
+            }
+            OverlayUpdate::ShowTooltip { generation } => {
+                if let Some(overlay) = cx.app_state.tooltip_due(self.id, generation) {
+                    self.overlays.push(overlay);
+                }
+            }
+        }
+        cx.request_layout(self.id);
+        ChangeFlags::LAYOUT
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        let removed: Vec<Id> = self
+            .overlays
+            .iter()
+            .filter(|overlay| overlay.owner.map(|id| !id.has_id_path()).unwrap_or(false))
+            .map(|overlay| overlay.id)
+            .collect();
+        for id in removed {
+            // the view the overlay belongs to was removed
+            self.unmount(cx.app_state_mut(), id);
+            cx.app_state_mut().view_state(self.id).request_layout = true;
+        }
+
+        let layer = cx.layout_node(self.id, true, |cx| {
+            self.overlays
+                .iter_mut()
+                .map(|overlay| overlay.layout_main(cx))
+                .collect()
+        });
+        cx.app_state_mut().overlay_root = Some(layer);
+        self.main.layout_main(cx)
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let mut layout_rect = self.main.compute_layout_main(cx);
+        let bounds = cx.app_state().window_size();
+        for overlay in &mut self.overlays {
+            if overlay.backdrop.is_some() {
+                // it covers the window
+                layout_rect = layout_rect.union(overlay.compute_layout_main(cx));
+                continue;
+            }
+            if let OverlayAnchor::View(id) = overlay.anchor {
+                overlay.anchor_rect = cx.app_state_mut().get_layout_rect(id);
+            }
+            let size = cx
+                .app_state()
+                .get_layout(overlay.id)
+                .map(|layout| Size::new(layout.size.width as f64, layout.size.height as f64))
+                .unwrap_or_default();
+            let origin = overlay.placement.place(overlay.anchor_rect, size, bounds);
+            if origin != overlay.origin {
+                // the overlay is measured before it can be placed, so it's laid out again
+                overlay.origin = origin;
+                cx.app_state_mut().request_layout(overlay.id);
+            }
+            layout_rect = layout_rect.union(overlay.compute_layout_main(cx));
+        }
+        Some(layout_rect)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        if id_path.is_some() {
+            // an event for a view inside was already sent to it by `event_main`
+            return false;
+        }
+        if let Event::PointerDown(pointer_event) = &event {
+            let pos = pointer_event.pos;
+            let rects: Vec<Option<Rect>> = self
+                .overlays
+                .iter()
+                .map(|overlay| {
+                    (!overlay.passthrough).then(|| cx.app_state.get_layout_rect(overlay.id))
+                })
+                .collect();
+            for (i, overlay) in self.overlays.iter().enumerate() {
+                let Some(action) = overlay.on_click_outside.as_ref() else {
+                    continue;
+                };
+                // a click in an overlay above, like a submenu, isn't outside
+                let inside = rects[i..].iter().flatten().any(|rect| rect.contains(pos));
+                if !inside && !overlay.anchor_rect.contains(pos) {
+                    action();
+                }
+            }
+        }
+        for overlay in self.overlays.iter_mut().rev() {
+            if overlay.passthrough || !cx.should_send(overlay.id, &event) {
+                continue;
+            }
+            if overlay.event_main(cx, None, event.clone()) {
+                return true;
+            }
+            if event.point().is_some() {
+                // the views beneath the overlay don't get the events of pointers over it
+                return false;
+            }
+        }
+        self.main.event_main(cx, None, event)
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.main.paint_main(cx);
+        for overlay in &mut self.overlays {
+            cx.save();
+            cx.set_z_index(OVERLAY_Z_INDEX);
+            overlay.paint_main(cx);
+            cx.restore();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glazier::kurbo::{Point, Rect, Size};
+
+    use super::{Align, Placement, Side};
+
+    #[test]
+    fn flips_to_the_side_with_room() {
+        let bounds = Size::new(200.0, 100.0);
+        let size = Size::new(50.0, 30.0);
+        let placement = Placement::new(Side::Bottom, Align::Start).gap(2.0);
+        let below = placement.place(Rect::new(10.0, 10.0, 40.0, 20.0), size, bounds);
+        assert_eq!(below, Point::new(10.0, 22.0));
+        let above = placement.place(Rect::new(10.0, 80.0, 40.0, 90.0), size, bounds);
+        assert_eq!(above, Point::new(10.0, 48.0));
+        let unflipped = placement.flip(false).shift(false).place(
+            Rect::new(10.0, 80.0, 40.0, 90.0),
+            size,
+            bounds,
+        );
+        assert_eq!(unflipped, Point::new(10.0, 92.0));
+    }
+
+    #[test]
+    fn aligns_and_shifts_into_bounds() {
+        let bounds = Size::new(200.0, 100.0);
+        let size = Size::new(50.0, 30.0);
+        let centered = Placement::new(Side::Right, Align::Center).place(
+            Rect::new(10.0, 40.0, 40.0, 60.0),
+            size,
+            bounds,
+        );
+        assert_eq!(centered, Point::new(40.0, 35.0));
+        let shifted = Placement::new(Side::Bottom, Align::Start).place(
+            Rect::new(180.0, 10.0, 190.0, 20.0),
+            size,
+            bounds,
+        );
+        assert_eq!(shifted, Point::new(150.0, 20.0));
+    }
+}
@@ -49,6 +49,10 @@ pub enum CursorStyle {
     Default,
     Pointer,
     Text,
+    /// For resizing columns, or anything else side to side
+    ColResize,
+    /// For resizing rows, or anything else up and down
+    RowResize,
 }
 
 /// The value for a [`Style`] property
@@ -101,6 +105,9 @@ impl<T> From<T> for StyleValue<T> {
     }
 }
 
+/// The font size of text when no style sets one
+pub(crate) const DEFAULT_FONT_SIZE: f32 = 14.0;
+
 // Creates `ComputedStyle` which has definite values for the fields, barring some specific cases.
 // Creates `Style` which has `StyleValue<T>`s for the fields
 macro_rules! define_styles {
@@ -550,6 +557,33 @@ impl Style {
             .inset_bottom_pct(inset)
     }
 
+    /// Sets the gap between columns, keeping the gap between rows
+    pub fn column_gap(mut self, width: LengthPercentage) -> Self {
+        let gap = self.gap.unwrap_or(Size::zero());
+        self.gap = StyleValue::Val(Size { width, ..gap });
+        self
+    }
+
+    /// Sets the gap between rows, keeping the gap between columns
+    pub fn row_gap(mut self, height: LengthPercentage) -> Self {
+        let gap = self.gap.unwrap_or(Size::zero());
+        self.gap = StyleValue::Val(Size { height, ..gap });
+        self
+    }
+
+    pub fn column_gap_px(self, gap: f32) -> Self {
+        self.column_gap(LengthPercentage::Points(gap))
+    }
+
+    pub fn row_gap_px(self, gap: f32) -> Self {
+        self.row_gap(LengthPercentage::Points(gap))
+    }
+
+    /// Sets the gap between both rows and columns
+    pub fn gap_px(self, gap: f32) -> Self {
+        self.column_gap_px(gap).row_gap_px(gap)
+    }
+
     pub fn cursor(mut self, cursor: impl Into<StyleValue<CursorStyle>>) -> Self {
         self.cursor = cursor.into().map(Some);
         self
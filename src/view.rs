@@ -93,10 +93,15 @@ use std::any::Any;
 
 use bitflags::bitflags;
 use floem_renderer::Renderer;
-use glazier::kurbo::{Affine, Circle, Line, Point, Rect, Size};
+use glazier::{
+    kurbo::{Affine, Circle, Line, Point, Rect, Size},
+    PointerType,
+};
 use taffy::prelude::Node;
 
 use crate::{
+    accessibility::AccessNode,
+    animate::Clock,
     context::{AppState, DragState, EventCx, LayoutCx, PaintCx, UpdateCx},
     event::{Event, EventListener},
     id::Id,
@@ -145,12 +150,19 @@ pub trait View {
         let _ = app_state.taffy.remove(node);
         id.remove_id_path();
         app_state.view_states.remove(&id);
+        app_state.signal_views.remove(&id);
     }
 
     fn debug_name(&self) -> std::borrow::Cow<'static, str> {
         core::any::type_name::<Self>().into()
     }
 
+    /// What the view reports to assistive technologies, see [crate::accessibility]. Views that
+    /// aren't controls report nothing.
+    fn accessibility(&self) -> Vec<AccessNode> {
+        Vec::new()
+    }
+
     /// Used internally by Floem to send an update to the correct view based on the `Id` path.
     /// It will invoke only once `update` when the correct view is located.
     ///
@@ -333,226 +345,14 @@ pub trait View {
         // of the view and the viewport of the view if it's in a scroll.
         let event = cx.offset_event(self.id(), event);
 
-        // if there's id_path, it's an event only for a view.
-        if let Some(id_path) = id_path {
-            if id_path.is_empty() {
-                // this happens when the parent is the destination,
-                // but the parent just passed the event on,
-                // so it's not really for this view and we stop
-                // the event propagation.
-                return false;
-            }
-
-            let id = id_path[0];
-            let id_path = &id_path[1..];
-
-            if id != self.id() {
-                // This shouldn't happen
-                return false;
-            }
-
-            // we're the parent of the event destination, so pass it on to the child
-            if !id_path.is_empty() {
-                if let Some(child) = self.child_mut(id_path[0]) {
-                    if child.event_main(cx, Some(id_path), event.clone()) {
-                        return true;
-                    }
-                } else {
-                    // we don't have the child, stop the event propagation
-                    return false;
-                }
-            }
-        }
-
-        // if the event was dispatched to an id_path, the event is supposed to be only
-        // handled by this view only, so we pass an empty id_path
-        // and the event propagation would be stopped at this view
-        if self.event(
-            cx,
-            if id_path.is_some() { Some(&[]) } else { None },
-            event.clone(),
-        ) {
-            return true;
+        // the propagation state set on the way to views that don't handle the event is forgotten,
+        // so that it doesn't reach the views the event is offered to next
+        let propagation = cx.propagation;
+        let handled = dispatch_event(self, cx, id_path, event);
+        if !handled {
+            cx.propagation = propagation;
         }
-
-        match &event {
-            Event::PointerDown(event) => {
-                if event.button.is_left() {
-                    let rect = cx.get_size(self.id()).unwrap_or_default().to_rect();
-                    let now_focused = rect.contains(event.pos);
-
-                    if now_focused {
-                        if cx.app_state.keyboard_navigable.contains(&id) {
-                            // if the view can be focused, we update the focus
-                            cx.app_state.update_focus(id, false);
-                        }
-                        if event.count == 2 && cx.has_event_listener(id, EventListener::DoubleClick)
-                        {
-                            let view_state = cx.app_state.view_state(id);
-                            view_state.last_pointer_down = Some(event.clone());
-                            cx.update_active(id);
-                        }
-                        if cx.has_event_listener(id, EventListener::Click) {
-                            let view_state = cx.app_state.view_state(id);
-                            view_state.last_pointer_down = Some(event.clone());
-                            cx.update_active(id);
-                        }
-                        if cx.app_state.draggable.contains(&id) && cx.app_state.drag_start.is_none()
-                        {
-                            cx.app_state.drag_start = Some((id, event.pos));
-                        }
-                    }
-                }
-            }
-            Event::PointerMove(pointer_event) => {
-                let rect = cx.get_size(id).unwrap_or_default().to_rect();
-                if rect.contains(pointer_event.pos) {
-                    if cx.app_state.is_dragging() {
-                        cx.app_state.dragging_over.insert(id);
-                        if let Some(action) = cx.get_event_listener(id, &EventListener::DragOver) {
-                            (*action)(&event);
-                        }
-                    } else {
-                        cx.app_state.hovered.insert(id);
-                        let style = cx.app_state.get_computed_style(id);
-                        if let Some(cursor) = style.cursor {
-                            if cx.app_state.cursor.is_none() {
-                                cx.app_state.cursor = Some(cursor);
-                            }
-                        }
-                    }
-                }
-                if cx.app_state.draggable.contains(&id) {
-                    if let Some((_, drag_start)) = cx
-                        .app_state
-                        .drag_start
-                        .as_ref()
-                        .filter(|(drag_id, _)| drag_id == &id)
-                    {
-                        let vec2 = pointer_event.pos - *drag_start;
-
-                        if let Some(dragging) = cx
-                            .app_state
-                            .dragging
-                            .as_mut()
-                            .filter(|d| d.id == id && d.released_at.is_none())
-                        {
-                            // update the dragging offset if the view is dragging and not released
-                            dragging.offset = vec2;
-                            id.request_paint();
-                        } else if vec2.x.abs() + vec2.y.abs() > 1.0 {
-                            // start dragging when moved 1 px
-                            cx.app_state.active = None;
-                            cx.update_active(id);
-                            cx.app_state.dragging = Some(DragState {
-                                id,
-                                offset: vec2,
-                                released_at: None,
-                            });
-                            id.request_paint();
-                            if let Some(action) =
-                                cx.get_event_listener(id, &EventListener::DragStart)
-                            {
-                                (*action)(&event);
-                            }
-                        }
-                    }
-                }
-            }
-            Event::PointerUp(pointer_event) => {
-                if pointer_event.button.is_left() {
-                    let rect = cx.get_size(self.id()).unwrap_or_default().to_rect();
-                    let on_view = rect.contains(pointer_event.pos);
-
-                    if id_path.is_none() {
-                        if on_view {
-                            if let Some(dragging) = cx.app_state.dragging.as_mut() {
-                                let dragging_id = dragging.id;
-                                if let Some(action) =
-                                    cx.get_event_listener(id, &EventListener::Drop)
-                                {
-                                    if (*action)(&event) {
-                                        // if the drop is processed, we set dragging to none so that the animation
-                                        // for the dragged view back to its original position isn't played.
-                                        cx.app_state.dragging = None;
-                                        id.request_paint();
-                                        if let Some(action) = cx.get_event_listener(
-                                            dragging_id,
-                                            &EventListener::DragEnd,
-                                        ) {
-                                            (*action)(&event);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    } else {
-                        if let Some(dragging) =
-                            cx.app_state.dragging.as_mut().filter(|d| d.id == id)
-                        {
-                            let dragging_id = dragging.id;
-                            dragging.released_at = Some(std::time::Instant::now());
-                            id.request_paint();
-                            if let Some(action) =
-                                cx.get_event_listener(dragging_id, &EventListener::DragEnd)
-                            {
-                                (*action)(&event);
-                            }
-                        }
-                        let last_pointer_down =
-                            cx.app_state.view_state(id).last_pointer_down.take();
-                        if let Some(action) = cx.get_event_listener(id, &EventListener::DoubleClick)
-                        {
-                            if on_view
-                                && last_pointer_down
-                                    .as_ref()
-                                    .map(|e| e.count == 2)
-                                    .unwrap_or(false)
-                                && (*action)(&event)
-                            {
-                                return true;
-                            }
-                        }
-                        if let Some(action) = cx.get_event_listener(id, &EventListener::Click) {
-                            if on_view && last_pointer_down.is_some() && (*action)(&event) {
-                                return true;
-                            }
-                        }
-                    }
-                }
-            }
-            Event::KeyDown(_) => {
-                if cx.app_state.is_focused(&id) && event.is_keyboard_trigger() {
-                    if let Some(action) = cx.get_event_listener(id, &EventListener::Click) {
-                        (*action)(&event);
-                    }
-                }
-            }
-            Event::WindowResized(_) => {
-                if let Some(view_state) = cx.app_state.view_states.get(&self.id()) {
-                    if !view_state.responsive_styles.is_empty() {
-                        cx.app_state.request_layout(self.id());
-                    }
-                }
-            }
-            _ => (),
-        }
-
-        if let Some(listener) = event.listener() {
-            if let Some(action) = cx.get_event_listener(self.id(), &listener) {
-                let should_run = if let Some(pos) = event.point() {
-                    let rect = cx.get_size(self.id()).unwrap_or_default().to_rect();
-                    rect.contains(pos)
-                } else {
-                    true
-                };
-                if should_run && (*action)(&event) {
-                    return true;
-                }
-            }
-        }
-
-        false
+        handled
     }
 
     /// Implement this to handle events and to pass them down to children
@@ -617,15 +417,11 @@ pub trait View {
                 let mut offset_scale = None;
                 if let Some(released_at) = dragging.released_at {
                     const LIMIT: f64 = 300.0;
-                    let elapsed = released_at.elapsed().as_millis() as f64;
+                    let elapsed = Clock::elapsed_since(released_at).as_millis() as f64;
                     if elapsed < LIMIT {
+                        // the next frame is scheduled by the animation frame loop
+                        // for as long as a released drag is in flight
                         offset_scale = Some(1.0 - elapsed / LIMIT);
-                        cx.app_state.request_timer(
-                            std::time::Duration::from_millis(8),
-                            Box::new(move || {
-                                id.request_paint();
-                            }),
-                        );
                     } else {
                         drag_set_to_none = true;
                     }
@@ -633,7 +429,10 @@ pub trait View {
                     offset_scale = Some(1.0);
                 }
 
-                if let Some(offset_scale) = offset_scale {
+                let preview = &cx.app_state.view_state(id).drag_preview;
+                let (preview_visible, preview_z_index) = (preview.visible, preview.z_index);
+
+                if let Some(offset_scale) = offset_scale.filter(|_| preview_visible) {
                     let offset = dragging_offset * offset_scale;
                     cx.save();
 
@@ -646,24 +445,30 @@ pub trait View {
                         .as_mut()
                         .unwrap()
                         .transform(cx.transform);
-                    cx.set_z_index(1000);
+                    cx.set_z_index(preview_z_index);
                     cx.clear_clip();
 
-                    let style = cx.app_state.get_computed_style(id).clone();
-                    let view_state = cx.app_state.view_state(id);
-                    let style = if let Some(dragging_style) = view_state.dragging_style.clone() {
-                        view_state
-                            .combined_style
-                            .clone()
-                            .apply(dragging_style)
-                            .compute(&ComputedStyle::default())
+                    if let Some(paint) = cx.app_state.view_state(id).drag_preview.paint.take() {
+                        paint(cx, size);
+                        cx.app_state.view_state(id).drag_preview.paint = Some(paint);
                     } else {
-                        style
-                    };
-                    paint_bg(cx, &style, size);
-                    self.paint(cx);
-                    paint_border(cx, &style, size);
-                    paint_outline(cx, &style, size);
+                        let style = cx.app_state.get_computed_style(id).clone();
+                        let view_state = cx.app_state.view_state(id);
+                        let style = if let Some(dragging_style) = view_state.dragging_style.clone()
+                        {
+                            view_state
+                                .combined_style
+                                .clone()
+                                .apply(dragging_style)
+                                .compute(&ComputedStyle::default())
+                        } else {
+                            style
+                        };
+                        paint_bg(cx, &style, size);
+                        self.paint(cx);
+                        paint_border(cx, &style, size);
+                        paint_outline(cx, &style, size);
+                    }
 
                     cx.restore();
                 }
@@ -681,7 +486,269 @@ pub trait View {
     fn paint(&mut self, cx: &mut PaintCx);
 }
 
-fn paint_bg(cx: &mut PaintCx, style: &ComputedStyle, size: Size) {
+/// Delivers `event`, already offset to the view, to the view and its children: the capture
+/// listeners of the view, then the children, then the view itself. An event handled below the
+/// view bubbles up to the view's listeners, unless its propagation was stopped.
+fn dispatch_event<V: View + ?Sized>(
+    view: &mut V,
+    cx: &mut EventCx,
+    id_path: Option<&[Id]>,
+    event: Event,
+) -> bool {
+    let id = view.id();
+    // the capture phase, on the way down to the view the event is for
+    if cx.capture_event(id, &event) {
+        return true;
+    }
+
+    // if there's id_path, it's an event only for a view.
+    if let Some(id_path) = id_path {
+        if id_path.is_empty() {
+            // this happens when the parent is the destination,
+            // but the parent just passed the event on,
+            // so it's not really for this view and we stop
+            // the event propagation.
+            return false;
+        }
+
+        let id = id_path[0];
+        let id_path = &id_path[1..];
+
+        if id != view.id() {
+            // This shouldn't happen
+            return false;
+        }
+
+        // we're the parent of the event destination, so pass it on to the child
+        if !id_path.is_empty() {
+            if let Some(child) = view.child_mut(id_path[0]) {
+                if child.event_main(cx, Some(id_path), event.clone()) {
+                    cx.bubble_handled_event(id, &event);
+                    return true;
+                }
+            } else {
+                // we don't have the child, stop the event propagation
+                return false;
+            }
+        }
+    }
+
+    // if the event was dispatched to an id_path, the event is supposed to be only
+    // handled by this view only, so we pass an empty id_path
+    // and the event propagation would be stopped at this view
+    if view.event(
+        cx,
+        if id_path.is_some() { Some(&[]) } else { None },
+        event.clone(),
+    ) {
+        cx.bubble_handled_event(id, &event);
+        return true;
+    }
+
+    if cx.propagation.is_default_prevented() {
+        return cx.bubble_event(id, &event);
+    }
+
+    match &event {
+        Event::PointerDown(event) => {
+            if !matches!(event.pointer_type, PointerType::Mouse(_)) {
+                // a touch only hovers views while it's down, so it enters them here
+                let rect = cx.get_size(id).unwrap_or_default().to_rect();
+                if rect.contains(event.pos) {
+                    cx.app_state.hovered.insert(id);
+                }
+            }
+            if event.button.is_left() {
+                let rect = cx.get_size(view.id()).unwrap_or_default().to_rect();
+                let now_focused = rect.contains(event.pos);
+
+                if now_focused {
+                    // views are reached innermost first, so inner recognizers win ties
+                    cx.gesture_pointer_down(id, event);
+                    if cx.app_state.keyboard_navigable.contains(&id) {
+                        // if the view can be focused, we update the focus
+                        cx.app_state.update_focus(id, false);
+                    }
+                    if event.count == 2 && cx.has_event_listener(id, EventListener::DoubleClick) {
+                        let view_state = cx.app_state.view_state(id);
+                        view_state.last_pointer_down = Some(event.clone());
+                        cx.update_active(id);
+                    }
+                    if cx.has_event_listener(id, EventListener::Click) {
+                        let view_state = cx.app_state.view_state(id);
+                        view_state.last_pointer_down = Some(event.clone());
+                        cx.update_active(id);
+                    }
+                    if cx.app_state.draggable.contains(&id) && cx.app_state.drag_start.is_none() {
+                        cx.app_state.drag_start = Some((id, event.pos));
+                    }
+                }
+            }
+        }
+        Event::PointerMove(pointer_event) => {
+            let rect = cx.get_size(id).unwrap_or_default().to_rect();
+            if rect.contains(pointer_event.pos) {
+                if cx.app_state.is_dragging() {
+                    cx.app_state.dragging_over.insert(id);
+                    if let Some(action) = cx.get_event_listener(id, &EventListener::DragOver) {
+                        action(&event);
+                    }
+                } else {
+                    cx.app_state.hovered.insert(id);
+                    let style = cx.app_state.get_computed_style(id);
+                    if let Some(cursor) = style.cursor {
+                        if cx.app_state.cursor.is_none() {
+                            cx.app_state.cursor = Some(cursor);
+                        }
+                    }
+                }
+            }
+            if cx.app_state.draggable.contains(&id) {
+                if let Some((_, drag_start)) = cx
+                    .app_state
+                    .drag_start
+                    .as_ref()
+                    .filter(|(drag_id, _)| drag_id == &id)
+                {
+                    let vec2 = pointer_event.pos - *drag_start;
+
+                    if let Some(dragging) = cx
+                        .app_state
+                        .dragging
+                        .as_mut()
+                        .filter(|d| d.id == id && d.released_at.is_none())
+                    {
+                        // update the dragging offset if the view is dragging and not released
+                        dragging.offset = vec2;
+                        id.request_paint();
+                    } else if vec2.x.abs() + vec2.y.abs() > 1.0 {
+                        // start dragging when moved 1 px
+                        cx.app_state.active = None;
+                        cx.update_active(id);
+                        let payload = cx
+                            .app_state
+                            .view_states
+                            .get(&id)
+                            .and_then(|s| s.drag_payload.as_ref())
+                            .map(|payload| payload());
+                        cx.app_state.dragging = Some(DragState {
+                            id,
+                            offset: vec2,
+                            released_at: None,
+                            payload,
+                        });
+                        id.request_paint();
+                        if let Some(action) = cx.get_event_listener(id, &EventListener::DragStart) {
+                            action(&event);
+                        }
+                    }
+                }
+            }
+        }
+        Event::PointerUp(pointer_event) => {
+            if pointer_event.button.is_left() {
+                let rect = cx.get_size(view.id()).unwrap_or_default().to_rect();
+                let on_view = rect.contains(pointer_event.pos);
+
+                if id_path.is_none() {
+                    if on_view {
+                        if let Some(dragging) = cx.app_state.dragging.as_ref() {
+                            let dragging_id = dragging.id;
+                            // a target accepting the payload gets it before the drop listeners
+                            let accepted = dragging
+                                .payload
+                                .as_ref()
+                                .zip(
+                                    cx.app_state
+                                        .view_states
+                                        .get(&id)
+                                        .and_then(|s| s.drop_target.as_ref()),
+                                )
+                                .filter(|(payload, target)| target.accepts(payload))
+                                .map(|(payload, target)| {
+                                    (target.action)(payload, pointer_event.pos)
+                                })
+                                .unwrap_or(false);
+                            let dropped = accepted
+                                || cx
+                                    .get_event_listener(id, &EventListener::Drop)
+                                    .map(|action| action(&event))
+                                    .unwrap_or(false);
+                            if dropped {
+                                // if the drop is processed, we set dragging to none so that the animation
+                                // for the dragged view back to its original position isn't played.
+                                cx.app_state.dragging = None;
+                                id.request_paint();
+                                if let Some(action) =
+                                    cx.get_event_listener(dragging_id, &EventListener::DragEnd)
+                                {
+                                    action(&event);
+                                }
+                            }
+                        }
+                    }
+                } else {
+                    if let Some(dragging) = cx.app_state.dragging.as_mut().filter(|d| d.id == id) {
+                        let dragging_id = dragging.id;
+                        dragging.released_at = Some(Clock::now());
+                        id.request_paint();
+                        if let Some(action) =
+                            cx.get_event_listener(dragging_id, &EventListener::DragEnd)
+                        {
+                            action(&event);
+                        }
+                    }
+                    let last_pointer_down = cx.app_state.view_state(id).last_pointer_down.take();
+                    if let Some(action) = cx.get_event_listener(id, &EventListener::DoubleClick) {
+                        if on_view
+                            && last_pointer_down
+                                .as_ref()
+                                .map(|e| e.count == 2)
+                                .unwrap_or(false)
+                            && action(&event)
+                        {
+                            return true;
+                        }
+                    }
+                    if let Some(action) = cx.get_event_listener(id, &EventListener::Click) {
+                        if on_view && last_pointer_down.is_some() && action(&event) {
+                            return true;
+                        }
+                    }
+                }
+            }
+        }
+        Event::PointerCancel(_) => {
+            // a cancelled pointer never completes a click
+            cx.app_state.view_state(id).last_pointer_down = None;
+        }
+        Event::FileDragOver(drag_event) => {
+            let rect = cx.get_size(id).unwrap_or_default().to_rect();
+            if rect.contains(drag_event.pos) {
+                cx.app_state.dragging_over.insert(id);
+            }
+        }
+        Event::KeyDown(_) => {
+            if cx.app_state.is_focused(&id) && event.is_keyboard_trigger() {
+                if let Some(action) = cx.get_event_listener(id, &EventListener::Click) {
+                    action(&event);
+                }
+            }
+        }
+        Event::WindowResized(_) => {
+            if let Some(view_state) = cx.app_state.view_states.get(&view.id()) {
+                if !view_state.responsive_styles.is_empty() {
+                    cx.app_state.request_layout(view.id());
+                }
+            }
+        }
+        _ => (),
+    }
+
+    cx.bubble_event(id, &event)
+}
+
+pub(crate) fn paint_bg(cx: &mut PaintCx, style: &ComputedStyle, size: Size) {
     let bg = match style.background {
         Some(color) => color,
         None => return,
@@ -715,7 +782,7 @@ fn paint_outline(cx: &mut PaintCx, style: &ComputedStyle, size: Size) {
     cx.stroke(&rect, style.outline_color, style.outline as f64);
 }
 
-fn paint_border(cx: &mut PaintCx, style: &ComputedStyle, size: Size) {
+pub(crate) fn paint_border(cx: &mut PaintCx, style: &ComputedStyle, size: Size) {
     let left = style.border_left;
     let top = style.border_top;
     let right = style.border_right;
@@ -777,171 +844,193 @@ fn paint_border(cx: &mut PaintCx, style: &ComputedStyle, size: Size) {
     }
 }
 
-/// Tab navigation finds the next or previous view with the `keyboard_navigatable` status in the tree.
-pub(crate) fn view_tab_navigation(root_view: &dyn View, app_state: &mut AppState, backwards: bool) {
-    let start = app_state.focus.unwrap_or(root_view.id());
-    println!("start id is {start:?}");
-    let tree_iter = |id: Id| {
-        if backwards {
-            view_tree_previous(root_view, &id, app_state)
-                .unwrap_or(view_nested_last_child(root_view).id())
-        } else {
-            view_tree_next(root_view, &id, app_state).unwrap_or(root_view.id())
-        }
-    };
+/// A view that groups the keyboard navigable views inside it, see
+/// [Decorators::focus_scope](crate::views::Decorators::focus_scope).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FocusScope {
+    /// Tab and Shift+Tab cycle through the views inside while one of them has the focus, like
+    /// in a toolbar.
+    Cycle,
+    /// The views inside are a single stop for Tab, and the arrow keys move the focus between
+    /// them, like in a radio group. Views with a negative tab index are only skipped by Tab, so
+    /// the stop can be chosen by giving the others a negative index.
+    Arrows,
+}
 
-    let mut new_focus = tree_iter(start);
-    println!("new focus is {new_focus:?}");
-    while new_focus != start
-        && (!app_state.keyboard_navigable.contains(&new_focus) || app_state.is_disabled(&new_focus))
-    {
-        new_focus = tree_iter(new_focus);
-        println!("new focus is {new_focus:?}");
+/// Tab navigation moves the focus to the next or previous view in tab order.
+///
+/// `root_view` doesn't have to be the root of the window: navigation stays inside it, which is
+/// how a modal keeps the focus.
+pub(crate) fn view_tab_navigation(root_view: &dyn View, app_state: &mut AppState, backwards: bool) {
+    if let Some(id) = view_next_focus(root_view, app_state, app_state.focus, backwards) {
+        app_state.clear_focus();
+        app_state.update_focus(id, true);
     }
+}
 
+/// Arrow key navigation moves the focus between the views of the [FocusScope::Arrows] group
+/// the focus is in. Returns false if it isn't in one.
+pub(crate) fn view_arrow_navigation(
+    root_view: &dyn View,
+    app_state: &mut AppState,
+    backwards: bool,
+) -> bool {
+    let Some(focus) = app_state.focus else {
+        return false;
+    };
+    let Some(group) = scoped_id_path(root_view, &focus)
+        .and_then(|id_path| enclosing_scope(root_view, &id_path, app_state, FocusScope::Arrows))
+    else {
+        return false;
+    };
+    let mut views = Vec::new();
+    collect_navigable(group, app_state, &mut views);
+    let Some(pos) = views.iter().position(|id| *id == focus) else {
+        return false;
+    };
+    let next = if backwards {
+        (pos + views.len() - 1) % views.len()
+    } else {
+        (pos + 1) % views.len()
+    };
     app_state.clear_focus();
-    app_state.update_focus(new_focus, true);
-    println!("Tab to {new_focus:?}");
+    app_state.update_focus(views[next], true);
+    true
 }
 
-fn view_children<'a>(view: &'a dyn View, id_path: &[Id]) -> Vec<&'a dyn View> {
-    let id = id_path[0];
-    let id_path = &id_path[1..];
-
-    if id == view.id() {
-        if id_path.is_empty() {
-            view.children()
-        } else if let Some(child) = view.child(id_path[0]) {
-            view_children(child, id_path)
-        } else {
-            Vec::new()
-        }
-    } else {
-        Vec::new()
+/// The view Tab moves the focus to from `from`, or Shift+Tab if `backwards`. The focus cycles
+/// inside the [FocusScope::Cycle] scope `from` is in, or else inside `root_view`.
+pub(crate) fn view_next_focus(
+    root_view: &dyn View,
+    app_state: &AppState,
+    from: Option<Id>,
+    backwards: bool,
+) -> Option<Id> {
+    let from = from.and_then(|id| Some((id, scoped_id_path(root_view, &id)?)));
+    let scope = from
+        .as_ref()
+        .and_then(|(_, id_path)| enclosing_scope(root_view, id_path, app_state, FocusScope::Cycle))
+        .unwrap_or(root_view);
+    let from = from.map(|(id, _)| id);
+    let order = tab_order(scope, app_state, from);
+    let pos = from.and_then(|from| order.iter().position(|id| *id == from));
+    match (pos, backwards) {
+        (Some(pos), false) => order.get((pos + 1) % order.len()).copied(),
+        (Some(pos), true) => order.get((pos + order.len() - 1) % order.len()).copied(),
+        (None, false) => order.first().copied(),
+        (None, true) => order.last().copied(),
     }
 }
 
-/// Get the next item in the tree, either the first child or the next sibling of this view or of the first parent view
-fn view_tree_next(root_view: &dyn View, id: &Id, app_state: &AppState) -> Option<Id> {
-    let id_path = id.id_path()?;
-
-    println!("id is {id:?}");
-    println!("id path is {:?}", id_path.0);
-
-    let children = view_children(root_view, &id_path.0);
-
-    println!(
-        "children is {:?}",
-        children.iter().map(|v| v.id()).collect::<Vec<_>>()
-    );
-    for child in children {
-        if app_state.is_hidden(child.id()) {
-            continue;
-        }
-        return Some(child.id());
+/// The first view Tab stops at inside the view `id`.
+pub(crate) fn view_first_focus_in(
+    root_view: &dyn View,
+    app_state: &AppState,
+    id: Id,
+) -> Option<Id> {
+    let id_path = scoped_id_path(root_view, &id)?;
+    let mut view = root_view;
+    for id in &id_path[1..] {
+        view = view.child(*id)?;
     }
+    tab_order(view, app_state, None).first().copied()
+}
 
-    let mut ancestor = *id;
-    loop {
-        let id_path = ancestor.id_path()?;
-        println!("try to find sibling for {:?}", id_path.0);
-        if let Some(next_sibling) = view_next_sibling(root_view, &id_path.0, app_state) {
-            println!("next sibling is {:?}", next_sibling.id());
-            return Some(next_sibling.id());
-        }
-        ancestor = ancestor.parent()?;
-        println!("go to ancestor {ancestor:?}");
-    }
+/// The keyboard navigable views inside `root_view` in the order Tab goes through them: views
+/// with a positive tab index first, from the lowest index, then the others in tree order.
+///
+/// A [FocusScope::Arrows] group is a single stop: `current` if it's in the group, or else its
+/// first view without a negative tab index, or else its first view.
+fn tab_order(root_view: &dyn View, app_state: &AppState, current: Option<Id>) -> Vec<Id> {
+    let mut order = Vec::new();
+    collect_tab_stops(root_view, app_state, current, &mut order);
+    // the sort is stable, so views with the same index stay in tree order
+    order.sort_by_key(|id| match app_state.tab_index(*id) {
+        index if index > 0 => index,
+        _ => i32::MAX,
+    });
+    order
 }
 
-/// Get the id of the view after this one (but with the same parent and level of nesting)
-fn view_next_sibling<'a>(
-    view: &'a dyn View,
-    id_path: &[Id],
+fn collect_tab_stops(
+    view: &dyn View,
     app_state: &AppState,
-) -> Option<&'a dyn View> {
-    let id = id_path[0];
-    let id_path = &id_path[1..];
-    if id == view.id() {
-        if app_state.is_hidden(id) {
-            return None;
-        }
-
-        if id_path.is_empty() {
-            return None;
-        }
-
-        if id_path.len() == 1 {
-            println!("id is {id:?} id_path is {:?}", id_path);
-            let child_id = id_path[0];
-            let children = view.children();
-            let pos = children.iter().position(|v| v.id() == child_id);
-            if let Some(pos) = pos {
-                if children.len() > 1 && pos < children.len() - 1 {
-                    return Some(children[pos + 1]);
-                }
-            }
-            return None;
-        }
-
-        if let Some(child) = view.child(id_path[0]) {
-            return view_next_sibling(child, id_path, app_state);
-        }
+    current: Option<Id>,
+    order: &mut Vec<Id>,
+) {
+    let id = view.id();
+    if app_state.is_hidden(id) {
+        return;
+    }
+    if app_state.focus_scope(id) == Some(FocusScope::Arrows) {
+        let mut group = Vec::new();
+        collect_navigable(view, app_state, &mut group);
+        let stop = current
+            .filter(|current| group.contains(current))
+            .or_else(|| {
+                group
+                    .iter()
+                    .find(|id| app_state.tab_index(**id) >= 0)
+                    .copied()
+            })
+            .or(group.first().copied());
+        order.extend(stop);
+        return;
+    }
+    if is_tab_stop(app_state, id) {
+        order.push(id);
+    }
+    for child in view.children() {
+        collect_tab_stops(child, app_state, current, order);
     }
-    None
 }
 
-/// Get the next item in the tree, the deepest last child of the previous sibling of this view or the parent
-fn view_tree_previous(root_view: &dyn View, id: &Id, app_state: &AppState) -> Option<Id> {
-    let id_path = id.id_path()?;
+/// The keyboard navigable views inside `view`, in tree order, whatever their tab index.
+fn collect_navigable(view: &dyn View, app_state: &AppState, views: &mut Vec<Id>) {
+    let id = view.id();
+    if app_state.is_hidden(id) {
+        return;
+    }
+    if app_state.keyboard_navigable.contains(&id) && !app_state.is_disabled(&id) {
+        views.push(id);
+    }
+    for child in view.children() {
+        collect_navigable(child, app_state, views);
+    }
+}
 
-    view_previous_sibling(root_view, &id_path.0, app_state)
-        .map(|view| view_nested_last_child(view).id())
-        .or_else(|| id.parent())
+fn is_tab_stop(app_state: &AppState, id: Id) -> bool {
+    app_state.keyboard_navigable.contains(&id)
+        && !app_state.is_disabled(&id)
+        && app_state.tab_index(id) >= 0
 }
 
-/// Get the id of the view before this one (but with the same parent and level of nesting)
-fn view_previous_sibling<'a>(
-    view: &'a dyn View,
+/// The innermost ancestor of the last view of `id_path` that is a focus scope of `kind`.
+fn enclosing_scope<'a>(
+    root_view: &'a dyn View,
     id_path: &[Id],
     app_state: &AppState,
+    kind: FocusScope,
 ) -> Option<&'a dyn View> {
-    let id = id_path[0];
-    let id_path = &id_path[1..];
-    if id == view.id() {
-        if app_state.is_hidden(id) {
-            return None;
-        }
-
-        if id_path.is_empty() {
-            return None;
-        }
-
-        if id_path.len() == 1 {
-            let child_id = id_path[0];
-            let children = view.children();
-            let pos = children.iter().position(|v| v.id() == child_id);
-            if let Some(pos) = pos {
-                if pos > 0 {
-                    return Some(children[pos - 1]);
-                }
-            }
-            return None;
+    let mut view = root_view;
+    let mut scope = None;
+    for id in &id_path[1..] {
+        if app_state.focus_scope(view.id()) == Some(kind) {
+            scope = Some(view);
         }
-
-        if let Some(child) = view.child(id_path[0]) {
-            return view_previous_sibling(child, id_path, app_state);
+        match view.child(*id) {
+            Some(child) => view = child,
+            None => break,
         }
     }
-    None
+    scope
 }
 
-fn view_nested_last_child(view: &dyn View) -> &dyn View {
-    let mut last_child = view;
-    while let Some(new_last_child) = last_child.children().pop() {
-        last_child = new_last_child;
-    }
-    last_child
+/// The id path of `id` starting at `root_view`, if it's inside it.
+fn scoped_id_path(root_view: &dyn View, id: &Id) -> Option<Vec<Id>> {
+    let id_path = id.id_path()?.0;
+    let start = id_path.iter().position(|id| *id == root_view.id())?;
+    Some(id_path[start..].to_vec())
 }
 
 /// Produces an ascii art debug display of all of the views.
@@ -956,6 +1045,12 @@ pub(crate) fn view_debug_tree(root_view: &dyn View) {
             print!("{}", if *leaf { "├── " } else { "└── " });
         }
         println!("{:?} {}", current_view.id(), &current_view.debug_name());
+        for node in current_view.accessibility() {
+            for line in &active_lines {
+                print!("{}", if *line { "│   " } else { "    " });
+            }
+            println!("  {node:?}");
+        }
 
         let mut children = current_view.children();
         if let Some(last_child) = children.pop() {
@@ -0,0 +1,129 @@
+//! # View signals
+//!
+//! Styles react to hover, focus and the other interaction states through decorators like
+//! [hover_style](crate::views::Decorators::hover_style). [ViewSignals] give the same states to
+//! application logic, as signals that can be read in effects and closures:
+//!
+//! ```ignore
+//! let signals = create_view_signals(cx.scope);
+//! stack(|| {
+//!     (
+//!         label(move || item.name.clone()),
+//!         label(|| "Delete".to_string())
+//!             .style(move || Style::BASE.apply_if(!signals.is_hovered().get(), |s| s.hide())),
+//!     )
+//! })
+//! .signals(signals)
+//! ```
+//!
+//! The signals follow the view they're given to with
+//! [Decorators::signals](crate::views::Decorators::signals), and are brought up to date after
+//! each event and each layout of the window.
+
+use glazier::kurbo::Rect;
+use leptos_reactive::{
+    create_rw_signal, ReadSignal, RwSignal, Scope, SignalSet, SignalWithUntracked,
+};
+
+use crate::{context::AppState, id::Id};
+
+/// Signals following the interaction state and the layout of a view.
+#[derive(Clone, Copy)]
+pub struct ViewSignals {
+    is_hovered: RwSignal<bool>,
+    is_focused: RwSignal<bool>,
+    is_active: RwSignal<bool>,
+    is_dragging_over: RwSignal<bool>,
+    layout_rect: RwSignal<Rect>,
+}
+
+/// Creates signals to give to a view with
+/// [Decorators::signals](crate::views::Decorators::signals). They have their initial values
+/// until then.
+pub fn create_view_signals(cx: Scope) -> ViewSignals {
+    ViewSignals {
+        is_hovered: create_rw_signal(cx, false),
+        is_focused: create_rw_signal(cx, false),
+        is_active: create_rw_signal(cx, false),
+        is_dragging_over: create_rw_signal(cx, false),
+        layout_rect: create_rw_signal(cx, Rect::ZERO),
+    }
+}
+
+impl ViewSignals {
+    /// Whether the pointer is over the view or one of its children.
+    pub fn is_hovered(&self) -> ReadSignal<bool> {
+        self.is_hovered.read_only()
+    }
+
+    pub fn is_focused(&self) -> ReadSignal<bool> {
+        self.is_focused.read_only()
+    }
+
+    /// Whether the view is pressed.
+    pub fn is_active(&self) -> ReadSignal<bool> {
+        self.is_active.read_only()
+    }
+
+    /// Whether a drag in progress is over the view.
+    pub fn is_dragging_over(&self) -> ReadSignal<bool> {
+        self.is_dragging_over.read_only()
+    }
+
+    /// The rect of the view and its children in window coordinates.
+    pub fn layout_rect(&self) -> ReadSignal<Rect> {
+        self.layout_rect.read_only()
+    }
+
+    /// Sets the signals that changed from the state of the view `id`, and returns whether any
+    /// of them did.
+    pub(crate) fn update(&self, app_state: &AppState, id: Id, layout_rect: Rect) -> bool {
+        let mut changed = set_changed(self.is_hovered, app_state.is_hovered(&id));
+        changed |= set_changed(self.is_focused, app_state.is_focused(&id));
+        changed |= set_changed(self.is_active, app_state.is_active(&id));
+        changed |= set_changed(self.is_dragging_over, app_state.dragging_over.contains(&id));
+        changed |= set_changed(self.layout_rect, layout_rect);
+        changed
+    }
+}
+
+/// Sets `signal` to `value` unless it already has it, so that its effects only run on
+/// changes.
+fn set_changed<T: PartialEq>(signal: RwSignal<T>, value: T) -> bool {
+    if signal.with_untracked(|current| *current == value) {
+        return false;
+    }
+    signal.set(value);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos_reactive::{create_runtime, raw_scope_and_disposer, SignalGetUntracked};
+
+    use super::create_view_signals;
+    use crate::{context::AppState, id::Id};
+
+    #[test]
+    fn signals_follow_hover_and_focus() {
+        let (cx, _) = raw_scope_and_disposer(create_runtime());
+        let mut app_state = AppState::new();
+        let id = Id::next();
+        let signals = create_view_signals(cx);
+        app_state.set_view_signals(id, signals);
+
+        app_state.hovered.insert(id);
+        assert!(app_state.update_view_signals());
+        assert!(signals.is_hovered().get_untracked());
+        assert!(!signals.is_focused().get_untracked());
+
+        app_state.hovered.clear();
+        app_state.focus = Some(id);
+        assert!(app_state.update_view_signals());
+        assert!(!signals.is_hovered().get_untracked());
+        assert!(signals.is_focused().get_untracked());
+
+        // nothing changed since the last update
+        assert!(!app_state.update_view_signals());
+    }
+}
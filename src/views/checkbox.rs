@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use glazier::kurbo::Rect;
+use leptos_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate};
+use vello::peniko::Color;
+
+use crate::{
+    animate::animation,
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::Style,
+    view::{ChangeFlags, View},
+    views::{svg, Decorators, Svg},
+};
+
+const CHECK_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 16 16"><polygon points="5.19,11.83 0.18,7.44 1.82,5.56 4.81,8.17 10,1.25 12,2.75" /></svg>"#;
+const DASH_SVG: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-2 -2 16 16"><rect x="1" y="5" width="10" height="2.5" /></svg>"#;
+const BOX_SIZE: f32 = 20.0;
+const MARK_SIZE: f64 = 14.0;
+const CHECKED_COLOR: Color = Color::rgb8(0x3b, 0x82, 0xf6);
+const BORDER_COLOR: Color = Color::rgb8(0xa1, 0xa1, 0xaa);
+const MARK_DURATION: Duration = Duration::from_millis(120);
+
+/// The state of a [tri_state_checkbox].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CheckState {
+    Unchecked,
+    Checked,
+    /// Neither checked nor unchecked, e.g. for a checkbox checking a group of checkboxes that
+    /// are only partly checked
+    Indeterminate,
+}
+
+pub struct TriStateCheckbox {
+    id: Id,
+    mark: Svg,
+}
+
+/// A checkbox showing `state`, toggled when it's clicked, or when Enter or Space is pressed
+/// while it's focused: an unchecked or indeterminate checkbox becomes checked, and a checked
+/// one unchecked. Only the application sets it to [CheckState::Indeterminate]. Its mark grows
+/// in when it's checked.
+///
+/// The box is styled with [Decorators::style], which can depend on `state`, and the mark with
+/// [TriStateCheckbox::mark_style].
+pub fn tri_state_checkbox(state: RwSignal<CheckState>) -> TriStateCheckbox {
+    let (id, mark) = ViewContext::new_id_with_child(|| {
+        svg(move || {
+            match state.get() {
+                CheckState::Unchecked => "",
+                CheckState::Checked => CHECK_SVG,
+                CheckState::Indeterminate => DASH_SVG,
+            }
+            .to_string()
+        })
+        .base_style(|| Style::BASE.color(Color::WHITE))
+        .animation({
+            let size = move || {
+                if state.get() == CheckState::Unchecked {
+                    0.0
+                } else {
+                    MARK_SIZE
+                }
+            };
+            animation().width(size).height(size).duration(MARK_DURATION)
+        })
+    });
+    TriStateCheckbox { id, mark }
+        .base_style(move || {
+            let checked = state.get() != CheckState::Unchecked;
+            Style::BASE
+                .size_px(BOX_SIZE, BOX_SIZE)
+                .border(1.0)
+                .border_radius(5.0)
+                .border_color(if checked { CHECKED_COLOR } else { BORDER_COLOR })
+                .background(if checked { CHECKED_COLOR } else { Color::WHITE })
+                .items_center()
+                .justify_center()
+        })
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            state.update(|state| {
+                *state = match state {
+                    CheckState::Checked => CheckState::Unchecked,
+                    CheckState::Unchecked | CheckState::Indeterminate => CheckState::Checked,
+                }
+            });
+            true
+        })
+}
+
+impl TriStateCheckbox {
+    /// The style of the check mark, which is painted in its color.
+    pub fn mark_style(self, style: impl Fn() -> Style + 'static) -> Self {
+        let cx = ViewContext::get_current();
+        let mark = self.mark.id();
+        create_effect(cx.scope, move |_| {
+            mark.update_style(style());
+        });
+        self
+    }
+}
+
+impl View for TriStateCheckbox {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.mark.id() == id {
+            Some(&self.mark)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.mark.id() == id {
+            Some(&mut self.mark)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.mark]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.mark]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "TriStateCheckbox".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| vec![self.mark.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        Some(self.mark.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        // the mark is part of the checkbox, so the checkbox gets the events on it
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.mark.paint_main(cx);
+    }
+}
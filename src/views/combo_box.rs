@@ -0,0 +1,262 @@
+use std::{fmt::Display, ops::Range, rc::Rc};
+
+use glazier::keyboard_types::Key;
+use leptos_reactive::{
+    create_effect, create_rw_signal, RwSignal, SignalGet, SignalSet, SignalWith,
+    SignalWithUntracked,
+};
+
+use crate::{
+    app_handle::ViewContext,
+    event::{Event, EventListener},
+    overlay::Placement,
+    style::Style,
+    view::View,
+    views::{
+        label, option_rect, popup_style, portal, scroll, stack, text_input, virtual_list,
+        Decorators, VirtualListDirection, VirtualListItemSize, HIGHLIGHT_COLOR, OPTION_HEIGHT,
+    },
+};
+
+/// An option of a [combo_box] matching the text typed in it.
+#[derive(Clone)]
+struct Suggestion<T> {
+    /// The index of the option in all the options
+    index: usize,
+    option: T,
+    text: String,
+    /// The part of `text` matching the typed text
+    matched: Range<usize>,
+}
+
+/// The byte range of the first part of `text` matching `query`, ignoring case.
+fn match_range(text: &str, query: &str) -> Option<Range<usize>> {
+    if query.is_empty() {
+        return Some(0..0);
+    }
+    text.char_indices().find_map(|(start, _)| {
+        let mut end = start;
+        let mut chars = text[start..].chars();
+        for q in query.chars() {
+            let c = chars.next()?;
+            if !c.to_lowercase().eq(q.to_lowercase()) {
+                return None;
+            }
+            end += c.len_utf8();
+        }
+        Some(start..end)
+    })
+}
+
+fn suggestions<T: Clone + Display>(
+    options: im::Vector<T>,
+    query: &str,
+) -> im::Vector<Suggestion<T>> {
+    options
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, option)| {
+            let text = option.to_string();
+            let matched = match_range(&text, query)?;
+            Some(Suggestion {
+                index,
+                option,
+                text,
+                matched,
+            })
+        })
+        .collect()
+}
+
+/// A text input suggesting the options of `options` containing the typed text, in a list below
+/// it. Picking a suggestion sets `selected` and puts its text in the input.
+///
+/// The list opens once the text changes, or with the down arrow. The arrow keys move the
+/// highlight, Enter picks the highlighted suggestion and Escape closes the list. Only the
+/// suggestions in view are made, so there can be many options. The part of each suggestion
+/// matching the typed text is bold.
+///
+/// The options are matched and shown with their [Display] text.
+pub fn combo_box<T>(
+    options: impl Fn() -> im::Vector<T> + 'static,
+    selected: RwSignal<Option<T>>,
+) -> impl View
+where
+    T: Clone + PartialEq + Display + 'static,
+{
+    let cx = ViewContext::get_current();
+    let selected_text = move || {
+        selected.with_untracked(|selected| {
+            selected
+                .as_ref()
+                .map(|selected| selected.to_string())
+                .unwrap_or_default()
+        })
+    };
+    let query = create_rw_signal(cx.scope, selected_text());
+    let open = create_rw_signal(cx.scope, false);
+    let highlighted = create_rw_signal(cx.scope, 0);
+    let matches = Rc::new(move || query.with(|query| suggestions(options(), query)));
+
+    create_effect(cx.scope, move |prev: Option<String>| {
+        let text = query.get();
+        // picking a suggestion sets the text too, which doesn't open the list again
+        if matches!(&prev, Some(prev) if *prev != text) && text != selected_text() {
+            highlighted.set(0);
+            open.set(true);
+        }
+        text
+    });
+    create_effect(cx.scope, move |_| {
+        // the text follows a selection made by the application
+        let text = selected.with(|selected| selected.as_ref().map(|s| s.to_string()));
+        if let Some(text) = text {
+            query.set(text);
+        }
+    });
+
+    let choose = {
+        let matches = matches.clone();
+        move |index: usize| {
+            if let Some(suggestion) = matches().get(index) {
+                selected.set(Some(suggestion.option.clone()));
+                query.set(suggestion.text.clone());
+            }
+            open.set(false);
+        }
+    };
+
+    let popup = {
+        let matches = matches.clone();
+        let choose = choose.clone();
+        move || {
+            let matches = matches.clone();
+            let choose = choose.clone();
+            scroll(move || {
+                virtual_list(
+                    VirtualListDirection::Vertical,
+                    VirtualListItemSize::Fixed(Box::new(|| OPTION_HEIGHT)),
+                    move || matches().into_iter().enumerate().collect::<im::Vector<_>>(),
+                    |(position, suggestion)| {
+                        (
+                            *position,
+                            suggestion.index,
+                            suggestion.matched.start,
+                            suggestion.matched.end,
+                        )
+                    },
+                    move |(position, suggestion)| {
+                        let choose = choose.clone();
+                        let Suggestion { text, matched, .. } = suggestion;
+                        let before = text[..matched.start].to_string();
+                        let fragment = text[matched.clone()].to_string();
+                        let after = text[matched.end..].to_string();
+                        stack(move || {
+                            (
+                                label(move || before.clone()),
+                                label(move || fragment.clone()).style(|| Style::BASE.font_bold()),
+                                label(move || after.clone()),
+                            )
+                        })
+                        .style(move || {
+                            Style::BASE
+                                .height_px(OPTION_HEIGHT as f32)
+                                .padding_horiz_px(8.0)
+                                .items_center()
+                                .apply_if(highlighted.get() == position, |s| {
+                                    s.background(HIGHLIGHT_COLOR)
+                                })
+                        })
+                        .on_event(EventListener::PointerEnter, move |_| {
+                            highlighted.set(position);
+                            false
+                        })
+                        .on_click(move |_| {
+                            choose(position);
+                            true
+                        })
+                    },
+                )
+                .style(|| Style::BASE.flex_col().min_width_pct(100.0))
+            })
+            .on_ensure_visible(move || option_rect(highlighted.get()))
+            .style(|| popup_style().min_width_px(200.0))
+        }
+    };
+
+    let on_key = move |event: &Event| -> bool {
+        let Event::KeyDown(key_event) = event else {
+            return false;
+        };
+        let is_open = open.get();
+        let len = matches().len();
+        match key_event.key {
+            Key::ArrowDown if !is_open => {
+                highlighted.set(0);
+                open.set(true);
+                true
+            }
+            Key::ArrowDown | Key::ArrowUp if is_open && len > 0 => {
+                let index = highlighted.get();
+                highlighted.set(if key_event.key == Key::ArrowDown {
+                    (index + 1).min(len - 1)
+                } else {
+                    index.saturating_sub(1)
+                });
+                true
+            }
+            Key::Enter if is_open => {
+                choose(highlighted.get());
+                true
+            }
+            Key::Escape if is_open => {
+                open.set(false);
+                true
+            }
+            _ => false,
+        }
+    };
+
+    stack(move || {
+        (
+            text_input(query)
+                .style(|| Style::BASE.min_width_px(200.0))
+                .on_event(EventListener::FocusLost, move |_| {
+                    open.set(false);
+                    false
+                }),
+            portal(move || open.get(), popup)
+                .placement(Placement::default().gap(2.0))
+                .on_click_outside(move || open.set(false)),
+        )
+    })
+    .on_event_capture(EventListener::KeyDown, move |event, propagation| {
+        // the list keys are handled before the text input, which would take Escape
+        if on_key(event) {
+            propagation.stop_propagation();
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{match_range, suggestions};
+
+    #[test]
+    fn matches_ignore_case() {
+        assert_eq!(match_range("New York", "york"), Some(4..8));
+        assert_eq!(match_range("New York", "NEW"), Some(0..3));
+        assert_eq!(match_range("Zürich", "ÜR"), Some(1..4));
+        assert_eq!(match_range("Paris", "rome"), None);
+        assert_eq!(match_range("Paris", ""), Some(0..0));
+    }
+
+    #[test]
+    fn suggestions_keep_their_index() {
+        let options = im::vector!["Berlin", "Paris", "Bern", "Rome"];
+        let matched = suggestions(options, "ber");
+        let indexes = matched.iter().map(|s| s.index).collect::<Vec<_>>();
+        assert_eq!(indexes, vec![0, 2]);
+        assert_eq!(matched[1].matched, 0..3);
+    }
+}
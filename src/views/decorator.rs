@@ -1,15 +1,23 @@
+use std::time::Duration;
+
 use glazier::kurbo::{Point, Rect};
-use leptos_reactive::create_effect;
+use leptos_reactive::{create_effect, on_cleanup};
 
 use crate::{
     animate::Animation,
     app_handle::{StyleSelector, ViewContext},
-    event::{Event, EventListener},
+    dnd::{DragPayload, DragPreview},
+    event::{Event, EventListener, EventPropagation, ListenerHandle},
+    gesture::{GestureConfig, GestureEvent, GestureKind},
+    id::Id,
     responsive::ScreenSize,
     style::Style,
-    view::View,
+    view::{FocusScope, View},
+    view_signals::ViewSignals,
 };
 
+use super::tooltip::Tooltip;
+
 pub trait Decorators: View + Sized {
     fn style(self, style: impl Fn() -> Style + 'static) -> Self {
         let cx = ViewContext::get_current();
@@ -81,12 +89,75 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// Orders this keyboard navigable view for tab navigation, similar to tabindex in html:
+    /// views with a positive index come first, from the lowest index, then the others in tree
+    /// order. Views with a negative index are skipped by Tab but can still be focused.
+    fn tab_index(self, index: i32) -> Self {
+        let id = self.id();
+        id.update_tab_index(index);
+        self
+    }
+
+    /// Groups the keyboard navigable views inside this view, see [FocusScope].
+    fn focus_scope(self, scope: FocusScope) -> Self {
+        let id = self.id();
+        id.update_focus_scope(scope);
+        self
+    }
+
+    /// Makes `signals` follow the interaction state and the layout of this view.
+    fn signals(self, signals: ViewSignals) -> Self {
+        let id = self.id();
+        id.update_signals(signals);
+        self
+    }
+
     fn draggable(self) -> Self {
         let id = self.id();
         id.draggable();
         self
     }
 
+    /// Makes the view draggable, carrying the payload made by `payload` when a drag starts.
+    fn drag_payload(self, payload: impl Fn() -> DragPayload + 'static) -> Self {
+        let id = self.id();
+        id.draggable();
+        id.update_drag_payload(payload);
+        self
+    }
+
+    /// What follows the pointer while the view is dragged. Defaults to [DragPreview::source].
+    fn drag_preview(self, preview: DragPreview) -> Self {
+        let id = self.id();
+        id.update_drag_preview(preview);
+        self
+    }
+
+    /// Accepts dropped payloads of `kinds`, which can end with `*` to accept every kind
+    /// starting the same. `action` gets the payload and the position of the drop, and returns
+    /// whether it took the payload.
+    fn on_drop(
+        self,
+        kinds: &[&str],
+        action: impl Fn(&DragPayload, Point) -> bool + 'static,
+    ) -> Self {
+        let id = self.id();
+        let kinds = kinds.iter().map(|kind| kind.to_string()).collect();
+        id.update_drop_target(kinds, Box::new(action));
+        self
+    }
+
+    /// The style to apply while a payload the view accepts is dragged over it
+    fn drop_accept_style(self, style: impl Fn() -> Style + 'static) -> Self {
+        let cx = ViewContext::get_current();
+        let id = self.id();
+        create_effect(cx.scope, move |_| {
+            let style = style();
+            id.update_style_selector(style, StyleSelector::DropAccept);
+        });
+        self
+    }
+
     fn active_style(self, style: impl Fn() -> Style + 'static) -> Self {
         let cx = ViewContext::get_current();
         let id = self.id();
@@ -129,21 +200,38 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// Listens to `listener` in the bubble phase, after this view and its children had a chance
+    /// to handle the event. Return `true` to handle the event, which keeps it from the other
+    /// views under it and from the built in handling of the views further up. The listener also
+    /// observes the events that the view or its children handled, unless their propagation was
+    /// stopped, and what it returns for those doesn't matter.
     fn on_event(self, listener: EventListener, action: impl Fn(&Event) -> bool + 'static) -> Self {
         let id = self.id();
-        id.update_event_listener(listener, Box::new(action));
+        keep_listener(id.update_event_listener(listener, Box::new(action)));
+        self
+    }
+
+    /// Listens to `listener` in the capture phase, before this view or any of its children
+    /// handle the event. See [EventPropagation] for stopping the event from going any further.
+    fn on_event_capture(
+        self,
+        listener: EventListener,
+        action: impl Fn(&Event, &mut EventPropagation) + 'static,
+    ) -> Self {
+        let id = self.id();
+        keep_listener(id.update_capture_listener(listener, Box::new(action)));
         self
     }
 
     fn on_click(self, action: impl Fn(&Event) -> bool + 'static) -> Self {
         let id = self.id();
-        id.update_event_listener(EventListener::Click, Box::new(action));
+        keep_listener(id.update_event_listener(EventListener::Click, Box::new(action)));
         self
     }
 
     fn on_double_click(self, action: impl Fn(&Event) -> bool + 'static) -> Self {
         let id = self.id();
-        id.update_event_listener(EventListener::DoubleClick, Box::new(action));
+        keep_listener(id.update_event_listener(EventListener::DoubleClick, Box::new(action)));
         self
     }
 
@@ -153,6 +241,78 @@ pub trait Decorators: View + Sized {
         self
     }
 
+    /// Listens to taps. A double tap is a tap with a count of 2, see [GestureEvent::Tap].
+    fn on_tap(self, action: impl Fn(&GestureEvent) + 'static) -> Self {
+        add_gesture_listener(self.id(), GestureKind::Tap, action);
+        self
+    }
+
+    fn on_long_press(self, action: impl Fn(&GestureEvent) + 'static) -> Self {
+        add_gesture_listener(self.id(), GestureKind::LongPress, action);
+        self
+    }
+
+    /// Listens to drags of one pointer. The view captures the pointer once the pan begins.
+    fn on_pan(self, action: impl Fn(&GestureEvent) + 'static) -> Self {
+        add_gesture_listener(self.id(), GestureKind::Pan, action);
+        self
+    }
+
+    fn on_swipe(self, action: impl Fn(&GestureEvent) + 'static) -> Self {
+        add_gesture_listener(self.id(), GestureKind::Swipe, action);
+        self
+    }
+
+    /// Listens to two pointers pinching and rotating. The view captures both pointers.
+    fn on_pinch(self, action: impl Fn(&GestureEvent) + 'static) -> Self {
+        add_gesture_listener(self.id(), GestureKind::Pinch, action);
+        self
+    }
+
+    /// Changes the thresholds the gestures of this view are recognized with.
+    fn gesture_config(self, config: GestureConfig) -> Self {
+        let id = self.id();
+        id.update_gesture_config(config);
+        self
+    }
+
+    /// Shows the view made by `tooltip` above the rest of the window once the pointer rests on
+    /// this view, or once this view is focused with the keyboard. It hides when the pointer
+    /// leaves, a pointer goes down, the window scrolls or a key is pressed.
+    fn tooltip<V: View + 'static>(self, tooltip: impl Fn() -> V + 'static) -> Self {
+        let cx = ViewContext::get_current();
+        let id = self.id();
+        id.update_tooltip(Tooltip {
+            scope: cx.scope,
+            view: Box::new(move || Box::new(tooltip())),
+        });
+        self
+    }
+
+    /// How long the pointer rests on this view before its tooltip shows, see
+    /// [DEFAULT_TOOLTIP_DELAY](crate::views::DEFAULT_TOOLTIP_DELAY).
+    fn tooltip_delay(self, delay: Duration) -> Self {
+        let id = self.id();
+        id.update_tooltip_delay(delay);
+        self
+    }
+
+    /// Scopes the key bindings of the window keymap that are limited to `context` to this view
+    /// and its children.
+    fn key_context(self, context: impl Into<String>) -> Self {
+        let id = self.id();
+        id.update_key_context(context);
+        self
+    }
+
+    /// Handles `command` while the focus is on this view or inside it. Return `true` to stop
+    /// the command from reaching the views further up.
+    fn on_command(self, command: impl Into<String>, action: impl Fn() -> bool + 'static) -> Self {
+        let id = self.id();
+        id.update_command_handler(command, Box::new(action));
+        self
+    }
+
     fn animation(self, anim: Animation) -> Self {
         let cx = ViewContext::get_current();
         let id = self.id();
@@ -175,3 +335,23 @@ pub trait Decorators: View + Sized {
 }
 
 impl<V: View> Decorators for V {}
+
+/// Enables the recognition of `kind` gestures on the view `id` and calls `action` with them.
+fn add_gesture_listener(id: Id, kind: GestureKind, action: impl Fn(&GestureEvent) + 'static) {
+    id.update_gesture(kind);
+    keep_listener(id.update_event_listener(
+        kind.into(),
+        Box::new(move |event| {
+            if let Event::Gesture(gesture) = event {
+                action(gesture);
+            }
+            true
+        }),
+    ));
+}
+
+/// Keeps a listener registered until the current reactive scope is disposed.
+fn keep_listener(handle: ListenerHandle) {
+    let cx = ViewContext::get_current();
+    on_cleanup(cx.scope, move || drop(handle));
+}
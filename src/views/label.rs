@@ -2,7 +2,7 @@ use std::any::Any;
 
 use crate::{
     cosmic_text::{Attrs, AttrsList, FamilyOwned, TextLayout},
-    style::{ComputedStyle, TextOverflow},
+    style::{ComputedStyle, TextOverflow, DEFAULT_FONT_SIZE},
 };
 use floem_renderer::{
     cosmic_text::{LineHeightValue, Style as FontStyle, Weight},
@@ -146,7 +146,7 @@ impl View for Label {
     fn layout(&mut self, cx: &mut crate::context::LayoutCx) -> taffy::prelude::Node {
         cx.layout_node(self.id, true, |cx| {
             let (width, height) = if self.label.is_empty() {
-                (0.0, cx.current_font_size().unwrap_or(14.0))
+                (0.0, cx.current_font_size().unwrap_or(DEFAULT_FONT_SIZE))
             } else {
                 let text_overflow = cx.app_state_mut().get_computed_style(self.id).text_overflow;
                 if self.color != cx.color
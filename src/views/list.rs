@@ -15,6 +15,8 @@ use crate::{
     view::{ChangeFlags, View},
 };
 
+use super::{paint_children, reorder_items, Reorder, ReorderTick};
+
 pub(crate) type FxIndexSet<T> = indexmap::IndexSet<T, BuildHasherDefault<FxHasher>>;
 
 #[derive(educe::Educe)]
@@ -32,6 +34,7 @@ where
     view_fn: VF,
     phantom: PhantomData<T>,
     cx: ViewContext,
+    reorder: Option<Reorder>,
 }
 
 pub fn list<IF, I, T, KF, K, VF, V>(each_fn: IF, key_fn: KF, view_fn: VF) -> List<V, VF, T>
@@ -82,6 +85,38 @@ where
         view_fn,
         phantom: PhantomData::default(),
         cx: child_cx,
+        reorder: None,
+    }
+}
+
+impl<V: View, VF, T> List<V, VF, T>
+where
+    VF: Fn(T) -> V + 'static,
+{
+    /// Lets the items be reordered by dragging them, or with the keyboard by pressing space
+    /// on a focused item to pick it up, the arrow keys to move it and space again to drop it.
+    /// The items need to be keyboard navigable to be picked up with the keyboard.
+    /// `on_reorder` gets the index the item was at and the index it should be moved to, as in
+    /// `let item = items.remove(from); items.insert(to, item)`.
+    ///
+    /// The list is laid out along the flex direction of its style.
+    pub fn reorderable(mut self, on_reorder: impl Fn(usize, usize) + 'static) -> Self {
+        self.reorder = Some(Reorder::new(on_reorder));
+        self
+    }
+
+    fn is_vertical(&self, app_state: &AppState) -> bool {
+        app_state
+            .view_states
+            .get(&self.id)
+            .map(|s| {
+                matches!(
+                    s.computed_style.flex_direction,
+                    taffy::style::FlexDirection::Column
+                        | taffy::style::FlexDirection::ColumnReverse
+                )
+            })
+            .unwrap_or(false)
     }
 }
 
@@ -142,6 +177,16 @@ where
         cx: &mut UpdateCx,
         state: Box<dyn std::any::Any>,
     ) -> crate::view::ChangeFlags {
+        if state.is::<ReorderTick>() {
+            let vertical = self.is_vertical(cx.app_state);
+            if let Some(reorder) = self.reorder.as_mut() {
+                reorder.autoscroll(cx.app_state, self.id, vertical);
+                let items = reorder_items(cx.app_state, vertical, 0, &self.children);
+                reorder.update_slot(&items, 0, self.children.len());
+            }
+            self.id.request_paint();
+            return ChangeFlags::empty();
+        }
         if let Ok(diff) = state.downcast() {
             ViewContext::save();
             ViewContext::set_current(self.cx);
@@ -181,6 +226,14 @@ where
         id_path: Option<&[Id]>,
         event: crate::event::Event,
     ) -> bool {
+        let vertical = self.is_vertical(cx.app_state);
+        if let Some(reorder) = self.reorder.as_mut() {
+            let items = reorder_items(cx.app_state, vertical, 0, &self.children);
+            let total_len = self.children.len();
+            if reorder.event(cx, self.id, vertical, 0, total_len, &items, &event) {
+                return true;
+            }
+        }
         for child in self.children.iter_mut() {
             if let Some((child, _)) = child.as_mut() {
                 let id = child.id();
@@ -193,11 +246,15 @@ where
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        for child in self.children.iter_mut() {
-            if let Some((child, _)) = child.as_mut() {
-                child.paint_main(cx);
-            }
-        }
+        let vertical = self.is_vertical(cx.app_state);
+        paint_children(
+            cx,
+            self.id,
+            self.reorder.as_ref(),
+            vertical,
+            0,
+            &mut self.children,
+        );
     }
 }
 
@@ -403,3 +460,81 @@ pub(super) fn apply_diff<T, V, VF>(
     // items
     children.retain(|c| c.is_some());
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use glazier::{keyboard_types::Key, Code, KeyEvent};
+    use leptos_reactive::{create_runtime, raw_scope_and_disposer};
+
+    use super::list;
+    use crate::{
+        app_handle::AppHandle,
+        event::Event,
+        style::Style,
+        view::View,
+        views::{container, empty, Decorators},
+    };
+
+    fn key_down(key: Key, code: Code) -> Event {
+        Event::KeyDown(KeyEvent {
+            key,
+            code,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn space_on_a_view_in_an_item_doesnt_pick_it_up() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let log = Rc::new(RefCell::new(Vec::new()));
+        let ids = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let (log, ids) = (log.clone(), ids.clone());
+            move || {
+                let on_reorder = {
+                    let log = log.clone();
+                    move |from, to| log.borrow_mut().push(format!("reorder {from} {to}"))
+                };
+                list(
+                    || 0..2,
+                    |i| *i,
+                    move |i| {
+                        let log = log.clone();
+                        let button = empty()
+                            .style(|| Style::BASE.size_px(20.0, 20.0))
+                            .keyboard_navigatable()
+                            .on_click(move |_| {
+                                log.borrow_mut().push(format!("click {i}"));
+                                true
+                            });
+                        let button_id = button.id();
+                        let item = container(move || button)
+                            .style(|| Style::BASE.size_px(100.0, 20.0))
+                            .keyboard_navigatable();
+                        ids.borrow_mut().push((item.id(), button_id));
+                        item
+                    },
+                )
+                .style(|| Style::BASE.flex_col())
+                .reorderable(on_reorder)
+            }
+        });
+        app.process_update();
+        let (item, button) = ids.borrow()[0];
+        let space = || key_down(Key::Character(" ".to_string()), Code::Space);
+
+        button.request_focus();
+        app.process_update();
+        app.event(space());
+        assert_eq!(*log.borrow(), ["click 0"]);
+
+        item.request_focus();
+        app.process_update();
+        app.event(space());
+        app.event(key_down(Key::ArrowDown, Code::ArrowDown));
+        app.event(space());
+        assert_eq!(*log.borrow(), ["click 0", "reorder 0 1"]);
+    }
+}
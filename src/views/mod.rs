@@ -1,6 +1,9 @@
 mod label;
 pub use label::*;
 
+mod radio;
+pub use radio::*;
+
 mod rich_text;
 pub use rich_text::*;
 
@@ -10,9 +13,15 @@ pub use list::*;
 mod svg;
 pub use svg::*;
 
+mod checkbox;
+pub use checkbox::*;
+
 mod clip;
 pub use clip::*;
 
+mod combo_box;
+pub use combo_box::*;
+
 mod container;
 pub use container::*;
 
@@ -25,9 +34,33 @@ pub use decorator::*;
 mod virtual_list;
 pub use virtual_list::*;
 
+mod virtual_grid;
+pub use virtual_grid::*;
+
+mod reorder;
+use reorder::*;
+
+mod modal;
+pub use modal::*;
+
+mod portal;
+pub use portal::*;
+
 mod scroll;
 pub use scroll::*;
 
+mod select;
+pub use select::*;
+
+mod slider;
+pub use slider::*;
+
+mod split;
+pub use split::*;
+
+mod table;
+pub use table::*;
+
 mod tab;
 pub use tab::*;
 
@@ -37,6 +70,15 @@ pub use stack::*;
 mod text_input;
 pub use text_input::*;
 
+mod toggle;
+pub use toggle::*;
+
+pub(crate) mod tooltip;
+pub use tooltip::DEFAULT_TOOLTIP_DELAY;
+
+mod tree_view;
+pub use tree_view::*;
+
 mod empty;
 pub use empty::*;
 
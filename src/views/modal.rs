@@ -0,0 +1,149 @@
+use std::{any::Any, rc::Rc};
+
+use glazier::kurbo::Rect;
+use leptos_reactive::{create_effect, RwSignal, Scope, SignalGet, SignalSet};
+use vello::peniko::Color;
+
+use crate::{
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    overlay::{Overlay, OverlayAnchor, OverlayUpdate},
+    style::Style,
+    view::{ChangeFlags, View},
+};
+
+pub struct Modal {
+    id: Id,
+    scope: Scope,
+    open: RwSignal<bool>,
+    content: Box<dyn Fn() -> Box<dyn View>>,
+    backdrop: Color,
+    close_on_escape: bool,
+    close_on_backdrop_click: bool,
+    /// The overlay showing the dialog while the modal is open
+    overlay: Option<Id>,
+}
+
+/// A dialog over the whole window while `open` is true.
+///
+/// The rest of the window is dimmed by a backdrop and gets no pointer events, and tab
+/// navigation stays inside the dialog. The dialog is closed by setting `open` to false, which
+/// Escape and clicks on the backdrop do unless they are turned off. The focus goes back to the
+/// view that had it before the dialog opened.
+///
+/// The modal itself takes no room in the layout, so it can go anywhere in the view tree.
+pub fn modal<V: View + 'static>(open: RwSignal<bool>, content: impl Fn() -> V + 'static) -> Modal {
+    let cx = ViewContext::get_current();
+    let id = cx.new_id();
+    create_effect(cx.scope, move |_| {
+        id.update_state(open.get(), false);
+    });
+    Modal {
+        id,
+        scope: cx.scope,
+        open,
+        content: Box::new(move || Box::new(content())),
+        backdrop: Color::rgba8(0, 0, 0, 100),
+        close_on_escape: true,
+        close_on_backdrop_click: true,
+        overlay: None,
+    }
+}
+
+impl Modal {
+    /// The color covering the window under the dialog.
+    pub fn backdrop(mut self, color: Color) -> Self {
+        self.backdrop = color;
+        self
+    }
+
+    pub fn close_on_escape(mut self, close: bool) -> Self {
+        self.close_on_escape = close;
+        self
+    }
+
+    pub fn close_on_backdrop_click(mut self, close: bool) -> Self {
+        self.close_on_backdrop_click = close;
+        self
+    }
+
+    fn open(&mut self) {
+        let Some(root) = self.id.root_id() else {
+            return;
+        };
+        let open = self.open;
+        let close: Rc<dyn Fn()> = Rc::new(move || open.set(false));
+        let overlay = Overlay::new(root, self.scope, OverlayAnchor::Rect(Rect::ZERO), || {
+            (self.content)()
+        })
+        .owner(self.id)
+        .backdrop(self.backdrop)
+        .trap_focus()
+        .on_escape(self.close_on_escape.then(|| close.clone()))
+        .on_backdrop_click(self.close_on_backdrop_click.then_some(close));
+        self.overlay = Some(overlay.id());
+        root.update_state(OverlayUpdate::Mount(overlay), false);
+    }
+
+    fn close(&mut self) {
+        let Some(overlay) = self.overlay.take() else {
+            return;
+        };
+        if let Some(root) = overlay.root_id() {
+            root.update_state(OverlayUpdate::Unmount(overlay), false);
+        }
+    }
+}
+
+impl View for Modal {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::BASE.absolute())
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Modal".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) -> ChangeFlags {
+        if let Ok(open) = state.downcast::<bool>() {
+            if *open && self.overlay.is_none() {
+                self.open();
+            } else if !*open {
+                self.close();
+            }
+        }
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        false
+    }
+
+    fn paint(&mut self, _cx: &mut PaintCx) {}
+}
@@ -0,0 +1,135 @@
+use std::{any::Any, rc::Rc};
+
+use leptos_reactive::{create_effect, Scope};
+
+use crate::{
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    overlay::{Overlay, OverlayAnchor, OverlayUpdate, Placement},
+    style::Style,
+    view::{ChangeFlags, View},
+};
+
+pub struct Portal {
+    id: Id,
+    scope: Scope,
+    content: Box<dyn Fn() -> Box<dyn View>>,
+    placement: Placement,
+    on_click_outside: Option<Rc<dyn Fn()>>,
+    /// The overlay showing the content while the portal is open
+    overlay: Option<Id>,
+}
+
+/// Shows the view made by `content` in the overlay layer of the window while `open` is true.
+///
+/// The content is placed next to the portal itself, which takes no room in the layout and
+/// covers its parent unless it's styled otherwise. So a portal inside a button opens next to
+/// the button. The content is made again each time the portal opens.
+pub fn portal<V: View + 'static>(
+    open: impl Fn() -> bool + 'static,
+    content: impl Fn() -> V + 'static,
+) -> Portal {
+    let cx = ViewContext::get_current();
+    let id = cx.new_id();
+    create_effect(cx.scope, move |_| {
+        id.update_state(open(), false);
+    });
+    Portal {
+        id,
+        scope: cx.scope,
+        content: Box::new(move || Box::new(content())),
+        placement: Placement::default(),
+        on_click_outside: None,
+        overlay: None,
+    }
+}
+
+impl Portal {
+    pub fn placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Called when a pointer goes down outside the content and outside the portal, e.g. to
+    /// close a popover.
+    pub fn on_click_outside(mut self, action: impl Fn() + 'static) -> Self {
+        self.on_click_outside = Some(Rc::new(action));
+        self
+    }
+
+    fn open(&mut self) {
+        let Some(root) = self.id.root_id() else {
+            return;
+        };
+        let overlay = Overlay::new(root, self.scope, OverlayAnchor::View(self.id), || {
+            (self.content)()
+        })
+        .placement(self.placement)
+        .owner(self.id)
+        .on_click_outside(self.on_click_outside.clone());
+        self.overlay = Some(overlay.id());
+        root.update_state(OverlayUpdate::Mount(overlay), false);
+    }
+
+    fn close(&mut self) {
+        let Some(overlay) = self.overlay.take() else {
+            return;
+        };
+        if let Some(root) = overlay.root_id() {
+            root.update_state(OverlayUpdate::Unmount(overlay), false);
+        }
+    }
+}
+
+impl View for Portal {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(Style::BASE.absolute().inset_px(0.0))
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Portal".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) -> ChangeFlags {
+        if let Ok(open) = state.downcast::<bool>() {
+            if *open && self.overlay.is_none() {
+                self.open();
+            } else if !*open {
+                self.close();
+            }
+        }
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        false
+    }
+
+    fn paint(&mut self, _cx: &mut PaintCx) {}
+}
@@ -0,0 +1,283 @@
+use std::{fmt::Display, hash::Hash, time::Duration};
+
+use glazier::{keyboard_types::Key, kurbo::Rect};
+use leptos_reactive::{create_effect, RwSignal, SignalSet, SignalWith};
+use vello::peniko::Color;
+
+use crate::{
+    animate::animation,
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::{Event, EventListener},
+    id::Id,
+    style::Style,
+    view::{ChangeFlags, FocusScope, View},
+    views::{empty, label, list, stack, Decorators, Empty},
+};
+
+const RADIO_SIZE: f32 = 20.0;
+const DOT_SIZE: f64 = 10.0;
+const SELECTED_COLOR: Color = Color::rgb8(0x3b, 0x82, 0xf6);
+const BORDER_COLOR: Color = Color::rgb8(0xa1, 0xa1, 0xaa);
+const DOT_DURATION: Duration = Duration::from_millis(120);
+
+pub struct RadioButton {
+    id: Id,
+    dot: Empty,
+}
+
+/// A radio button setting `selected` to `value` when it's clicked, or when Enter or Space is
+/// pressed while it's focused. Its dot grows in when it's selected.
+///
+/// Every radio button is a stop for Tab. Use [radio_group] for a group with a single Tab stop
+/// where the arrow keys move the selection.
+///
+/// The button is styled with [Decorators::style] and the dot with [RadioButton::dot_style].
+pub fn radio_button<T: PartialEq + Clone + 'static>(
+    value: T,
+    selected: RwSignal<T>,
+) -> RadioButton {
+    let is_selected = {
+        let value = value.clone();
+        move || selected.with(|selected| *selected == value)
+    };
+    let (id, dot) = ViewContext::new_id_with_child(|| {
+        empty()
+            .base_style(|| {
+                Style::BASE
+                    .border_radius(DOT_SIZE as f32 / 2.0)
+                    .background(SELECTED_COLOR)
+            })
+            .animation({
+                let is_selected = is_selected.clone();
+                let size = move || if is_selected() { DOT_SIZE } else { 0.0 };
+                animation()
+                    .width(size.clone())
+                    .height(size)
+                    .duration(DOT_DURATION)
+            })
+    });
+
+    RadioButton { id, dot }
+        .base_style(move || {
+            Style::BASE
+                .size_px(RADIO_SIZE, RADIO_SIZE)
+                .border(1.0)
+                .border_radius(RADIO_SIZE / 2.0)
+                .border_color(if is_selected() {
+                    SELECTED_COLOR
+                } else {
+                    BORDER_COLOR
+                })
+                .background(Color::WHITE)
+                .items_center()
+                .justify_center()
+        })
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            selected.set(value.clone());
+            true
+        })
+}
+
+/// A column of radio buttons setting `selected`, one for each of `options`, labeled with its
+/// [Display] text. Only the selected radio button is a stop for Tab, and the arrow keys move
+/// the focus and the selection between them.
+pub fn radio_group<T>(options: impl Fn() -> Vec<T> + 'static, selected: RwSignal<T>) -> impl View
+where
+    T: Clone + PartialEq + Eq + Hash + Display + 'static,
+{
+    list(
+        options,
+        |option| option.clone(),
+        move |option| {
+            let text = option.to_string();
+            stack({
+                let option = option.clone();
+                move || (group_button(option, selected), label(move || text.clone()))
+            })
+            .style(|| Style::BASE.items_center().gap_px(6.0))
+            .on_click(move |_| {
+                selected.set(option.clone());
+                true
+            })
+        },
+    )
+    .style(|| Style::BASE.flex_col().gap_px(4.0))
+    .focus_scope(FocusScope::Arrows)
+}
+
+/// A radio button of a [radio_group], with a roving tab index and selected when it's focused
+/// with the arrow keys.
+fn group_button<T: PartialEq + Clone + 'static>(value: T, selected: RwSignal<T>) -> RadioButton {
+    let button = radio_button(value.clone(), selected);
+    let id = button.id();
+    let cx = ViewContext::get_current();
+    create_effect(cx.scope, {
+        let value = value.clone();
+        move |_| {
+            // a roving tab index, so that Tab goes to the selected button
+            let is_selected = selected.with(|selected| *selected == value);
+            id.update_tab_index(if is_selected { 0 } else { -1 });
+        }
+    });
+    button.on_event(EventListener::FocusGained, move |event| {
+        // the selection follows the focus moved with the arrow keys, but not with the
+        // pointer or Tab
+        if is_arrow_key(event) {
+            selected.set(value.clone());
+        }
+        false
+    })
+}
+
+fn is_arrow_key(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::KeyDown(key)
+            if matches!(
+                key.key,
+                Key::ArrowUp | Key::ArrowDown | Key::ArrowLeft | Key::ArrowRight
+            )
+    )
+}
+
+impl RadioButton {
+    pub fn dot_style(self, style: impl Fn() -> Style + 'static) -> Self {
+        let cx = ViewContext::get_current();
+        let dot = self.dot.id();
+        create_effect(cx.scope, move |_| {
+            dot.update_style(style());
+        });
+        self
+    }
+}
+
+impl View for RadioButton {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.dot.id() == id {
+            Some(&self.dot)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.dot.id() == id {
+            Some(&mut self.dot)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.dot]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.dot]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "RadioButton".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| vec![self.dot.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        Some(self.dot.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        // the dot is part of the button, so the button gets the events on it
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.dot.paint_main(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use glazier::{keyboard_types::Key, KeyEvent};
+    use leptos_reactive::{create_runtime, create_rw_signal, raw_scope_and_disposer, SignalGet};
+
+    use super::{radio_button, radio_group};
+    use crate::{
+        app_handle::AppHandle,
+        event::Event,
+        style::Style,
+        view::View,
+        views::{empty, stack, Decorators},
+    };
+
+    fn key_down(key: Key) -> Event {
+        Event::KeyDown(KeyEvent {
+            key,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn tab_goes_to_the_selected_button_of_a_group() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let selected = create_rw_signal(scope, 2);
+        let before = Rc::new(RefCell::new(None));
+        let mut app = AppHandle::new(scope, {
+            let before = before.clone();
+            move || {
+                let first = empty()
+                    .style(|| Style::BASE.size_px(20.0, 20.0))
+                    .keyboard_navigatable();
+                *before.borrow_mut() = Some(first.id());
+                stack(move || (first, radio_group(|| vec![1, 2, 3], selected)))
+                    .style(|| Style::BASE.flex_col())
+            }
+        });
+        app.process_update();
+
+        before.borrow().unwrap().request_focus();
+        app.process_update();
+        app.event(key_down(Key::Tab));
+        assert_eq!(selected.get(), 2);
+        app.event(key_down(Key::ArrowUp));
+        assert_eq!(selected.get(), 1);
+    }
+
+    #[test]
+    fn buttons_outside_of_a_group_are_all_tab_stops() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let selected = create_rw_signal(scope, 1);
+        let ids = Rc::new(RefCell::new(Vec::new()));
+        let mut app = AppHandle::new(scope, {
+            let ids = ids.clone();
+            move || {
+                let first = radio_button(1, selected);
+                let second = radio_button(2, selected);
+                ids.borrow_mut().extend([first.id(), second.id()]);
+                stack(move || (first, second))
+            }
+        });
+        app.process_update();
+        let (first, second) = (ids.borrow()[0], ids.borrow()[1]);
+
+        first.request_focus();
+        app.process_update();
+        app.event(key_down(Key::Tab));
+        assert_eq!(app.app_state.focus, Some(second));
+        assert_eq!(selected.get(), 1);
+    }
+}
@@ -0,0 +1,432 @@
+//! Reordering the items of a `List` or `VirtualList` by dragging them, or with the keyboard.
+
+use std::time::Duration;
+
+use floem_renderer::Renderer;
+use glazier::{
+    keyboard_types::Key,
+    kurbo::{Rect, Size, Vec2},
+    Code, PointerId,
+};
+use leptos_reactive::ScopeDisposer;
+use vello::peniko::Color;
+
+use crate::{
+    context::{AppState, EventCx, PaintCx},
+    event::Event,
+    id::Id,
+    view::View,
+};
+
+use super::scroll_by;
+
+/// How far the pointer moves before an item is picked up
+const DRAG_SLOP: f64 = 4.0;
+/// How close to the edge of the enclosing scroll view the pointer starts scrolling it
+const AUTOSCROLL_EDGE: f64 = 32.0;
+const AUTOSCROLL_INTERVAL: Duration = Duration::from_millis(16);
+const INDICATOR_COLOR: Color = Color::rgb8(0x3b, 0x82, 0xf6);
+
+/// Sent to the list to scroll again while an item is held near an edge
+pub(crate) struct ReorderTick;
+
+/// A child of the list, with its index in the whole list and its extent on the main axis
+#[derive(Clone, Copy)]
+pub(crate) struct ReorderItem {
+    id: Id,
+    index: usize,
+    start: f64,
+    end: f64,
+}
+
+pub(crate) fn reorder_items<V: View>(
+    app_state: &AppState,
+    vertical: bool,
+    first: usize,
+    children: &[Option<(V, ScopeDisposer)>],
+) -> Vec<ReorderItem> {
+    children
+        .iter()
+        .filter_map(|child| child.as_ref())
+        .enumerate()
+        .filter_map(|(i, (child, _))| {
+            let id = child.id();
+            let layout = app_state.get_layout(id)?;
+            let (start, size) = if vertical {
+                (layout.location.y, layout.size.height)
+            } else {
+                (layout.location.x, layout.size.width)
+            };
+            Some(ReorderItem {
+                id,
+                index: first + i,
+                start: start as f64,
+                end: (start + size) as f64,
+            })
+        })
+        .collect()
+}
+
+struct ReorderDrag {
+    from: usize,
+    /// The index the item will have once it's dropped
+    to: usize,
+    /// `None` when the item was picked up with the keyboard
+    pointer: Option<PointerId>,
+    /// Where the pointer went down and where it is now, on the main axis of the list
+    start: f64,
+    pos: f64,
+    /// The extent of the item on the main axis
+    size: f64,
+    started: bool,
+}
+
+pub(crate) struct Reorder {
+    on_reorder: Box<dyn Fn(usize, usize)>,
+    drag: Option<ReorderDrag>,
+    autoscrolling: bool,
+}
+
+impl Reorder {
+    pub(crate) fn new(on_reorder: impl Fn(usize, usize) + 'static) -> Self {
+        Self {
+            on_reorder: Box::new(on_reorder),
+            drag: None,
+            autoscrolling: false,
+        }
+    }
+
+    fn active_drag(&self) -> Option<&ReorderDrag> {
+        self.drag.as_ref().filter(|drag| drag.started)
+    }
+
+    /// Handles the events of the list `id` before its children do. Returns `true` if the event
+    /// was part of a reorder.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn event(
+        &mut self,
+        cx: &mut EventCx,
+        id: Id,
+        vertical: bool,
+        first: usize,
+        total_len: usize,
+        items: &[ReorderItem],
+        event: &Event,
+    ) -> bool {
+        let main = |x: f64, y: f64| if vertical { y } else { x };
+        match event {
+            Event::PointerDown(pointer_event) if pointer_event.button.is_left() => {
+                if self.active_drag().is_some() {
+                    return true;
+                }
+                let pos = main(pointer_event.pos.x, pointer_event.pos.y);
+                self.drag = items
+                    .iter()
+                    .find(|item| item.start <= pos && pos < item.end)
+                    .map(|item| ReorderDrag {
+                        from: item.index,
+                        to: item.index,
+                        pointer: Some(pointer_event.pointer_id),
+                        start: pos,
+                        pos,
+                        size: item.end - item.start,
+                        started: false,
+                    });
+                // the item still gets the event, as it might be a click
+                false
+            }
+            Event::PointerMove(pointer_event) => {
+                let Some(drag) = self
+                    .drag
+                    .as_mut()
+                    .filter(|drag| drag.pointer == Some(pointer_event.pointer_id))
+                else {
+                    return false;
+                };
+                drag.pos = main(pointer_event.pos.x, pointer_event.pos.y);
+                if !drag.started {
+                    if (drag.pos - drag.start).abs() <= DRAG_SLOP {
+                        return false;
+                    }
+                    drag.started = true;
+                    cx.capture_pointer(id, pointer_event.pointer_id);
+                    if cx.app_state.active_pointer == Some(pointer_event.pointer_id) {
+                        // moving the item isn't a click on it
+                        cx.app_state.active = None;
+                    }
+                }
+                drag.to = slot_at(items, drag.from, drag.pos, first, total_len);
+                id.request_paint();
+                if !self.autoscrolling {
+                    self.autoscroll(cx.app_state, id, vertical);
+                }
+                true
+            }
+            Event::PointerUp(pointer_event) | Event::PointerCancel(pointer_event) => {
+                if self.drag.as_ref().and_then(|drag| drag.pointer)
+                    != Some(pointer_event.pointer_id)
+                {
+                    return false;
+                }
+                let drag = self.drag.take().unwrap();
+                if drag.started {
+                    if matches!(event, Event::PointerUp(_)) && drag.from != drag.to {
+                        (self.on_reorder)(drag.from, drag.to);
+                    }
+                    id.request_paint();
+                }
+                drag.started
+            }
+            Event::KeyDown(key_event) => {
+                if let Some(drag) = self.drag.as_mut().filter(|drag| drag.pointer.is_none()) {
+                    let (back, forward) = if vertical {
+                        (Key::ArrowUp, Key::ArrowDown)
+                    } else {
+                        (Key::ArrowLeft, Key::ArrowRight)
+                    };
+                    match &key_event.key {
+                        key if *key == back => drag.to = drag.to.saturating_sub(1),
+                        key if *key == forward => {
+                            drag.to = (drag.to + 1).min(total_len.saturating_sub(1))
+                        }
+                        Key::Enter => {
+                            self.drop_keyboard_drag();
+                        }
+                        Key::Escape => self.drag = None,
+                        _ if key_event.code == Code::Space => {
+                            self.drop_keyboard_drag();
+                        }
+                        _ => return false,
+                    }
+                    id.request_paint();
+                    return true;
+                }
+                if key_event.code != Code::Space || self.active_drag().is_some() {
+                    return false;
+                }
+                // pick up the focused item. Space pressed on a view inside the item, like a
+                // button, only goes to that view.
+                let Some(focus) = cx.app_state.focus else {
+                    return false;
+                };
+                let Some(item) = items.iter().find(|item| item.id == focus) else {
+                    return false;
+                };
+                self.drag = Some(ReorderDrag {
+                    from: item.index,
+                    to: item.index,
+                    pointer: None,
+                    start: item.start,
+                    pos: item.start,
+                    size: item.end - item.start,
+                    started: true,
+                });
+                id.request_paint();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn drop_keyboard_drag(&mut self) {
+        if let Some(drag) = self.drag.take() {
+            if drag.from != drag.to {
+                (self.on_reorder)(drag.from, drag.to);
+            }
+        }
+    }
+
+    /// Scrolls the scroll view the list is in while the dragged item is near its edges, and
+    /// keeps scrolling with a timer until it's moved away.
+    pub(crate) fn autoscroll(&mut self, app_state: &mut AppState, id: Id, vertical: bool) {
+        self.autoscrolling = false;
+        let Some(drag) = self
+            .drag
+            .as_mut()
+            .filter(|drag| drag.started && drag.pointer.is_some())
+        else {
+            return;
+        };
+        let Some((scroll, viewport, offset, content)) = enclosing_scroll(app_state, id) else {
+            return;
+        };
+        let (pos, start, end, max) = if vertical {
+            (
+                drag.pos + offset.y,
+                viewport.y0,
+                viewport.y1,
+                content.height - viewport.height(),
+            )
+        } else {
+            (
+                drag.pos + offset.x,
+                viewport.x0,
+                viewport.x1,
+                content.width - viewport.width(),
+            )
+        };
+        // scroll faster the closer the pointer gets to the edge
+        let delta = if pos < start + AUTOSCROLL_EDGE {
+            -(start + AUTOSCROLL_EDGE - pos).min(AUTOSCROLL_EDGE) / 2.0
+        } else if pos > end - AUTOSCROLL_EDGE {
+            (pos - end + AUTOSCROLL_EDGE).min(AUTOSCROLL_EDGE) / 2.0
+        } else {
+            0.0
+        };
+        let delta = (start + delta).clamp(0.0, max.max(0.0)) - start;
+        if delta == 0.0 {
+            return;
+        }
+
+        scroll_by(
+            scroll,
+            if vertical {
+                Vec2::new(0.0, delta)
+            } else {
+                Vec2::new(delta, 0.0)
+            },
+        );
+        // the list moves under the pointer
+        drag.pos += delta;
+        self.autoscrolling = true;
+        app_state.request_timer(
+            AUTOSCROLL_INTERVAL,
+            Box::new(move || id.update_state(ReorderTick, false)),
+        );
+    }
+
+    /// Updates the slot of the dragged item after the list scrolled under it.
+    pub(crate) fn update_slot(&mut self, items: &[ReorderItem], first: usize, total_len: usize) {
+        if let Some(drag) = self.drag.as_mut().filter(|drag| drag.started) {
+            if drag.pointer.is_some() {
+                drag.to = slot_at(items, drag.from, drag.pos, first, total_len);
+            }
+        }
+    }
+
+    /// Where the gap left for the dragged item starts on the main axis.
+    fn gap_start(&self, items: &[ReorderItem]) -> Option<f64> {
+        let drag = self.active_drag()?;
+        let target = items.iter().find(|item| item.index == drag.to)?;
+        Some(if drag.to > drag.from {
+            target.end - drag.size
+        } else {
+            target.start
+        })
+    }
+
+    /// How far an item is moved on the main axis to make room for the dragged item.
+    fn shift(&self, item: &ReorderItem) -> f64 {
+        let Some(drag) = self.active_drag() else {
+            return 0.0;
+        };
+        if drag.from < drag.to && item.index > drag.from && item.index <= drag.to {
+            -drag.size
+        } else if drag.to < drag.from && item.index >= drag.to && item.index < drag.from {
+            drag.size
+        } else {
+            0.0
+        }
+    }
+}
+
+/// The index the dragged item gets if it's dropped at `pos`.
+fn slot_at(items: &[ReorderItem], from: usize, pos: f64, first: usize, total_len: usize) -> usize {
+    let before = items
+        .iter()
+        .filter(|item| item.index != from && (item.start + item.end) / 2.0 < pos)
+        .count();
+    // the items before the first child are before the slot too
+    let to = first - usize::from(from < first) + before;
+    to.min(total_len.saturating_sub(1))
+}
+
+/// The nearest scroll view around `id`, with the viewport of its child, the offset from `id`
+/// to its child, and the size of its child.
+pub(crate) fn enclosing_scroll(app_state: &AppState, id: Id) -> Option<(Id, Rect, Vec2, Size)> {
+    let mut current = id;
+    let mut offset = Vec2::ZERO;
+    loop {
+        let layout = app_state.get_layout(current)?;
+        if let Some(viewport) = app_state.view_states.get(&current).and_then(|s| s.viewport) {
+            let size = Size::new(layout.size.width as f64, layout.size.height as f64);
+            return Some((current.parent()?, viewport, offset, size));
+        }
+        offset += Vec2::new(layout.location.x as f64, layout.location.y as f64);
+        current = current.parent()?;
+    }
+}
+
+/// Paints the children of the list `id`, making room for the dragged item and painting it
+/// above the others.
+pub(crate) fn paint_children<V: View>(
+    cx: &mut PaintCx,
+    id: Id,
+    reorder: Option<&Reorder>,
+    vertical: bool,
+    first: usize,
+    children: &mut [Option<(V, ScopeDisposer)>],
+) {
+    let Some(reorder) = reorder.filter(|reorder| reorder.active_drag().is_some()) else {
+        for child in children.iter_mut() {
+            if let Some((child, _)) = child.as_mut() {
+                child.paint_main(cx);
+            }
+        }
+        return;
+    };
+    let items = reorder_items(cx.app_state, vertical, first, children);
+    let drag = reorder.active_drag().unwrap();
+    let axis = |offset: f64| {
+        if vertical {
+            (0.0, offset)
+        } else {
+            (offset, 0.0)
+        }
+    };
+
+    let mut dragged = None;
+    for child in children.iter_mut() {
+        let Some((child, _)) = child.as_mut() else {
+            continue;
+        };
+        let Some(item) = items.iter().find(|item| item.id == child.id()) else {
+            continue;
+        };
+        if item.index == drag.from {
+            dragged = Some((child, *item));
+            continue;
+        }
+        cx.save();
+        cx.offset(axis(reorder.shift(item)));
+        child.paint_main(cx);
+        cx.restore();
+    }
+
+    let gap_start = reorder.gap_start(&items);
+    if let Some(gap_start) = gap_start {
+        let size = cx
+            .get_layout(id)
+            .map(|layout| Size::new(layout.size.width as f64, layout.size.height as f64))
+            .unwrap_or_default();
+        let rect = if vertical {
+            Rect::new(0.0, gap_start - 1.0, size.width, gap_start + 1.0)
+        } else {
+            Rect::new(gap_start - 1.0, 0.0, gap_start + 1.0, size.height)
+        };
+        cx.fill(&rect, INDICATOR_COLOR);
+    }
+
+    if let Some((child, item)) = dragged {
+        let offset = if drag.pointer.is_some() {
+            drag.pos - drag.start
+        } else {
+            gap_start.map(|gap| gap - item.start).unwrap_or(0.0)
+        };
+        cx.save();
+        cx.set_z_index(1000);
+        cx.offset(axis(offset));
+        child.paint_main(cx);
+        cx.restore();
+    }
+}
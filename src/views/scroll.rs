@@ -27,6 +27,44 @@ enum ScrollState {
     HiddenBar(bool),
 }
 
+/// Scrolls the scroll view `id` by `delta`.
+pub(crate) fn scroll_by(id: Id, delta: Vec2) {
+    id.update_state(ScrollState::ScrollDelta(delta), false);
+}
+
+/// Scrolls the scroll view `id` so that `rect` of its child is visible, after the next layout.
+pub(crate) fn ensure_visible(id: Id, rect: Rect) {
+    id.update_state(ScrollState::EnsureVisible(rect), true);
+}
+
+/// Scrolls every scroll view the view `id` is in so that the view is visible.
+pub(crate) fn scroll_into_view(app_state: &AppState, id: Id) {
+    let Some(layout) = app_state.get_layout(id) else {
+        return;
+    };
+    // the rect of the view, relative to `current`
+    let mut rect = Rect::new(
+        0.0,
+        0.0,
+        layout.size.width as f64,
+        layout.size.height as f64,
+    );
+    let mut current = id;
+    while let Some(parent) = current.parent() {
+        let Some(layout) = app_state.get_layout(current) else {
+            return;
+        };
+        let mut offset = Vec2::new(layout.location.x as f64, layout.location.y as f64);
+        if let Some(viewport) = app_state.view_states.get(&current).and_then(|s| s.viewport) {
+            // `current` is the child of the scroll view `parent`
+            parent.update_state(ScrollState::EnsureVisible(rect), false);
+            offset -= viewport.origin().to_vec2();
+        }
+        rect = rect + offset;
+        current = parent;
+    }
+}
+
 /// Minimum length for any scrollbar to be when measured on that
 /// scrollbar's primary axis.
 const SCROLLBAR_MIN_SIZE: f64 = 10.0;
@@ -666,7 +704,7 @@ impl<V: View> View for Scroll<V> {
         if let Event::PointerWheel(pointer_event) = &event {
             if let Some(listener) = event.listener() {
                 if let Some(action) = cx.get_event_listener(self.id, &listener) {
-                    if (*action)(&event) {
+                    if action(&event) {
                         return true;
                     }
                 }
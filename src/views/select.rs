@@ -0,0 +1,328 @@
+use std::{
+    cell::RefCell,
+    fmt::Display,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use glazier::{keyboard_types::Key, kurbo::Rect};
+use leptos_reactive::{create_rw_signal, RwSignal, SignalGet, SignalSet, SignalWith};
+use vello::peniko::Color;
+
+use crate::{
+    animate::Clock,
+    app_handle::ViewContext,
+    event::{Event, EventListener},
+    overlay::Placement,
+    style::Style,
+    view::View,
+    views::{container, label, list, portal, scroll, stack, Decorators},
+};
+
+pub(crate) const OPTION_HEIGHT: f64 = 28.0;
+pub(crate) const MAX_POPUP_HEIGHT: f32 = 240.0;
+pub(crate) const HIGHLIGHT_COLOR: Color = Color::rgb8(0xdb, 0xea, 0xfe);
+pub(crate) const BORDER_COLOR: Color = Color::rgb8(0xa1, 0xa1, 0xaa);
+/// Keys typed within this long of each other search for an option together
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The text typed to jump to an option.
+struct TypeAhead {
+    query: String,
+    last_key: Instant,
+}
+
+impl TypeAhead {
+    fn push(&mut self, text: &str) -> &str {
+        let now = Clock::now();
+        if now.saturating_duration_since(self.last_key) > TYPE_AHEAD_TIMEOUT {
+            self.query.clear();
+        }
+        self.last_key = now;
+        self.query.push_str(&text.to_lowercase());
+        &self.query
+    }
+
+    fn is_typing(&self) -> bool {
+        !self.query.is_empty() && Clock::elapsed_since(self.last_key) <= TYPE_AHEAD_TIMEOUT
+    }
+}
+
+/// The option whose text starts with `query`, ignoring case, searching from `current` and
+/// wrapping around. A single letter searches from the option after `current`, so that typing
+/// the same letter again goes through the options starting with it.
+fn type_ahead_match(texts: &[String], query: &str, current: Option<usize>) -> Option<usize> {
+    if texts.is_empty() || query.is_empty() {
+        return None;
+    }
+    let start = match current {
+        Some(current) if query.chars().count() == 1 => current + 1,
+        Some(current) => current,
+        None => 0,
+    };
+    (0..texts.len())
+        .map(|i| (start + i) % texts.len())
+        .find(|&i| texts[i].to_lowercase().starts_with(query))
+}
+
+/// The rect of the option at `index` in a popup list, for scrolling it into view.
+pub(crate) fn option_rect(index: usize) -> Rect {
+    Rect::new(
+        0.0,
+        index as f64 * OPTION_HEIGHT,
+        1.0,
+        (index + 1) as f64 * OPTION_HEIGHT,
+    )
+}
+
+pub(crate) fn popup_style() -> Style {
+    Style::BASE
+        .max_height_px(MAX_POPUP_HEIGHT)
+        .border(1.0)
+        .border_radius(4.0)
+        .border_color(BORDER_COLOR)
+        .background(Color::WHITE)
+}
+
+/// A button showing the selected option of `options`, which opens the list of options below
+/// it when it's clicked. Picking an option sets `selected` and closes the list.
+///
+/// While the button is focused, the arrow keys, Enter and Space open the list. Once it's open,
+/// the arrow keys, Home and End move the highlight, Enter or Space pick the highlighted option
+/// and Escape closes it. Typing the start of an option jumps to it, picking it right away while
+/// the list is closed.
+///
+/// The options are shown with their [Display] text.
+pub fn select<T>(options: impl Fn() -> Vec<T> + 'static, selected: RwSignal<T>) -> impl View
+where
+    T: Clone + PartialEq + Display + 'static,
+{
+    let cx = ViewContext::get_current();
+    let options = Rc::new(options);
+    let open = create_rw_signal(cx.scope, false);
+    let highlighted = create_rw_signal(cx.scope, None::<usize>);
+    let type_ahead = Rc::new(RefCell::new(TypeAhead {
+        query: String::new(),
+        last_key: Clock::now(),
+    }));
+
+    let selected_index = {
+        let options = options.clone();
+        move || {
+            options()
+                .iter()
+                .position(|option| selected.with(|selected| selected == option))
+        }
+    };
+    let choose = {
+        let options = options.clone();
+        move |index: usize| {
+            if let Some(option) = options().into_iter().nth(index) {
+                selected.set(option);
+            }
+            open.set(false);
+        }
+    };
+    let show = {
+        let selected_index = selected_index.clone();
+        move || {
+            highlighted.set(selected_index());
+            open.set(true);
+        }
+    };
+
+    let popup = {
+        let options = options.clone();
+        let choose = choose.clone();
+        move || {
+            let options = options.clone();
+            let choose = choose.clone();
+            scroll(move || {
+                list(
+                    move || options().into_iter().enumerate(),
+                    |(index, option)| (*index, option.to_string()),
+                    move |(index, option)| {
+                        let text = option.to_string();
+                        let choose = choose.clone();
+                        container(move || label(move || text.clone()))
+                            .style(move || {
+                                Style::BASE
+                                    .height_px(OPTION_HEIGHT as f32)
+                                    .padding_horiz_px(8.0)
+                                    .items_center()
+                                    .apply_if(highlighted.get() == Some(index), |s| {
+                                        s.background(HIGHLIGHT_COLOR)
+                                    })
+                                    .apply_if(selected.with(|s| *s == option), |s| s.font_bold())
+                            })
+                            .on_event(EventListener::PointerEnter, move |_| {
+                                highlighted.set(Some(index));
+                                false
+                            })
+                            .on_click(move |_| {
+                                choose(index);
+                                true
+                            })
+                    },
+                )
+                .style(|| Style::BASE.flex_col().min_width_pct(100.0))
+            })
+            .on_ensure_visible(move || highlighted.get().map(option_rect).unwrap_or_default())
+            .style(popup_style)
+        }
+    };
+
+    let on_key = {
+        let show = show.clone();
+        move |event: &Event| -> bool {
+            let Event::KeyDown(key_event) = event else {
+                return false;
+            };
+            let is_open = open.get();
+            let len = options().len();
+            let is_typing = type_ahead.borrow().is_typing();
+            let is_space = matches!(&key_event.key, Key::Character(text) if text == " ");
+            match &key_event.key {
+                Key::Character(text)
+                    if !key_event.mods.ctrl()
+                        && !key_event.mods.meta()
+                        && (!is_space || is_typing) =>
+                {
+                    let texts = options()
+                        .iter()
+                        .map(|option| option.to_string())
+                        .collect::<Vec<_>>();
+                    let current = if is_open {
+                        highlighted.get()
+                    } else {
+                        selected_index()
+                    };
+                    let mut type_ahead = type_ahead.borrow_mut();
+                    let query = type_ahead.push(text);
+                    if let Some(index) = type_ahead_match(&texts, query, current) {
+                        if is_open {
+                            highlighted.set(Some(index));
+                        } else {
+                            choose(index);
+                        }
+                    }
+                    true
+                }
+                Key::Escape if is_open => {
+                    open.set(false);
+                    true
+                }
+                Key::ArrowDown | Key::ArrowUp | Key::Enter if !is_open => {
+                    show();
+                    true
+                }
+                _ if is_space && !is_open => {
+                    show();
+                    true
+                }
+                _ if is_space || key_event.key == Key::Enter => {
+                    match highlighted.get() {
+                        Some(index) => choose(index),
+                        None => open.set(false),
+                    }
+                    true
+                }
+                Key::ArrowDown | Key::ArrowUp | Key::Home | Key::End if is_open && len > 0 => {
+                    let last = len - 1;
+                    let index = match (&key_event.key, highlighted.get()) {
+                        (Key::Home, _) | (Key::ArrowDown, None) => 0,
+                        (Key::End, _) | (Key::ArrowUp, None) => last,
+                        (Key::ArrowDown, Some(index)) => (index + 1).min(last),
+                        (_, Some(index)) => index.saturating_sub(1),
+                    };
+                    highlighted.set(Some(index));
+                    true
+                }
+                _ => false,
+            }
+        }
+    };
+
+    stack(move || {
+        (
+            label(move || selected.with(|selected| selected.to_string())),
+            label(|| "▾".to_string()),
+            portal(move || open.get(), popup)
+                .placement(Placement::default().gap(2.0))
+                .on_click_outside(move || open.set(false)),
+        )
+    })
+    .base_style(|| {
+        Style::BASE
+            .min_width_px(120.0)
+            .padding_vert_px(4.0)
+            .padding_horiz_px(8.0)
+            .gap_px(8.0)
+            .items_center()
+            .justify_between()
+            .border(1.0)
+            .border_radius(4.0)
+            .border_color(BORDER_COLOR)
+            .background(Color::WHITE)
+    })
+    .keyboard_navigatable()
+    .on_click(move |_| {
+        if open.get() {
+            open.set(false);
+        } else {
+            show();
+        }
+        true
+    })
+    .on_event_capture(EventListener::KeyDown, move |event, propagation| {
+        // the keys are handled before the keyboard click, which Enter and Space would trigger
+        if on_key(event) {
+            propagation.stop_propagation();
+        }
+    })
+    .on_event(EventListener::FocusLost, move |_| {
+        open.set(false);
+        false
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{type_ahead_match, TypeAhead};
+    use crate::animate::Clock;
+
+    #[test]
+    fn type_ahead_finds_options_by_prefix() {
+        let texts = ["Apple", "Banana", "blueberry", "Cherry"].map(String::from);
+        assert_eq!(type_ahead_match(&texts, "bl", None), Some(2));
+        assert_eq!(type_ahead_match(&texts, "bl", Some(2)), Some(2));
+        assert_eq!(type_ahead_match(&texts, "ch", Some(0)), Some(3));
+        assert_eq!(type_ahead_match(&texts, "x", Some(0)), None);
+    }
+
+    #[test]
+    fn repeated_letter_cycles_through_options() {
+        let texts = ["Apple", "Banana", "blueberry", "Cherry"].map(String::from);
+        assert_eq!(type_ahead_match(&texts, "b", Some(0)), Some(1));
+        assert_eq!(type_ahead_match(&texts, "b", Some(1)), Some(2));
+        assert_eq!(type_ahead_match(&texts, "b", Some(2)), Some(1));
+    }
+
+    #[test]
+    fn type_ahead_resets_after_a_pause() {
+        Clock::manual();
+        let mut type_ahead = TypeAhead {
+            query: String::new(),
+            last_key: Clock::now(),
+        };
+        assert_eq!(type_ahead.push("B"), "b");
+        Clock::advance(Duration::from_millis(1000));
+        assert!(type_ahead.is_typing());
+        assert_eq!(type_ahead.push("l"), "bl");
+        Clock::advance(Duration::from_millis(1001));
+        assert!(!type_ahead.is_typing());
+        assert_eq!(type_ahead.push("c"), "c");
+    }
+}
@@ -0,0 +1,590 @@
+use std::any::Any;
+
+use glazier::{
+    keyboard_types::Key,
+    kurbo::{Point, Rect, Size},
+    PointerId,
+};
+use leptos_reactive::{create_effect, RwSignal, SignalGet, SignalSet};
+use vello::peniko::Color;
+
+use crate::{
+    accessibility::{AccessNode, Orientation, Role},
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::{ComputedStyle, Style},
+    view::{paint_bg, paint_border, ChangeFlags, View},
+};
+
+const TRACK_COLOR: Color = Color::rgb8(0xd4, 0xd4, 0xd8);
+const FILL_COLOR: Color = Color::rgb8(0x3b, 0x82, 0xf6);
+const THUMB_BORDER_COLOR: Color = Color::rgb8(0xa1, 0xa1, 0xaa);
+/// PageUp and PageDown move the value by this many steps
+const PAGE_STEPS: f64 = 10.0;
+
+enum SliderValue {
+    Single(RwSignal<f64>),
+    Range(RwSignal<(f64, f64)>),
+}
+
+impl SliderValue {
+    fn get(&self) -> Vec<f64> {
+        match self {
+            SliderValue::Single(value) => vec![value.get()],
+            SliderValue::Range(range) => {
+                let (start, end) = range.get();
+                vec![start, end]
+            }
+        }
+    }
+
+    /// Sets the value of `thumb`, keeping the thumbs of a range in order.
+    fn set(&self, values: &[f64], thumb: usize, value: f64) {
+        match self {
+            SliderValue::Single(signal) => signal.set(value),
+            SliderValue::Range(signal) => signal.set(if thumb == 0 {
+                (value.min(values[1]), values[1])
+            } else {
+                (values[0], value.max(values[0]))
+            }),
+        }
+    }
+}
+
+enum SliderUpdate {
+    Values(Vec<f64>),
+    TrackStyle(Style),
+    FillStyle(Style),
+    ThumbStyle(Style),
+}
+
+/// The thumb held by a pointer
+struct SliderDrag {
+    pointer: PointerId,
+    thumb: usize,
+}
+
+pub struct Slider {
+    id: Id,
+    value: SliderValue,
+    values: Vec<f64>,
+    min: f64,
+    max: f64,
+    step: f64,
+    vertical: bool,
+    track_style: ComputedStyle,
+    fill_style: ComputedStyle,
+    thumb_style: ComputedStyle,
+    drag: Option<SliderDrag>,
+    /// The thumb moved by the keyboard, the one last moved with a pointer
+    thumb: usize,
+}
+
+/// A slider setting `value` between a minimum and a maximum, 0 and 1 unless they're changed
+/// with [Slider::min] and [Slider::max].
+///
+/// It's moved by dragging its thumb or clicking on its track, and with the arrow keys,
+/// PageUp and PageDown, Home and End when it's focused.
+pub fn slider(value: RwSignal<f64>) -> Slider {
+    new_slider(SliderValue::Single(value))
+}
+
+/// A slider with two thumbs setting the start and the end of `range`.
+///
+/// A click on the track moves the closest thumb, and the keyboard moves the thumb last moved
+/// with a pointer.
+pub fn range_slider(range: RwSignal<(f64, f64)>) -> Slider {
+    new_slider(SliderValue::Range(range))
+}
+
+fn new_slider(value: SliderValue) -> Slider {
+    let cx = ViewContext::get_current();
+    let id = cx.new_id();
+    id.keyboard_navigatable();
+    let values = value.get();
+    let signal = match &value {
+        SliderValue::Single(value) => SliderValue::Single(*value),
+        SliderValue::Range(range) => SliderValue::Range(*range),
+    };
+    create_effect(cx.scope, move |_| {
+        id.update_state(SliderUpdate::Values(signal.get()), false);
+    });
+    Slider {
+        id,
+        value,
+        values,
+        min: 0.0,
+        max: 1.0,
+        step: 0.0,
+        vertical: false,
+        track_style: default_track_style().compute(&ComputedStyle::default()),
+        fill_style: default_fill_style().compute(&ComputedStyle::default()),
+        thumb_style: default_thumb_style().compute(&ComputedStyle::default()),
+        drag: None,
+        thumb: 0,
+    }
+}
+
+fn default_track_style() -> Style {
+    Style::BASE
+        .size_px(4.0, 4.0)
+        .border_radius(2.0)
+        .background(TRACK_COLOR)
+}
+
+fn default_fill_style() -> Style {
+    Style::BASE
+        .size_px(4.0, 4.0)
+        .border_radius(2.0)
+        .background(FILL_COLOR)
+}
+
+fn default_thumb_style() -> Style {
+    Style::BASE
+        .size_px(16.0, 16.0)
+        .border_radius(8.0)
+        .border(1.0)
+        .border_color(THUMB_BORDER_COLOR)
+        .background(Color::WHITE)
+}
+
+impl Slider {
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = min;
+        self
+    }
+
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = max;
+        self
+    }
+
+    /// The value moves by multiples of `step` from the minimum. It moves freely if `step` is 0,
+    /// the default, and the keyboard then moves it by a hundredth of the range.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step.max(0.0);
+        self
+    }
+
+    /// Lays the slider out from bottom to top.
+    pub fn vertical(mut self) -> Self {
+        self.vertical = true;
+        self
+    }
+
+    /// The style of the track, the thickness of which is its height, or its width if the
+    /// slider is vertical.
+    pub fn track_style(self, style: impl Fn() -> Style + 'static) -> Self {
+        self.part_style(style, SliderUpdate::TrackStyle)
+    }
+
+    /// The style of the part of the track up to the thumb, or between the thumbs of a range.
+    pub fn fill_style(self, style: impl Fn() -> Style + 'static) -> Self {
+        self.part_style(style, SliderUpdate::FillStyle)
+    }
+
+    /// The style of the thumbs, which are as big as its width and height.
+    pub fn thumb_style(self, style: impl Fn() -> Style + 'static) -> Self {
+        self.part_style(style, SliderUpdate::ThumbStyle)
+    }
+
+    fn part_style(
+        self,
+        style: impl Fn() -> Style + 'static,
+        update: fn(Style) -> SliderUpdate,
+    ) -> Self {
+        let cx = ViewContext::get_current();
+        let id = self.id;
+        create_effect(cx.scope, move |_| {
+            id.update_state(update(style()), false);
+        });
+        self
+    }
+
+    /// Where the thumbs can go along the main axis, from the minimum to the maximum.
+    fn thumb_range(&self, size: Size) -> (f64, f64) {
+        let (length, thumb) = if self.vertical {
+            (size.height, points(self.thumb_style.height, 0.0))
+        } else {
+            (size.width, points(self.thumb_style.width, 0.0))
+        };
+        let start = thumb / 2.0;
+        (start, (length - thumb / 2.0).max(start))
+    }
+
+    /// The position of `value` along the main axis, in view coordinates.
+    fn position(&self, size: Size, value: f64) -> f64 {
+        let (start, end) = self.thumb_range(size);
+        let fraction = if self.max > self.min {
+            ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        if self.vertical {
+            end - fraction * (end - start)
+        } else {
+            start + fraction * (end - start)
+        }
+    }
+
+    /// The value at the point `pos`, in view coordinates.
+    fn value_at(&self, size: Size, pos: Point) -> f64 {
+        let (start, end) = self.thumb_range(size);
+        let fraction = if end > start {
+            let offset = if self.vertical {
+                end - pos.y
+            } else {
+                pos.x - start
+            };
+            (offset / (end - start)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        self.snap(self.min + fraction * (self.max - self.min))
+    }
+
+    fn snap(&self, value: f64) -> f64 {
+        let value = if self.step > 0.0 {
+            self.min + ((value - self.min) / self.step).round() * self.step
+        } else {
+            value
+        };
+        value.clamp(self.min, self.max.max(self.min))
+    }
+
+    fn key_step(&self) -> f64 {
+        if self.step > 0.0 {
+            self.step
+        } else {
+            (self.max - self.min) / 100.0
+        }
+    }
+
+    fn set_value(&mut self, thumb: usize, value: f64) {
+        if self.values.get(thumb) != Some(&value) {
+            self.value.set(&self.values, thumb, value);
+        }
+    }
+
+    /// The rect of the track or the fill from `from` to `to` along the main axis.
+    fn bar(&self, size: Size, style: &ComputedStyle, from: f64, to: f64) -> Rect {
+        let (from, to) = (from.min(to), from.max(to));
+        if self.vertical {
+            let thickness = points(style.width, 4.0);
+            let x = (size.width - thickness) / 2.0;
+            Rect::new(x, from, x + thickness, to)
+        } else {
+            let thickness = points(style.height, 4.0);
+            let y = (size.height - thickness) / 2.0;
+            Rect::new(from, y, to, y + thickness)
+        }
+    }
+
+    fn thumb_rect(&self, size: Size, value: f64) -> Rect {
+        let pos = self.position(size, value);
+        let thumb = Size::new(
+            points(self.thumb_style.width, 0.0),
+            points(self.thumb_style.height, 0.0),
+        );
+        let center = if self.vertical {
+            Point::new(size.width / 2.0, pos)
+        } else {
+            Point::new(pos, size.height / 2.0)
+        };
+        Rect::from_center_size(center, thumb)
+    }
+}
+
+/// The length of `dimension` if it's in points, or else `default`.
+fn points(dimension: taffy::style::Dimension, default: f64) -> f64 {
+    match dimension {
+        taffy::style::Dimension::Points(points) => points as f64,
+        _ => default,
+    }
+}
+
+/// Paints a part of the slider with its background and border.
+fn paint_part(cx: &mut PaintCx, style: &ComputedStyle, rect: Rect, disabled: bool) {
+    let mut style = style.clone();
+    if disabled {
+        // disabled sliders are faded
+        style.background = style.background.map(|color| Color {
+            a: color.a / 2,
+            ..color
+        });
+        style.border_color.a /= 2;
+    }
+    cx.save();
+    cx.offset((rect.x0, rect.y0));
+    paint_bg(cx, &style, rect.size());
+    paint_border(cx, &style, rect.size());
+    cx.restore();
+}
+
+impl View for Slider {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        Some(if self.vertical {
+            Style::BASE.width_px(20.0).min_height_px(100.0)
+        } else {
+            Style::BASE.height_px(20.0).min_width_px(100.0)
+        })
+    }
+
+    fn child(&self, _id: Id) -> Option<&dyn View> {
+        None
+    }
+
+    fn child_mut(&mut self, _id: Id) -> Option<&mut dyn View> {
+        None
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        Vec::new()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        Vec::new()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        match self.value {
+            SliderValue::Single(_) => "Slider".into(),
+            SliderValue::Range(_) => "RangeSlider".into(),
+        }
+    }
+
+    /// A slider node for each thumb, named "start" and "end" for a range.
+    fn accessibility(&self) -> Vec<AccessNode> {
+        let names: &[&str] = match self.value {
+            SliderValue::Single(_) => &[],
+            SliderValue::Range(_) => &["start", "end"],
+        };
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(thumb, value)| AccessNode {
+                name: names.get(thumb).map(|name| name.to_string()),
+                value: Some(*value),
+                min: Some(self.min),
+                max: Some(self.max),
+                step: Some(self.step).filter(|step| *step > 0.0),
+                orientation: Some(if self.vertical {
+                    Orientation::Vertical
+                } else {
+                    Orientation::Horizontal
+                }),
+                ..AccessNode::new(Role::Slider)
+            })
+            .collect()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, state: Box<dyn Any>) -> ChangeFlags {
+        if let Ok(update) = state.downcast::<SliderUpdate>() {
+            match *update {
+                SliderUpdate::Values(values) => {
+                    self.values = values;
+                    return ChangeFlags::PAINT | ChangeFlags::ACCESSIBILITY;
+                }
+                SliderUpdate::TrackStyle(style) => {
+                    self.track_style = default_track_style()
+                        .apply(style)
+                        .compute(&ComputedStyle::default());
+                }
+                SliderUpdate::FillStyle(style) => {
+                    self.fill_style = default_fill_style()
+                        .apply(style)
+                        .compute(&ComputedStyle::default());
+                }
+                SliderUpdate::ThumbStyle(style) => {
+                    self.thumb_style = default_thumb_style()
+                        .apply(style)
+                        .compute(&ComputedStyle::default());
+                }
+            }
+            ChangeFlags::PAINT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, false, |_| Vec::new())
+    }
+
+    fn event(&mut self, cx: &mut EventCx, _id_path: Option<&[Id]>, event: Event) -> bool {
+        let size = cx
+            .get_layout(self.id)
+            .map(|layout| Size::new(layout.size.width as f64, layout.size.height as f64))
+            .unwrap_or_default();
+        match &event {
+            Event::PointerDown(pointer_event) => {
+                if !pointer_event.button.is_left() || !size.to_rect().contains(pointer_event.pos) {
+                    return false;
+                }
+                let value = self.value_at(size, pointer_event.pos);
+                // a range moves the thumb closest to the pointer
+                let thumb = match self.values[..] {
+                    [start, end] if value > end || (value - end).abs() < (value - start).abs() => 1,
+                    _ => 0,
+                };
+                self.thumb = thumb;
+                self.drag = Some(SliderDrag {
+                    pointer: pointer_event.pointer_id,
+                    thumb,
+                });
+                cx.capture_pointer(self.id, pointer_event.pointer_id);
+                cx.app_state.update_focus(self.id, false);
+                cx.update_active(self.id);
+                self.set_value(thumb, value);
+                true
+            }
+            Event::PointerMove(pointer_event) => {
+                let Some(thumb) = self
+                    .drag
+                    .as_ref()
+                    .filter(|drag| drag.pointer == pointer_event.pointer_id)
+                    .map(|drag| drag.thumb)
+                else {
+                    return false;
+                };
+                let value = self.value_at(size, pointer_event.pos);
+                self.set_value(thumb, value);
+                true
+            }
+            Event::PointerUp(pointer_event) | Event::PointerCancel(pointer_event) => {
+                if self.drag.as_ref().map(|drag| drag.pointer) != Some(pointer_event.pointer_id) {
+                    return false;
+                }
+                self.drag = None;
+                true
+            }
+            Event::KeyDown(key_event) => {
+                let Some(&current) = self.values.get(self.thumb) else {
+                    return false;
+                };
+                let step = self.key_step();
+                let value = match &key_event.key {
+                    Key::ArrowRight | Key::ArrowUp => current + step,
+                    Key::ArrowLeft | Key::ArrowDown => current - step,
+                    Key::PageUp => current + step * PAGE_STEPS,
+                    Key::PageDown => current - step * PAGE_STEPS,
+                    Key::Home => self.min,
+                    Key::End => self.max,
+                    _ => return false,
+                };
+                let value = self.snap(value);
+                self.set_value(self.thumb, value);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let Some(layout) = cx.get_layout(self.id) else {
+            return;
+        };
+        let size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        let disabled = cx.app_state.is_disabled(&self.id);
+
+        let main_length = if self.vertical {
+            size.height
+        } else {
+            size.width
+        };
+        let track = self.bar(size, &self.track_style, 0.0, main_length);
+        paint_part(cx, &self.track_style, track, disabled);
+
+        let positions: Vec<f64> = self
+            .values
+            .iter()
+            .map(|value| self.position(size, *value))
+            .collect();
+        let (from, to) = match positions[..] {
+            [pos] if self.vertical => (pos, main_length),
+            [pos] => (0.0, pos),
+            [start, end] => (start, end),
+            _ => return,
+        };
+        let fill = self.bar(size, &self.fill_style, from, to);
+        paint_part(cx, &self.fill_style, fill, disabled);
+
+        for value in &self.values {
+            let thumb = self.thumb_rect(size, *value);
+            paint_part(cx, &self.thumb_style, thumb, disabled);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use leptos_reactive::{create_runtime, create_rw_signal, raw_scope_and_disposer};
+
+    use super::*;
+
+    fn test_slider(min: f64, max: f64, step: f64, vertical: bool) -> Slider {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        Slider {
+            id: Id::next(),
+            value: SliderValue::Single(create_rw_signal(scope, 0.0)),
+            values: vec![0.0],
+            min,
+            max,
+            step,
+            vertical,
+            track_style: default_track_style().compute(&ComputedStyle::default()),
+            fill_style: default_fill_style().compute(&ComputedStyle::default()),
+            thumb_style: default_thumb_style().compute(&ComputedStyle::default()),
+            drag: None,
+            thumb: 0,
+        }
+    }
+
+    #[test]
+    fn snaps_to_steps_inside_the_range() {
+        let slider = test_slider(0.0, 10.0, 2.0, false);
+        assert_eq!(slider.snap(3.2), 4.0);
+        assert_eq!(slider.snap(-5.0), 0.0);
+        assert_eq!(slider.snap(11.0), 10.0);
+    }
+
+    #[test]
+    fn maps_positions_to_values_from_the_thumb_centers() {
+        let size = Size::new(116.0, 20.0);
+        let slider = test_slider(0.0, 100.0, 0.0, false);
+        // the thumb is 16 wide, so its center goes from 8 to 108
+        assert_eq!(slider.value_at(size, Point::new(8.0, 10.0)), 0.0);
+        assert_eq!(slider.value_at(size, Point::new(58.0, 10.0)), 50.0);
+        assert_eq!(slider.position(size, 100.0), 108.0);
+
+        let size = Size::new(20.0, 116.0);
+        let slider = test_slider(0.0, 100.0, 0.0, true);
+        // vertical sliders go up
+        assert_eq!(slider.value_at(size, Point::new(10.0, 108.0)), 0.0);
+        assert_eq!(slider.position(size, 100.0), 8.0);
+    }
+
+    #[test]
+    fn reports_a_slider_node_for_each_thumb() {
+        let (scope, _) = raw_scope_and_disposer(create_runtime());
+        let slider = Slider {
+            value: SliderValue::Range(create_rw_signal(scope, (2.0, 6.0))),
+            values: vec![2.0, 6.0],
+            ..test_slider(0.0, 10.0, 2.0, false)
+        };
+        let nodes = slider.accessibility();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[1].role, Role::Slider);
+        assert_eq!(nodes[1].name.as_deref(), Some("end"));
+        assert_eq!(nodes[1].value, Some(6.0));
+        assert_eq!((nodes[1].min, nodes[1].max), (Some(0.0), Some(10.0)));
+        assert_eq!(nodes[1].step, Some(2.0));
+        assert_eq!(nodes[1].orientation, Some(Orientation::Horizontal));
+    }
+}
@@ -0,0 +1,478 @@
+use std::any::Any;
+
+use floem_renderer::Renderer;
+use glazier::{
+    kurbo::{Point, Rect, Size},
+    PointerId,
+};
+use leptos_reactive::{create_effect, create_rw_signal, RwSignal, SignalGet, SignalSet};
+use taffy::{prelude::Node, style::Dimension};
+use vello::peniko::Color;
+
+use crate::{
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::{CursorStyle, Style},
+    view::{ChangeFlags, View},
+};
+
+/// The room between the panes, where the divider is painted
+const DIVIDER_WIDTH: f64 = 1.0;
+/// How far from the divider the pointer can grab it
+const HANDLE_REACH: f64 = 3.0;
+const DIVIDER_COLOR: Color = Color::rgb8(0xd4, 0xd4, 0xd8);
+
+/// How a [split] lays out its panes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitDirection {
+    /// Side by side, with a divider going up and down between them
+    Horizontal,
+    /// One above the other, with a divider going side to side between them
+    Vertical,
+}
+
+/// One of the two panes of a [split].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SplitPane {
+    First,
+    Second,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct PaneLimits {
+    min: f64,
+    max: Option<f64>,
+}
+
+/// Clamps the size of the first pane so that both panes stay inside their limits, if they
+/// can, out of `available` room for both.
+fn clamp_first(first: f64, available: f64, limits: [PaneLimits; 2]) -> f64 {
+    let [first_limits, second_limits] = limits;
+    let low = first_limits
+        .min
+        .max(available - second_limits.max.unwrap_or(f64::INFINITY));
+    let high = first_limits
+        .max
+        .unwrap_or(f64::INFINITY)
+        .min(available - second_limits.min);
+    first.min(high).max(low).max(0.0)
+}
+
+struct SplitDrag {
+    pointer: PointerId,
+    /// Where the pointer grabbed the divider, from its start
+    offset: f64,
+}
+
+pub struct Split<A: View, B: View> {
+    id: Id,
+    direction: SplitDirection,
+    first: A,
+    second: B,
+    ratio: RwSignal<f64>,
+    /// The share of the room the first pane gets, from `ratio`
+    current: f64,
+    limits: [PaneLimits; 2],
+    /// The pane a double click on the divider collapses
+    collapsible: SplitPane,
+    collapsed: bool,
+    divider_color: Color,
+    drag: Option<SplitDrag>,
+}
+
+/// Lays out `first` and `second` side by side or one above the other, with a divider between
+/// them that can be dragged to resize them.
+///
+/// The share of the room the first pane gets is a ratio from 0 to 1, which can be bound to a
+/// signal with [Split::ratio], e.g. to persist it. The panes can have minimum and maximum
+/// sizes, and a double click on the divider collapses one of them, or expands it back.
+///
+/// A split fills its parent, so splits can be nested as panes of other splits.
+pub fn split<A: View, B: View>(
+    direction: SplitDirection,
+    first: impl FnOnce() -> A,
+    second: impl FnOnce() -> B,
+) -> Split<A, B> {
+    let (id, (first, second)) = ViewContext::new_id_with_child(|| (first(), second()));
+    let cx = ViewContext::get_current();
+    let ratio = create_rw_signal(cx.scope, 0.5);
+    watch_ratio(id, ratio);
+    Split {
+        id,
+        direction,
+        first,
+        second,
+        ratio,
+        current: 0.5,
+        limits: [PaneLimits::default(); 2],
+        collapsible: SplitPane::First,
+        collapsed: false,
+        divider_color: DIVIDER_COLOR,
+        drag: None,
+    }
+}
+
+fn watch_ratio(id: Id, ratio: RwSignal<f64>) {
+    let cx = ViewContext::get_current();
+    create_effect(cx.scope, move |_| {
+        id.update_state(ratio.get(), false);
+    });
+}
+
+impl<A: View, B: View> Split<A, B> {
+    /// Binds the share of the room the first pane gets, from 0 to 1, to `ratio`. Dragging the
+    /// divider sets it, and setting it moves the divider.
+    pub fn ratio(mut self, ratio: RwSignal<f64>) -> Self {
+        watch_ratio(self.id, ratio);
+        self.ratio = ratio;
+        self
+    }
+
+    pub fn min_size(mut self, pane: SplitPane, size: f64) -> Self {
+        self.limits[pane as usize].min = size;
+        self
+    }
+
+    pub fn max_size(mut self, pane: SplitPane, size: f64) -> Self {
+        self.limits[pane as usize].max = Some(size);
+        self
+    }
+
+    /// The pane a double click on the divider collapses. Defaults to [SplitPane::First].
+    pub fn collapsible(mut self, pane: SplitPane) -> Self {
+        self.collapsible = pane;
+        self
+    }
+
+    pub fn divider_color(mut self, color: Color) -> Self {
+        self.divider_color = color;
+        self
+    }
+
+    fn is_horizontal(&self) -> bool {
+        self.direction == SplitDirection::Horizontal
+    }
+
+    fn main(&self, size: Size) -> f64 {
+        if self.is_horizontal() {
+            size.width
+        } else {
+            size.height
+        }
+    }
+
+    fn main_pos(&self, pos: Point) -> f64 {
+        if self.is_horizontal() {
+            pos.x
+        } else {
+            pos.y
+        }
+    }
+
+    fn is_collapsed(&self, pane: SplitPane) -> bool {
+        self.collapsed && self.collapsible == pane
+    }
+
+    fn pane_rect(cx: &EventCx, id: Id) -> Rect {
+        cx.get_layout(id)
+            .map(|layout| {
+                Rect::new(
+                    layout.location.x as f64,
+                    layout.location.y as f64,
+                    (layout.location.x + layout.size.width) as f64,
+                    (layout.location.y + layout.size.height) as f64,
+                )
+            })
+            .unwrap_or_default()
+    }
+
+    /// The divider, right after the first pane.
+    fn divider_rect(&self, size: Size, first: Rect) -> Rect {
+        if self.is_horizontal() {
+            Rect::new(first.x1, 0.0, first.x1 + DIVIDER_WIDTH, size.height)
+        } else {
+            Rect::new(0.0, first.y1, size.width, first.y1 + DIVIDER_WIDTH)
+        }
+    }
+
+    fn handle_rect(&self, divider: Rect) -> Rect {
+        if self.is_horizontal() {
+            divider.inflate(HANDLE_REACH, 0.0)
+        } else {
+            divider.inflate(0.0, HANDLE_REACH)
+        }
+    }
+
+    fn cursor(&self) -> CursorStyle {
+        if self.is_horizontal() {
+            CursorStyle::ColResize
+        } else {
+            CursorStyle::RowResize
+        }
+    }
+
+    /// The flex sizing of `pane`, on top of its own style.
+    fn pane_style(&self, mut style: taffy::style::Style, pane: SplitPane) -> taffy::style::Style {
+        let limits = self.limits[pane as usize];
+        let (basis, min, max) = if self.is_collapsed(pane) {
+            (Dimension::Points(0.0), 0.0, Some(0.0))
+        } else if pane == SplitPane::First {
+            (
+                Dimension::Percent(self.current as f32),
+                limits.min,
+                limits.max,
+            )
+        } else {
+            (Dimension::Points(0.0), limits.min, limits.max)
+        };
+        style.flex_basis = basis;
+        // the second pane takes the rest of the room, unless it's collapsed
+        let fills = pane == SplitPane::Second || self.is_collapsed(SplitPane::Second);
+        style.flex_grow = if fills && !self.is_collapsed(pane) {
+            1.0
+        } else {
+            0.0
+        };
+        style.flex_shrink = if self.is_collapsed(pane) { 0.0 } else { 1.0 };
+        let min = Dimension::Points(min as f32);
+        let max = max.map_or(Dimension::Auto, |max| Dimension::Points(max as f32));
+        if self.is_horizontal() {
+            style.min_size.width = min;
+            style.max_size.width = max;
+        } else {
+            style.min_size.height = min;
+            style.max_size.height = max;
+        }
+        style
+    }
+
+    fn layout_pane(&self, cx: &mut LayoutCx, node: Node, pane: SplitPane) {
+        let taffy = &mut cx.app_state_mut().taffy;
+        if let Ok(style) = taffy.style(node) {
+            let style = self.pane_style(style.clone(), pane);
+            let _ = taffy.set_style(node, style);
+        }
+    }
+}
+
+impl<A: View, B: View> View for Split<A, B> {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn view_style(&self) -> Option<Style> {
+        let style = Style::BASE.size_pct(100.0, 100.0);
+        Some(if self.is_horizontal() {
+            style.flex_row().column_gap_px(DIVIDER_WIDTH as f32)
+        } else {
+            style.flex_col().row_gap_px(DIVIDER_WIDTH as f32)
+        })
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.first.id() == id {
+            Some(&self.first)
+        } else if self.second.id() == id {
+            Some(&self.second)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.first.id() == id {
+            Some(&mut self.first)
+        } else if self.second.id() == id {
+            Some(&mut self.second)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.first, &self.second]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.first, &mut self.second]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Split".into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn Any>) -> ChangeFlags {
+        if let Ok(ratio) = state.downcast::<f64>() {
+            self.current = ratio.clamp(0.0, 1.0);
+            cx.request_layout(self.id);
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, true, |cx| {
+            let first = self.first.layout_main(cx);
+            let second = self.second.layout_main(cx);
+            self.layout_pane(cx, first, SplitPane::First);
+            self.layout_pane(cx, second, SplitPane::Second);
+            vec![first, second]
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let first = self.first.compute_layout_main(cx);
+        let second = self.second.compute_layout_main(cx);
+        Some(first.union(second))
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        let size = cx
+            .get_layout(self.id)
+            .map(|layout| Size::new(layout.size.width as f64, layout.size.height as f64))
+            .unwrap_or_default();
+        let first = Self::pane_rect(cx, self.first.id());
+        let divider = self.divider_rect(size, first);
+        let on_handle = event
+            .point()
+            .map(|pos| self.handle_rect(divider).contains(pos))
+            .unwrap_or(false);
+
+        match &event {
+            Event::PointerDown(pointer_event) if pointer_event.button.is_left() && on_handle => {
+                if pointer_event.count == 2 {
+                    self.collapsed = !self.collapsed;
+                    self.drag = None;
+                } else {
+                    let start = self.main_pos(divider.origin());
+                    self.drag = Some(SplitDrag {
+                        pointer: pointer_event.pointer_id,
+                        offset: self.main_pos(pointer_event.pos) - start,
+                    });
+                    cx.capture_pointer(self.id, pointer_event.pointer_id);
+                }
+                cx.app_state.request_layout(self.id);
+                return true;
+            }
+            Event::PointerMove(pointer_event) => {
+                if let Some(drag) = self
+                    .drag
+                    .as_ref()
+                    .filter(|drag| drag.pointer == pointer_event.pointer_id)
+                {
+                    let main = self.main(size);
+                    if main > 0.0 {
+                        let first = self.main_pos(pointer_event.pos) - drag.offset;
+                        let first = clamp_first(first, main - DIVIDER_WIDTH, self.limits);
+                        // dragging a collapsed pane opens it again
+                        self.collapsed = false;
+                        self.ratio.set(first / main);
+                    }
+                    cx.app_state.cursor = Some(self.cursor());
+                    return true;
+                }
+            }
+            Event::PointerUp(pointer_event) | Event::PointerCancel(pointer_event)
+                if self.drag.as_ref().map(|drag| drag.pointer)
+                    == Some(pointer_event.pointer_id) =>
+            {
+                self.drag = None;
+                return true;
+            }
+            _ => {}
+        }
+
+        let mut handled = false;
+        for (pane, child) in [
+            (SplitPane::First, &mut self.first as &mut dyn View),
+            (SplitPane::Second, &mut self.second as &mut dyn View),
+        ] {
+            let collapsed = self.collapsed && self.collapsible == pane;
+            if !collapsed && cx.should_send(child.id(), &event) {
+                handled = child.event_main(cx, id_path, event.clone());
+                if handled {
+                    break;
+                }
+            }
+        }
+        if on_handle && matches!(event, Event::PointerMove(_)) {
+            cx.app_state.cursor = Some(self.cursor());
+        }
+        handled
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        let Some(layout) = cx.get_layout(self.id) else {
+            return;
+        };
+        let size = Size::new(layout.size.width as f64, layout.size.height as f64);
+        let mut first_rect = Rect::ZERO;
+        for (pane, child) in [
+            (SplitPane::First, &mut self.first as &mut dyn View),
+            (SplitPane::Second, &mut self.second as &mut dyn View),
+        ] {
+            let Some(layout) = cx.get_layout(child.id()) else {
+                continue;
+            };
+            let rect = Rect::new(
+                layout.location.x as f64,
+                layout.location.y as f64,
+                (layout.location.x + layout.size.width) as f64,
+                (layout.location.y + layout.size.height) as f64,
+            );
+            if pane == SplitPane::First {
+                first_rect = rect;
+            }
+            if self.collapsed && self.collapsible == pane {
+                continue;
+            }
+            // a pane smaller than its content doesn't paint over the other one
+            cx.save();
+            cx.clip(&rect);
+            child.paint_main(cx);
+            cx.restore();
+        }
+        let divider = self.divider_rect(size, first_rect);
+        cx.fill(&divider, self.divider_color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clamp_first, PaneLimits};
+
+    #[test]
+    fn first_pane_stays_inside_both_limits() {
+        let limits = [
+            PaneLimits {
+                min: 100.0,
+                max: Some(400.0),
+            },
+            PaneLimits {
+                min: 200.0,
+                max: None,
+            },
+        ];
+        assert_eq!(clamp_first(50.0, 1000.0, limits), 100.0);
+        assert_eq!(clamp_first(300.0, 1000.0, limits), 300.0);
+        assert_eq!(clamp_first(700.0, 1000.0, limits), 400.0);
+        // the second pane's minimum wins over the first one's maximum
+        assert_eq!(clamp_first(350.0, 500.0, limits), 300.0);
+    }
+
+    #[test]
+    fn second_pane_maximum_pushes_the_divider() {
+        let limits = [
+            PaneLimits::default(),
+            PaneLimits {
+                min: 0.0,
+                max: Some(300.0),
+            },
+        ];
+        assert_eq!(clamp_first(100.0, 1000.0, limits), 700.0);
+        assert_eq!(clamp_first(800.0, 1000.0, limits), 800.0);
+    }
+}
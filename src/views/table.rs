@@ -0,0 +1,539 @@
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    collections::HashSet,
+    hash::Hash,
+    ops::{Range, RangeInclusive},
+    rc::Rc,
+};
+
+use glazier::{keyboard_types::Key, kurbo::Rect, Modifiers};
+use leptos_reactive::{
+    create_effect, create_rw_signal, RwSignal, SignalGet, SignalSet, SignalUpdate, SignalWith,
+};
+use vello::peniko::Color;
+
+use crate::{
+    app_handle::ViewContext,
+    event::{Event, EventListener},
+    gesture::{GestureConfig, GestureEvent, GesturePhase},
+    style::{CursorStyle, Style},
+    view::View,
+    views::{
+        container_box, empty, label, scroll, stack, virtual_list, Decorators, VirtualListDirection,
+        VirtualListItemSize, VirtualListVector,
+    },
+};
+
+const ROW_HEIGHT: f64 = 28.0;
+const HEADER_HEIGHT: f64 = 32.0;
+const DEFAULT_WIDTH: f64 = 120.0;
+const DEFAULT_MIN_WIDTH: f64 = 40.0;
+/// The width of the grip at the right edge of a header cell resizing its column
+const RESIZE_HANDLE_WIDTH: f32 = 6.0;
+const HEADER_COLOR: Color = Color::rgb8(0xf4, 0xf4, 0xf5);
+const SELECTED_COLOR: Color = Color::rgb8(0xdb, 0xea, 0xfe);
+const CURSOR_COLOR: Color = Color::rgb8(0x3b, 0x82, 0xf6);
+const GRID_COLOR: Color = Color::rgb8(0xe4, 0xe4, 0xe7);
+
+/// A column of a [table].
+pub struct TableColumn<T> {
+    header: Rc<dyn Fn() -> Box<dyn View>>,
+    cell: Rc<dyn Fn(&T) -> Box<dyn View>>,
+    width: f64,
+    min_width: f64,
+    compare: Option<Rc<dyn Fn(&T, &T) -> Ordering>>,
+}
+
+impl<T> TableColumn<T> {
+    /// A column with the view made by `header` in the header row, and the view made by `cell`
+    /// for each row.
+    pub fn new<H: View + 'static, C: View + 'static>(
+        header: impl Fn() -> H + 'static,
+        cell: impl Fn(&T) -> C + 'static,
+    ) -> Self {
+        Self {
+            header: Rc::new(move || Box::new(header())),
+            cell: Rc::new(move |row| Box::new(cell(row))),
+            width: DEFAULT_WIDTH,
+            min_width: DEFAULT_MIN_WIDTH,
+            compare: None,
+        }
+    }
+
+    /// The width the column starts with.
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// How narrow the column can be resized.
+    pub fn min_width(mut self, min_width: f64) -> Self {
+        self.min_width = min_width;
+        self
+    }
+
+    /// Lets the rows be sorted by this column, in the order of `compare`, by clicking its
+    /// header.
+    pub fn sortable(mut self, compare: impl Fn(&T, &T) -> Ordering + 'static) -> Self {
+        self.compare = Some(Rc::new(compare));
+        self
+    }
+}
+
+/// How the rows of a [table] can be selected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableSelection {
+    None,
+    Single,
+    /// Shift selects the rows from the last one selected, and Ctrl adds or removes rows
+    Multiple,
+}
+
+/// The rows of a table with their index, in the order they're shown.
+struct TableRows<T>(im::Vector<T>);
+
+impl<T: Clone> VirtualListVector<(usize, T)> for TableRows<T> {
+    type ItemIterator = std::iter::Zip<Range<usize>, im::vector::ConsumingIter<T>>;
+
+    fn total_len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn slice(&mut self, range: Range<usize>) -> Self::ItemIterator {
+        range.clone().zip(self.0.slice(range))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum SelectionChange {
+    None,
+    /// Selects the rows in the range, and only them
+    Set(RangeInclusive<usize>),
+    Toggle(usize),
+}
+
+/// What selecting the row at `index` does, from the row at `anchor` where the last selection
+/// started.
+fn selection_change(
+    mode: TableSelection,
+    shift: bool,
+    ctrl: bool,
+    anchor: Option<usize>,
+    index: usize,
+) -> SelectionChange {
+    match (mode, anchor) {
+        (TableSelection::None, _) => SelectionChange::None,
+        (TableSelection::Multiple, Some(anchor)) if shift => {
+            SelectionChange::Set(anchor.min(index)..=anchor.max(index))
+        }
+        (TableSelection::Multiple, _) if ctrl => SelectionChange::Toggle(index),
+        _ => SelectionChange::Set(index..=index),
+    }
+}
+
+/// Where `column` starts and ends in the row, with the columns in `order`.
+fn column_span(order: &[usize], widths: &[f64], column: usize) -> (f64, f64) {
+    let mut start = 0.0;
+    for &other in order {
+        let width = widths.get(other).copied().unwrap_or_default();
+        if other == column {
+            return (start, start + width);
+        }
+        start += width;
+    }
+    (start, start)
+}
+
+fn modifiers(event: &Event) -> Modifiers {
+    match event {
+        Event::PointerDown(pointer_event) | Event::PointerUp(pointer_event) => {
+            pointer_event.modifiers
+        }
+        Event::KeyDown(key_event) | Event::KeyUp(key_event) => key_event.mods,
+        _ => Modifiers::default(),
+    }
+}
+
+/// A table of `rows` with `columns`, filling its parent and scrolling in both directions. Only
+/// the rows and columns in view are made, so there can be many of both.
+///
+/// The header row stays at the top. Clicking the header of a sortable column sorts the rows by
+/// it, and clicking it again reverses the order. Columns are resized by dragging the right edge
+/// of their header, and reordered by dragging their header.
+///
+/// Rows are selected by clicking them, which puts the keys made by `key_fn` in `selection`,
+/// as allowed by `mode`. While the table is focused, the arrow keys, Home, End, PageUp and
+/// PageDown move between the cells, Ctrl with Home or End goes to the first or last row, and
+/// Space or Enter select the row of the current cell. Shift with the keys moving between rows
+/// selects the rows on the way.
+pub fn table<T, K>(
+    columns: Vec<TableColumn<T>>,
+    rows: impl Fn() -> im::Vector<T> + 'static,
+    key_fn: impl Fn(&T) -> K + 'static,
+    selection: RwSignal<HashSet<K>>,
+    mode: TableSelection,
+) -> impl View
+where
+    T: Clone + 'static,
+    K: Eq + Hash + 'static,
+{
+    let cx = ViewContext::get_current();
+    let columns = Rc::new(columns);
+    let key_fn = Rc::new(key_fn);
+    let order = create_rw_signal(cx.scope, (0..columns.len()).collect::<Vec<_>>());
+    let widths = create_rw_signal(
+        cx.scope,
+        columns
+            .iter()
+            .map(|column| column.width)
+            .collect::<Vec<_>>(),
+    );
+    // the column the rows are sorted by, and whether they're in ascending order
+    let sort = create_rw_signal(cx.scope, None::<(usize, bool)>);
+    let sorted = create_rw_signal(cx.scope, im::Vector::new());
+    // the row and the column of the current cell
+    let cursor = create_rw_signal(cx.scope, None::<(usize, usize)>);
+    let anchor = create_rw_signal(cx.scope, None::<usize>);
+    let viewport = create_rw_signal(cx.scope, Rect::ZERO);
+
+    {
+        let columns = columns.clone();
+        create_effect(cx.scope, move |_| {
+            let mut rows = rows();
+            let compare = sort.get().and_then(|(column, ascending)| {
+                Some((columns.get(column)?.compare.clone()?, ascending))
+            });
+            if let Some((compare, ascending)) = compare {
+                rows.sort_by(|a, b| {
+                    let ordering = compare(a, b);
+                    if ascending {
+                        ordering
+                    } else {
+                        ordering.reverse()
+                    }
+                });
+            }
+            sorted.set(rows);
+        });
+    }
+
+    let shown_columns = move || {
+        order.with(|order| {
+            widths.with(|widths| {
+                order
+                    .iter()
+                    .map(|&column| (column, widths[column]))
+                    .collect::<im::Vector<_>>()
+            })
+        })
+    };
+    let column_width =
+        move || VirtualListItemSize::Fn(Box::new(|(_, width): &(usize, f64)| *width));
+
+    let select = {
+        let key_fn = key_fn.clone();
+        move |index: usize, shift: bool, ctrl: bool| {
+            match selection_change(mode, shift, ctrl, anchor.get(), index) {
+                SelectionChange::None => return,
+                SelectionChange::Set(range) => {
+                    let keys = sorted.with(|rows| {
+                        range
+                            .filter_map(|i| rows.get(i))
+                            .map(|row| key_fn(row))
+                            .collect::<HashSet<_>>()
+                    });
+                    selection.set(keys);
+                }
+                SelectionChange::Toggle(index) => {
+                    if let Some(key) = sorted.with(|rows| rows.get(index).map(|row| key_fn(row))) {
+                        selection.update(|selection| {
+                            if !selection.remove(&key) {
+                                selection.insert(key);
+                            }
+                        });
+                    }
+                }
+            }
+            if !shift {
+                anchor.set(Some(index));
+            }
+        }
+    };
+
+    let header = {
+        let columns = columns.clone();
+        virtual_list(
+            VirtualListDirection::Horizontal,
+            column_width(),
+            shown_columns,
+            |(column, _)| *column,
+            move |(column, _)| {
+                let header = columns[column].header.clone();
+                let sortable = columns[column].compare.is_some();
+                let min_width = columns[column].min_width;
+                let start_width = Rc::new(Cell::new(0.0));
+                stack(|| {
+                    (
+                        container_box(|| header()),
+                        label(move || {
+                            match sort.get() {
+                                Some((sorted, true)) if sorted == column => "▲",
+                                Some((sorted, false)) if sorted == column => "▼",
+                                _ => "",
+                            }
+                            .to_string()
+                        }),
+                        empty()
+                            .style(|| {
+                                Style::BASE
+                                    .absolute()
+                                    .inset_right_px(0.0)
+                                    .width_px(RESIZE_HANDLE_WIDTH)
+                                    .height_pct(100.0)
+                                    .cursor(CursorStyle::ColResize)
+                            })
+                            .gesture_config(GestureConfig {
+                                slop: 1.0,
+                                ..Default::default()
+                            })
+                            .on_pan(move |gesture| {
+                                let GestureEvent::Pan {
+                                    phase, translation, ..
+                                } = gesture
+                                else {
+                                    return;
+                                };
+                                if *phase == GesturePhase::Began {
+                                    start_width.set(widths.with(|widths| widths[column]));
+                                }
+                                if matches!(phase, GesturePhase::Began | GesturePhase::Changed) {
+                                    let width = (start_width.get() + translation.x).max(min_width);
+                                    widths.update(|widths| widths[column] = width);
+                                }
+                            })
+                            // the grip doesn't sort or start moving the column
+                            .on_event(EventListener::PointerDown, |_| true),
+                    )
+                })
+                .style(move || {
+                    Style::BASE
+                        .width_px(widths.with(|widths| widths[column]) as f32)
+                        .height_pct(100.0)
+                        .padding_horiz_px(6.0)
+                        .gap_px(4.0)
+                        .items_center()
+                        .border_right(1.0)
+                        .border_color(GRID_COLOR)
+                        .font_bold()
+                })
+                .on_click(move |_| {
+                    if sortable {
+                        sort.update(|sort| {
+                            *sort = match *sort {
+                                Some((sorted, ascending)) if sorted == column => {
+                                    Some((column, !ascending))
+                                }
+                                _ => Some((column, true)),
+                            }
+                        });
+                    }
+                    true
+                })
+            },
+        )
+        .reorderable(move |from, to| {
+            order.update(|order| {
+                let column = order.remove(from);
+                order.insert(to, column);
+            });
+        })
+        .style(move || {
+            Style::BASE
+                .absolute()
+                .inset_top_px(viewport.get().y0 as f32)
+                .height_px(HEADER_HEIGHT as f32)
+                .min_width_pct(100.0)
+                .border_bottom(1.0)
+                .border_color(GRID_COLOR)
+                .background(HEADER_COLOR)
+                .z_index(1)
+        })
+    };
+
+    let body = {
+        let key_fn = key_fn.clone();
+        let select = select.clone();
+        virtual_list(
+            VirtualListDirection::Vertical,
+            VirtualListItemSize::Fixed(Box::new(|| ROW_HEIGHT)),
+            move || TableRows(sorted.get()),
+            {
+                let key_fn = key_fn.clone();
+                move |(index, row)| (*index, key_fn(row))
+            },
+            move |(index, row)| {
+                let columns = columns.clone();
+                let select = select.clone();
+                let key = key_fn(&row);
+                virtual_list(
+                    VirtualListDirection::Horizontal,
+                    column_width(),
+                    shown_columns,
+                    |(column, _)| *column,
+                    move |(column, _)| {
+                        container_box(|| (columns[column].cell)(&row))
+                            .style(move || {
+                                let is_cursor = cursor.get() == Some((index, column));
+                                Style::BASE
+                                    .width_px(widths.with(|widths| widths[column]) as f32)
+                                    .height_pct(100.0)
+                                    .padding_horiz_px(6.0)
+                                    .items_center()
+                                    .apply_if(is_cursor, |s| {
+                                        s.border(1.0).border_color(CURSOR_COLOR)
+                                    })
+                            })
+                            .on_event(EventListener::PointerDown, move |_| {
+                                cursor.set(Some((index, column)));
+                                false
+                            })
+                    },
+                )
+                .style(move || {
+                    Style::BASE
+                        .height_px(ROW_HEIGHT as f32)
+                        .border_bottom(1.0)
+                        .border_color(GRID_COLOR)
+                        .apply_if(selection.with(|selection| selection.contains(&key)), |s| {
+                            s.background(SELECTED_COLOR)
+                        })
+                })
+                .on_click(move |event| {
+                    let mods = modifiers(event);
+                    select(index, mods.shift(), mods.ctrl() || mods.meta());
+                    true
+                })
+            },
+        )
+        .style(|| {
+            Style::BASE
+                .flex_col()
+                .min_width_pct(100.0)
+                .margin_top_px(HEADER_HEIGHT as f32)
+        })
+    };
+
+    let on_key = move |event: &Event| -> bool {
+        let Event::KeyDown(key_event) = event else {
+            return false;
+        };
+        let len = sorted.with(|rows| rows.len());
+        let order = order.get();
+        if len == 0 || order.is_empty() {
+            return false;
+        }
+        let (row, column) = cursor.get().unwrap_or((0, order[0]));
+        let position = order.iter().position(|&c| c == column).unwrap_or(0);
+        let last_position = order.len() - 1;
+        let page = ((viewport.get().height() - HEADER_HEIGHT) / ROW_HEIGHT).max(1.0) as usize;
+        let mods = key_event.mods;
+        let ctrl = mods.ctrl() || mods.meta();
+        let (row, position) = match &key_event.key {
+            Key::ArrowUp => (row.saturating_sub(1), position),
+            Key::ArrowDown => ((row + 1).min(len - 1), position),
+            Key::ArrowLeft => (row, position.saturating_sub(1)),
+            Key::ArrowRight => (row, (position + 1).min(last_position)),
+            Key::PageUp => (row.saturating_sub(page), position),
+            Key::PageDown => ((row + page).min(len - 1), position),
+            Key::Home if ctrl => (0, position),
+            Key::Home => (row, 0),
+            Key::End if ctrl => (len - 1, position),
+            Key::End => (row, last_position),
+            Key::Enter => {
+                select(row, mods.shift(), ctrl);
+                cursor.set(Some((row, column)));
+                return true;
+            }
+            Key::Character(text) if text == " " => {
+                select(row, mods.shift(), ctrl);
+                cursor.set(Some((row, column)));
+                return true;
+            }
+            _ => return false,
+        };
+        cursor.set(Some((row, order[position])));
+        let moves_row = matches!(
+            key_event.key,
+            Key::ArrowUp | Key::ArrowDown | Key::PageUp | Key::PageDown | Key::Home | Key::End
+        );
+        if mods.shift() && moves_row && mode == TableSelection::Multiple {
+            select(row, true, false);
+        }
+        true
+    };
+
+    scroll(move || stack(move || (body, header)).style(|| Style::BASE.flex_col()))
+        .on_scroll(move |rect| viewport.set(rect))
+        .on_ensure_visible(move || {
+            let Some((row, column)) = cursor.get() else {
+                return Rect::ZERO;
+            };
+            let (x0, x1) =
+                order.with(|order| widths.with(|widths| column_span(order, widths, column)));
+            // the header row covers the top of the view
+            let y0 = row as f64 * ROW_HEIGHT;
+            Rect::new(x0, y0, x1, y0 + HEADER_HEIGHT + ROW_HEIGHT)
+        })
+        .style(|| Style::BASE.size_pct(100.0, 100.0))
+        .keyboard_navigatable()
+        .on_event(EventListener::KeyDown, on_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::views::VirtualListVector;
+
+    use super::{column_span, selection_change, SelectionChange, TableRows, TableSelection};
+
+    #[test]
+    fn multiple_selection_follows_modifiers() {
+        let mode = TableSelection::Multiple;
+        assert_eq!(
+            selection_change(mode, false, false, Some(2), 5),
+            SelectionChange::Set(5..=5)
+        );
+        assert_eq!(
+            selection_change(mode, true, false, Some(7), 3),
+            SelectionChange::Set(3..=7)
+        );
+        assert_eq!(
+            selection_change(mode, false, true, Some(7), 3),
+            SelectionChange::Toggle(3)
+        );
+        assert_eq!(
+            selection_change(TableSelection::Single, true, true, Some(7), 3),
+            SelectionChange::Set(3..=3)
+        );
+        assert_eq!(
+            selection_change(TableSelection::None, false, false, None, 3),
+            SelectionChange::None
+        );
+    }
+
+    #[test]
+    fn column_spans_follow_the_order() {
+        let widths = [100.0, 50.0, 80.0];
+        assert_eq!(column_span(&[0, 1, 2], &widths, 1), (100.0, 150.0));
+        assert_eq!(column_span(&[2, 1, 0], &widths, 1), (80.0, 130.0));
+        assert_eq!(column_span(&[2, 1, 0], &widths, 0), (130.0, 230.0));
+    }
+
+    #[test]
+    fn rows_are_sliced_with_their_index() {
+        let mut rows = TableRows(im::vector!['a', 'b', 'c', 'd']);
+        assert_eq!(rows.total_len(), 4);
+        let sliced = rows.slice(1..3).collect::<Vec<_>>();
+        assert_eq!(sliced, vec![(1, 'b'), (2, 'c')]);
+    }
+}
@@ -24,7 +24,7 @@ use std::{
 
 use crate::{
     cosmic_text::{Attrs, AttrsList, FamilyOwned, TextLayout},
-    style::ComputedStyle,
+    style::{ComputedStyle, DEFAULT_FONT_SIZE},
 };
 use glazier::{
     keyboard_types::Key,
@@ -140,7 +140,6 @@ enum ClipDirection {
     Backward,
 }
 
-const DEFAULT_FONT_SIZE: f32 = 14.0;
 const CURSOR_BLINK_INTERVAL_MS: u64 = 500;
 
 impl TextInput {
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use glazier::kurbo::Rect;
+use leptos_reactive::{create_effect, RwSignal, SignalGet, SignalUpdate};
+use vello::peniko::Color;
+
+use crate::{
+    animate::animation,
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    style::Style,
+    view::{ChangeFlags, View},
+    views::{empty, Decorators, Empty},
+};
+
+const TRACK_WIDTH: f32 = 36.0;
+const TRACK_HEIGHT: f32 = 20.0;
+const THUMB_SIZE: f32 = 16.0;
+/// The space between the thumb and the edges of the track
+const THUMB_INSET: f32 = 2.0;
+const ON_COLOR: Color = Color::rgb8(0x3b, 0x82, 0xf6);
+const OFF_COLOR: Color = Color::rgb8(0xd4, 0xd4, 0xd8);
+const SLIDE_DURATION: Duration = Duration::from_millis(150);
+
+pub struct Toggle {
+    id: Id,
+    thumb: Empty,
+}
+
+/// A switch turning `on` on and off when it's clicked, or when Enter or Space is pressed while
+/// it's focused. Its thumb slides to the side of the new state.
+///
+/// The track is styled with [Decorators::style], which can depend on `on`, and the thumb with
+/// [Toggle::thumb_style].
+pub fn toggle(on: RwSignal<bool>) -> Toggle {
+    let (id, thumb) = ViewContext::new_id_with_child(|| {
+        empty()
+            .base_style(|| {
+                Style::BASE
+                    .absolute()
+                    .inset_top_px(THUMB_INSET)
+                    .size_px(THUMB_SIZE, THUMB_SIZE)
+                    .border_radius(THUMB_SIZE / 2.0)
+                    .background(Color::WHITE)
+            })
+            .animation(
+                animation()
+                    .inset_left(move || {
+                        if on.get() {
+                            (TRACK_WIDTH - THUMB_SIZE - THUMB_INSET) as f64
+                        } else {
+                            THUMB_INSET as f64
+                        }
+                    })
+                    .duration(SLIDE_DURATION),
+            )
+    });
+    Toggle { id, thumb }
+        .base_style(move || {
+            Style::BASE
+                .size_px(TRACK_WIDTH, TRACK_HEIGHT)
+                .border_radius(TRACK_HEIGHT / 2.0)
+                .background(if on.get() { ON_COLOR } else { OFF_COLOR })
+        })
+        .keyboard_navigatable()
+        .on_click(move |_| {
+            on.update(|on| *on = !*on);
+            true
+        })
+}
+
+impl Toggle {
+    pub fn thumb_style(self, style: impl Fn() -> Style + 'static) -> Self {
+        let cx = ViewContext::get_current();
+        let thumb = self.thumb.id();
+        create_effect(cx.scope, move |_| {
+            thumb.update_style(style());
+        });
+        self
+    }
+}
+
+impl View for Toggle {
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        if self.thumb.id() == id {
+            Some(&self.thumb)
+        } else {
+            None
+        }
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        if self.thumb.id() == id {
+            Some(&mut self.thumb)
+        } else {
+            None
+        }
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        vec![&self.thumb]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        vec![&mut self.thumb]
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "Toggle".into()
+    }
+
+    fn update(&mut self, _cx: &mut UpdateCx, _state: Box<dyn std::any::Any>) -> ChangeFlags {
+        ChangeFlags::empty()
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> taffy::prelude::Node {
+        cx.layout_node(self.id, true, |cx| vec![self.thumb.layout_main(cx)])
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        Some(self.thumb.compute_layout_main(cx))
+    }
+
+    fn event(&mut self, _cx: &mut EventCx, _id_path: Option<&[Id]>, _event: Event) -> bool {
+        // the thumb is part of the toggle, so the toggle gets the events on it
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        self.thumb.paint_main(cx);
+    }
+}
@@ -0,0 +1,40 @@
+use std::time::Duration;
+
+use glazier::kurbo::{Point, Rect};
+use leptos_reactive::Scope;
+
+use crate::{id::Id, view::View};
+
+/// How long the pointer rests on a view before its tooltip shows, unless the view has a
+/// [tooltip_delay](crate::views::Decorators::tooltip_delay).
+pub const DEFAULT_TOOLTIP_DELAY: Duration = Duration::from_millis(600);
+
+/// A tooltip under the pointer starts this far below it, to stay clear of the cursor.
+const CURSOR_HEIGHT: f64 = 20.0;
+
+/// The tooltip of a view, created in the overlay layer each time it shows.
+pub(crate) struct Tooltip {
+    pub(crate) scope: Scope,
+    pub(crate) view: Box<dyn Fn() -> Box<dyn View>>,
+}
+
+/// The tooltip of the window. There's at most one, for the view the pointer rests on or the
+/// view focused with the keyboard.
+#[derive(Default)]
+pub(crate) struct TooltipState {
+    /// The view whose tooltip is waiting for its delay or is shown
+    pub(crate) owner: Option<Id>,
+    /// The tooltip belongs to the focused view rather than to the hovered one
+    pub(crate) from_focus: bool,
+    /// Where the tooltip goes, in window coordinates
+    pub(crate) anchor: Rect,
+    /// Bumped whenever the tooltip hides, so that the delays started before do nothing
+    pub(crate) generation: u64,
+    /// The overlay showing the tooltip
+    pub(crate) shown: Option<Id>,
+}
+
+/// The anchor for a tooltip at the pointer position `pos`.
+pub(crate) fn cursor_anchor(pos: Point) -> Rect {
+    Rect::new(pos.x, pos.y, pos.x, pos.y + CURSOR_HEIGHT)
+}
@@ -0,0 +1,504 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+};
+
+use glazier::{keyboard_types::Key, kurbo::Rect};
+use leptos_reactive::{
+    create_effect, create_rw_signal, ReadSignal, RwSignal, Scope, SignalGet, SignalSet,
+    SignalUpdate, SignalWith,
+};
+use vello::peniko::Color;
+
+use crate::{
+    app_handle::ViewContext,
+    event::{Event, EventListener},
+    ext_event::create_ext_action,
+    style::Style,
+    view::View,
+    views::{
+        empty, ensure_visible, label, list, scroll, stack, virtual_list, Decorators,
+        VirtualListDirection, VirtualListItemSize,
+    },
+};
+
+const ROW_HEIGHT: f64 = 24.0;
+/// The indentation of each level of the tree
+const INDENT: f32 = 16.0;
+const SELECTED_COLOR: Color = Color::rgb8(0xdb, 0xea, 0xfe);
+const GUIDE_COLOR: Color = Color::rgb8(0xe4, 0xe4, 0xe7);
+
+/// An item of a [tree_view].
+pub trait TreeItem: Clone + Send + 'static {
+    type Key: Clone + Eq + Hash + 'static;
+
+    fn key(&self) -> Self::Key;
+
+    /// Whether the item can be expanded. Its children are only loaded once it is.
+    fn has_children(&self) -> bool;
+
+    /// Loads the children of the item, and gives them to `children`, which can be done later
+    /// and from another thread.
+    fn load_children(&self, children: TreeChildren<Self>);
+}
+
+/// Takes the children loaded by [TreeItem::load_children] back to the tree.
+pub struct TreeChildren<T>(Box<dyn FnOnce(Vec<T>) + Send>);
+
+impl<T> TreeChildren<T> {
+    pub fn send(self, children: Vec<T>) {
+        (self.0)(children)
+    }
+}
+
+/// The selection and the expanded items of a [tree_view], which the application can change.
+pub struct TreeState<K: 'static> {
+    pub(crate) selected: RwSignal<Option<K>>,
+    pub(crate) expanded: RwSignal<HashSet<K>>,
+    /// The item to scroll to, once it's shown
+    pub(crate) scroll_to: RwSignal<Option<K>>,
+}
+
+impl<K: 'static> Clone for TreeState<K> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: 'static> Copy for TreeState<K> {}
+
+/// Creates the state to give to a [tree_view], with nothing selected or expanded.
+pub fn create_tree_state<K: Clone + Eq + Hash + 'static>(cx: Scope) -> TreeState<K> {
+    TreeState {
+        selected: create_rw_signal(cx, None),
+        expanded: create_rw_signal(cx, HashSet::new()),
+        scroll_to: create_rw_signal(cx, None),
+    }
+}
+
+impl<K: Clone + Eq + Hash + 'static> TreeState<K> {
+    pub fn selected(&self) -> ReadSignal<Option<K>> {
+        self.selected.read_only()
+    }
+
+    pub fn select(&self, key: Option<K>) {
+        self.selected.set(key);
+    }
+
+    pub fn is_expanded(&self, key: &K) -> bool {
+        self.expanded.with(|expanded| expanded.contains(key))
+    }
+
+    pub fn expand(&self, key: K) {
+        self.expanded.update(|expanded| {
+            expanded.insert(key);
+        });
+    }
+
+    pub fn collapse(&self, key: &K) {
+        self.expanded.update(|expanded| {
+            expanded.remove(key);
+        });
+    }
+
+    /// Expands the items along `path`, the keys from a root to an item, loading their children
+    /// as needed, then selects the item and scrolls to it.
+    pub fn reveal(&self, path: Vec<K>) {
+        let Some((key, ancestors)) = path.split_last() else {
+            return;
+        };
+        self.expanded
+            .update(|expanded| expanded.extend(ancestors.iter().cloned()));
+        self.selected.set(Some(key.clone()));
+        self.scroll_to.set(Some(key.clone()));
+    }
+}
+
+enum Children<T> {
+    Loading,
+    Loaded(Vec<T>),
+}
+
+/// A visible row of a [tree_view].
+#[derive(Clone)]
+struct TreeRow<T: TreeItem> {
+    item: T,
+    key: T::Key,
+    parent: Option<T::Key>,
+    depth: usize,
+    expandable: bool,
+    expanded: bool,
+    loading: bool,
+}
+
+/// Adds the rows of `items` and of their expanded descendants to `rows`, depth first. The
+/// expanded items whose children aren't loaded yet are added to `to_load`.
+fn flatten<T: TreeItem>(
+    items: Vec<T>,
+    parent: Option<&T::Key>,
+    depth: usize,
+    expanded: &HashSet<T::Key>,
+    nodes: &HashMap<T::Key, Children<T>>,
+    rows: &mut im::Vector<TreeRow<T>>,
+    to_load: &mut Vec<T>,
+) {
+    for item in items {
+        let key = item.key();
+        let expandable = item.has_children();
+        let is_expanded = expandable && expanded.contains(&key);
+        let children = if is_expanded { nodes.get(&key) } else { None };
+        rows.push_back(TreeRow {
+            item: item.clone(),
+            key: key.clone(),
+            parent: parent.cloned(),
+            depth,
+            expandable,
+            expanded: is_expanded,
+            loading: is_expanded && !matches!(children, Some(Children::Loaded(_))),
+        });
+        match children {
+            Some(Children::Loaded(children)) => flatten(
+                children.clone(),
+                Some(&key),
+                depth + 1,
+                expanded,
+                nodes,
+                rows,
+                to_load,
+            ),
+            None if is_expanded => to_load.push(item),
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum TreeAction<K> {
+    Select(K),
+    Expand(K),
+    Collapse(K),
+}
+
+/// What `key` does in the tree, with the item `selected` selected.
+fn key_action<T: TreeItem>(
+    rows: &im::Vector<TreeRow<T>>,
+    selected: Option<&T::Key>,
+    key: &Key,
+) -> Option<TreeAction<T::Key>> {
+    let last = rows.len().checked_sub(1)?;
+    let index = selected.and_then(|selected| rows.iter().position(|row| row.key == *selected));
+    let select = |index: usize| Some(TreeAction::Select(rows[index].key.clone()));
+    match (key, index) {
+        (Key::Home, _) | (Key::ArrowDown, None) => select(0),
+        (Key::End, _) | (Key::ArrowUp, None) => select(last),
+        (Key::ArrowDown, Some(index)) => select((index + 1).min(last)),
+        (Key::ArrowUp, Some(index)) => select(index.saturating_sub(1)),
+        (Key::ArrowRight, Some(index)) => {
+            let row = &rows[index];
+            if row.expandable && !row.expanded {
+                Some(TreeAction::Expand(row.key.clone()))
+            } else if rows
+                .get(index + 1)
+                .map_or(false, |next| next.depth > row.depth)
+            {
+                // the first child
+                select(index + 1)
+            } else {
+                None
+            }
+        }
+        (Key::ArrowLeft, Some(index)) => {
+            let row = &rows[index];
+            if row.expanded {
+                Some(TreeAction::Collapse(row.key.clone()))
+            } else {
+                row.parent.clone().map(TreeAction::Select)
+            }
+        }
+        (Key::Enter, Some(index)) if rows[index].expandable => {
+            let row = &rows[index];
+            Some(if row.expanded {
+                TreeAction::Collapse(row.key.clone())
+            } else {
+                TreeAction::Expand(row.key.clone())
+            })
+        }
+        _ => None,
+    }
+}
+
+/// A tree of the items of `roots` and their children, shown with `view_fn`, with a chevron
+/// expanding and collapsing the items having children. The children of an item are loaded with
+/// [TreeItem::load_children] the first time it's expanded.
+///
+/// Clicking an item selects it. While the tree is focused, the up and down arrows, Home and End
+/// move the selection, the right arrow expands the selected item or goes to its first child, the
+/// left arrow collapses it or goes to its parent, and Enter expands or collapses it.
+///
+/// Only the rows in view are made, so the tree can be large. [TreeState::reveal] shows and
+/// scrolls to any item.
+pub fn tree_view<T, V>(
+    roots: impl Fn() -> Vec<T> + 'static,
+    view_fn: impl Fn(&T) -> V + 'static,
+    state: TreeState<T::Key>,
+) -> impl View
+where
+    T: TreeItem,
+    V: View + 'static,
+{
+    let cx = ViewContext::get_current();
+    let nodes = create_rw_signal(cx.scope, HashMap::<T::Key, Children<T>>::new());
+    let rows = create_rw_signal(cx.scope, im::Vector::<TreeRow<T>>::new());
+    let view_fn = Rc::new(view_fn);
+
+    let load = move |item: T| {
+        let key = item.key();
+        nodes.update(|nodes| {
+            nodes.insert(key.clone(), Children::Loading);
+        });
+        let send = create_ext_action(cx.scope, move |children: Vec<T>| {
+            nodes.update(|nodes| {
+                nodes.insert(key.clone(), Children::Loaded(children));
+            });
+        });
+        item.load_children(TreeChildren(Box::new(send)));
+    };
+
+    create_effect(cx.scope, move |_| {
+        let mut flat = im::Vector::new();
+        let mut to_load = Vec::new();
+        state.expanded.with(|expanded| {
+            nodes.with(|nodes| flatten(roots(), None, 0, expanded, nodes, &mut flat, &mut to_load))
+        });
+        rows.set(flat);
+        for item in to_load {
+            load(item);
+        }
+    });
+
+    let toggle = move |key: &T::Key| {
+        if state.is_expanded(key) {
+            state.collapse(key);
+        } else {
+            state.expand(key.clone());
+        }
+    };
+
+    let row_view = move |row: TreeRow<T>| {
+        let view_fn = view_fn.clone();
+        let TreeRow {
+            item,
+            key,
+            depth,
+            expandable,
+            expanded,
+            loading,
+            ..
+        } = row;
+        let is_selected = {
+            let key = key.clone();
+            move || {
+                state
+                    .selected
+                    .with(|selected| selected.as_ref() == Some(&key))
+            }
+        };
+        let chevron_key = key.clone();
+        stack(move || {
+            (
+                // an indentation guide for each level
+                list(
+                    move || 0..depth,
+                    |level| *level,
+                    |_| {
+                        empty().style(|| {
+                            Style::BASE
+                                .width_px(1.0)
+                                .height_pct(100.0)
+                                .margin_left_px(INDENT / 2.0 - 0.5)
+                                .margin_right_px(INDENT / 2.0 - 0.5)
+                                .background(GUIDE_COLOR)
+                        })
+                    },
+                )
+                .style(|| Style::BASE.height_pct(100.0)),
+                label(move || {
+                    match (expandable, expanded, loading) {
+                        (true, true, true) => "…",
+                        (true, true, false) => "▾",
+                        (true, false, _) => "▸",
+                        (false, _, _) => "",
+                    }
+                    .to_string()
+                })
+                .style(|| Style::BASE.width_px(INDENT).justify_center())
+                .on_click(move |_| {
+                    if expandable {
+                        toggle(&chevron_key);
+                    }
+                    state.selected.set(Some(chevron_key.clone()));
+                    true
+                }),
+                view_fn(&item),
+            )
+        })
+        .style(move || {
+            Style::BASE
+                .height_px(ROW_HEIGHT as f32)
+                .min_width_pct(100.0)
+                .padding_right_px(8.0)
+                .items_center()
+                .apply_if(is_selected(), |s| s.background(SELECTED_COLOR))
+        })
+        .on_click(move |_| {
+            state.selected.set(Some(key.clone()));
+            true
+        })
+    };
+
+    let on_key = move |event: &Event| {
+        let Event::KeyDown(key_event) = event else {
+            return false;
+        };
+        let action = rows.with(|rows| {
+            state
+                .selected
+                .with(|selected| key_action(rows, selected.as_ref(), &key_event.key))
+        });
+        match action {
+            Some(TreeAction::Select(key)) => {
+                state.selected.set(Some(key.clone()));
+                state.scroll_to.set(Some(key));
+            }
+            Some(TreeAction::Expand(key)) => state.expand(key),
+            Some(TreeAction::Collapse(key)) => state.collapse(&key),
+            None => return false,
+        }
+        true
+    };
+
+    let view = scroll(move || {
+        virtual_list(
+            VirtualListDirection::Vertical,
+            VirtualListItemSize::Fixed(Box::new(|| ROW_HEIGHT)),
+            move || rows.get(),
+            |row| (row.key.clone(), row.expanded, row.loading),
+            row_view,
+        )
+        .style(|| Style::BASE.flex_col().min_width_pct(100.0))
+    })
+    .keyboard_navigatable()
+    .on_event(EventListener::KeyDown, on_key);
+
+    let scroll_id = view.id();
+    create_effect(cx.scope, move |_| {
+        // waits for the item to be shown, which can take loading its ancestors' children
+        let Some(key) = state.scroll_to.get() else {
+            return;
+        };
+        let index = rows.with(|rows| rows.iter().position(|row| row.key == key));
+        if let Some(index) = index {
+            let y0 = index as f64 * ROW_HEIGHT;
+            ensure_visible(scroll_id, Rect::new(0.0, y0, 1.0, y0 + ROW_HEIGHT));
+            state.scroll_to.set(None);
+        }
+    });
+
+    view
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use glazier::keyboard_types::Key;
+
+    use super::{flatten, key_action, Children, TreeAction, TreeChildren, TreeItem, TreeRow};
+
+    #[derive(Clone)]
+    struct Node(&'static str, bool);
+
+    impl TreeItem for Node {
+        type Key = &'static str;
+
+        fn key(&self) -> &'static str {
+            self.0
+        }
+
+        fn has_children(&self) -> bool {
+            self.1
+        }
+
+        fn load_children(&self, _children: TreeChildren<Self>) {}
+    }
+
+    fn rows(expanded: &[&'static str]) -> (im::Vector<TreeRow<Node>>, Vec<&'static str>) {
+        let expanded = expanded.iter().copied().collect::<HashSet<_>>();
+        let nodes = HashMap::from([
+            (
+                "src",
+                Children::Loaded(vec![Node("lib.rs", false), Node("views", true)]),
+            ),
+            ("views", Children::Loading),
+        ]);
+        let roots = vec![
+            Node("src", true),
+            Node("docs", true),
+            Node("Cargo.toml", false),
+        ];
+        let mut rows = im::Vector::new();
+        let mut to_load = Vec::new();
+        flatten(roots, None, 0, &expanded, &nodes, &mut rows, &mut to_load);
+        (rows, to_load.iter().map(|node| node.0).collect())
+    }
+
+    #[test]
+    fn flattens_expanded_items() {
+        let (flat, to_load) = rows(&["src", "views", "docs"]);
+        let keys = flat
+            .iter()
+            .map(|row| (row.key, row.depth))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                ("src", 0),
+                ("lib.rs", 1),
+                ("views", 1),
+                ("docs", 0),
+                ("Cargo.toml", 0)
+            ]
+        );
+        assert!(flat[2].loading && flat[3].loading && !flat[0].loading);
+        assert_eq!(flat[1].parent, Some("src"));
+        assert_eq!(to_load, vec!["docs"]);
+    }
+
+    #[test]
+    fn arrows_expand_and_walk_the_tree() {
+        let (flat, _) = rows(&["src"]);
+        let action = |selected, key| key_action(&flat, Some(&selected), &key);
+        assert_eq!(
+            action("src", Key::ArrowRight),
+            Some(TreeAction::Select("lib.rs"))
+        );
+        assert_eq!(
+            action("views", Key::ArrowRight),
+            Some(TreeAction::Expand("views"))
+        );
+        assert_eq!(
+            action("views", Key::ArrowLeft),
+            Some(TreeAction::Select("src"))
+        );
+        assert_eq!(
+            action("src", Key::ArrowLeft),
+            Some(TreeAction::Collapse("src"))
+        );
+        assert_eq!(action("lib.rs", Key::ArrowRight), None);
+        assert_eq!(
+            key_action(&flat, None, &Key::End),
+            Some(TreeAction::Select("Cargo.toml"))
+        );
+    }
+}
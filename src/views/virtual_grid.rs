@@ -0,0 +1,447 @@
+use std::{hash::Hash, marker::PhantomData, ops::Range};
+
+use glazier::kurbo::{Rect, Size};
+use leptos_reactive::{
+    create_effect, create_signal, ScopeDisposer, SignalGet, SignalSet, WriteSignal,
+};
+use smallvec::SmallVec;
+use taffy::{
+    prelude::Node,
+    style::{Dimension, LengthPercentageAuto, Position},
+};
+
+use crate::{
+    app_handle::ViewContext,
+    context::{EventCx, LayoutCx, PaintCx, UpdateCx},
+    event::Event,
+    id::Id,
+    view::{ChangeFlags, View},
+};
+
+use super::{apply_diff, diff, Diff, DiffOpAdd, FxIndexSet, HashRun, VirtualListVector};
+
+/// The size of the rows or the columns of a [virtual_grid].
+pub enum VirtualGridSize {
+    /// The same size for every row or column
+    Fixed(Box<dyn Fn() -> f64>),
+    /// The size of the row or column at an index
+    Fn(Box<dyn Fn(usize) -> f64>),
+}
+
+/// How many columns a [virtual_grid] has.
+pub enum VirtualGridColumns {
+    /// `count` columns sized by `width`
+    Fixed {
+        count: usize,
+        width: VirtualGridSize,
+    },
+    /// As many columns of at least `min_width` as fit in the width of the grid, sharing it
+    /// evenly. The items flow into more or fewer columns as the grid is resized.
+    Fit { min_width: f64 },
+}
+
+/// The offsets of the rows or the columns of a grid.
+enum Tracks {
+    Fixed {
+        size: f64,
+        count: usize,
+    },
+    /// The offset of each track, then the offset of the end
+    Sized(Vec<f64>),
+}
+
+impl Tracks {
+    fn new(size: &VirtualGridSize, count: usize) -> Self {
+        match size {
+            VirtualGridSize::Fixed(size) => Tracks::Fixed {
+                size: size(),
+                count,
+            },
+            VirtualGridSize::Fn(size) => {
+                let mut offsets = Vec::with_capacity(count + 1);
+                let mut offset = 0.0;
+                offsets.push(offset);
+                for index in 0..count {
+                    offset += size(index);
+                    offsets.push(offset);
+                }
+                Tracks::Sized(offsets)
+            }
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Tracks::Fixed { count, .. } => *count,
+            Tracks::Sized(offsets) => offsets.len() - 1,
+        }
+    }
+
+    /// The offset of the track `index`, or of the end with the number of tracks.
+    fn offset(&self, index: usize) -> f64 {
+        match self {
+            Tracks::Fixed { size, .. } => *size * index as f64,
+            Tracks::Sized(offsets) => offsets[index],
+        }
+    }
+
+    fn total(&self) -> f64 {
+        self.offset(self.len())
+    }
+
+    /// The tracks overlapping `min..max`.
+    fn visible(&self, min: f64, max: f64) -> Range<usize> {
+        let len = self.len();
+        match self {
+            Tracks::Fixed { size, .. } if *size > 0.0 => {
+                let start = ((min / size).floor().max(0.0) as usize).min(len);
+                let end = ((max / size).ceil().max(0.0) as usize).min(len);
+                start..end.max(start)
+            }
+            Tracks::Fixed { .. } => 0..len,
+            Tracks::Sized(offsets) => {
+                let start = offsets
+                    .partition_point(|offset| *offset <= min)
+                    .saturating_sub(1)
+                    .min(len);
+                let end = offsets.partition_point(|offset| *offset < max).min(len);
+                start..end.max(start)
+            }
+        }
+    }
+}
+
+/// The number of columns of at least `min_width` fitting in `width`, at least one.
+fn fit_columns(width: f64, min_width: f64) -> usize {
+    if min_width > 0.0 {
+        ((width / min_width).floor() as usize).max(1)
+    } else {
+        1
+    }
+}
+
+pub struct VirtualGrid<V: View, VF, T>
+where
+    VF: Fn(T) -> V + 'static,
+    T: 'static,
+{
+    id: Id,
+    children: Vec<Option<(V, ScopeDisposer)>>,
+    /// Where each child is in the grid
+    rects: Vec<Rect>,
+    /// The size of the whole grid
+    size: Size,
+    viewport: Rect,
+    set_viewport: WriteSignal<Rect>,
+    width: f64,
+    set_width: WriteSignal<f64>,
+    view_fn: VF,
+    phantom: PhantomData<T>,
+    cx: ViewContext,
+    /// Takes the size of the whole grid, for the cells to be scrolled through
+    size_node: Option<Node>,
+}
+
+struct VirtualGridState<T> {
+    diff: Diff<T>,
+    rects: Vec<Rect>,
+    size: Size,
+}
+
+/// A grid of the items of `each_fn`, in rows from left to right, of which only the ones
+/// intersecting the viewport are made with `view_fn`. Like [list](super::list), the views are
+/// kept for the items whose key stays the same.
+///
+/// With [VirtualGridColumns::Fit], the grid takes its width from its style, so it should be
+/// given one, like a width of 100%.
+pub fn virtual_grid<T, IF, I, KF, K, VF, V>(
+    columns: VirtualGridColumns,
+    row_height: VirtualGridSize,
+    each_fn: IF,
+    key_fn: KF,
+    view_fn: VF,
+) -> VirtualGrid<V, VF, T>
+where
+    T: 'static,
+    IF: Fn() -> I + 'static,
+    I: VirtualListVector<T>,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
+    VF: Fn(T) -> V + 'static,
+    V: View + 'static,
+{
+    let cx = ViewContext::get_current();
+    let id = cx.new_id();
+
+    let mut child_cx = cx;
+    child_cx.id = id;
+
+    let (viewport, set_viewport) = create_signal(cx.scope, Rect::ZERO);
+    let (width, set_width) = create_signal(cx.scope, 0.0);
+
+    create_effect(cx.scope, move |prev_hash_run| {
+        let mut items_vector = each_fn();
+        let viewport = viewport.get();
+        let total_len = items_vector.total_len();
+
+        let (column_count, column_tracks, fills_width) = match &columns {
+            VirtualGridColumns::Fixed { count, width } => {
+                let count = (*count).max(1);
+                (count, Tracks::new(width, count), false)
+            }
+            VirtualGridColumns::Fit { min_width } => {
+                let width = width.get();
+                let count = fit_columns(width, *min_width);
+                let size = width / count as f64;
+                (count, Tracks::Fixed { size, count }, true)
+            }
+        };
+        let row_count = (total_len + column_count - 1) / column_count;
+        let row_tracks = Tracks::new(&row_height, row_count);
+
+        let visible_columns = column_tracks.visible(viewport.x0, viewport.x1);
+        let mut items = Vec::new();
+        let mut rects = Vec::new();
+        for row in row_tracks.visible(viewport.y0, viewport.y1) {
+            let start = row * column_count;
+            let range = (start + visible_columns.start).min(total_len)
+                ..(start + visible_columns.end).min(total_len);
+            for (column, item) in (visible_columns.start..).zip(items_vector.slice(range)) {
+                rects.push(Rect::new(
+                    column_tracks.offset(column),
+                    row_tracks.offset(row),
+                    column_tracks.offset(column + 1),
+                    row_tracks.offset(row + 1),
+                ));
+                items.push(item);
+            }
+        }
+        // a grid filling its width doesn't push it wider, so that it can shrink too
+        let size = Size::new(
+            if fills_width {
+                0.0
+            } else {
+                column_tracks.total()
+            },
+            row_tracks.total(),
+        );
+
+        let hashed_items = items.iter().map(&key_fn).collect::<FxIndexSet<_>>();
+        let diff = if let Some(HashRun(prev_hash_run)) = prev_hash_run {
+            let mut diff = diff(&prev_hash_run, &hashed_items);
+            let mut items = items
+                .into_iter()
+                .map(Some)
+                .collect::<SmallVec<[Option<_>; 128]>>();
+            for added in &mut diff.added {
+                added.view = Some(items[added.at].take().unwrap());
+            }
+            diff
+        } else {
+            let mut diff = Diff::default();
+            for (i, item) in items.into_iter().enumerate() {
+                diff.added.push(DiffOpAdd {
+                    at: i,
+                    view: Some(item),
+                });
+            }
+            diff
+        };
+        id.update_state(VirtualGridState { diff, rects, size }, false);
+        HashRun(hashed_items)
+    });
+
+    VirtualGrid {
+        id,
+        children: Vec::new(),
+        rects: Vec::new(),
+        size: Size::ZERO,
+        viewport: Rect::ZERO,
+        set_viewport,
+        width: 0.0,
+        set_width,
+        view_fn,
+        phantom: PhantomData,
+        cx: child_cx,
+        size_node: None,
+    }
+}
+
+impl<V: View + 'static, VF, T> View for VirtualGrid<V, VF, T>
+where
+    VF: Fn(T) -> V + 'static,
+{
+    fn id(&self) -> Id {
+        self.id
+    }
+
+    fn child(&self, id: Id) -> Option<&dyn View> {
+        self.children
+            .iter()
+            .filter_map(|child| child.as_ref())
+            .find(|(view, _)| view.id() == id)
+            .map(|(view, _)| view as &dyn View)
+    }
+
+    fn child_mut(&mut self, id: Id) -> Option<&mut dyn View> {
+        self.children
+            .iter_mut()
+            .filter_map(|child| child.as_mut())
+            .find(|(view, _)| view.id() == id)
+            .map(|(view, _)| view as &mut dyn View)
+    }
+
+    fn children(&self) -> Vec<&dyn View> {
+        self.children
+            .iter()
+            .filter_map(|child| child.as_ref())
+            .map(|child| &child.0 as &dyn View)
+            .collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut dyn View> {
+        self.children
+            .iter_mut()
+            .filter_map(|child| child.as_mut())
+            .map(|child| &mut child.0 as &mut dyn View)
+            .collect()
+    }
+
+    fn debug_name(&self) -> std::borrow::Cow<'static, str> {
+        "VirtualGrid".into()
+    }
+
+    fn update(&mut self, cx: &mut UpdateCx, state: Box<dyn std::any::Any>) -> ChangeFlags {
+        if let Ok(state) = state.downcast::<VirtualGridState<T>>() {
+            // the cells move when the columns reflow, even if they stay the same
+            if self.rects == state.rects && self.size == state.size && state.diff.is_empty() {
+                return ChangeFlags::empty();
+            }
+            self.rects = state.rects;
+            self.size = state.size;
+            ViewContext::save();
+            ViewContext::set_current(self.cx);
+            apply_diff(cx.app_state, state.diff, &mut self.children, &self.view_fn);
+            ViewContext::restore();
+            cx.request_layout(self.id());
+            ChangeFlags::LAYOUT
+        } else {
+            ChangeFlags::empty()
+        }
+    }
+
+    fn layout(&mut self, cx: &mut LayoutCx) -> Node {
+        cx.layout_node(self.id, true, |cx| {
+            let mut nodes = Vec::with_capacity(self.children.len() + 1);
+            for (child, rect) in self.children.iter_mut().zip(&self.rects) {
+                let Some((child, _)) = child.as_mut() else {
+                    continue;
+                };
+                let node = child.layout_main(cx);
+                let taffy = &mut cx.app_state_mut().taffy;
+                if let Ok(style) = taffy.style(node) {
+                    let mut style = style.clone();
+                    style.position = Position::Absolute;
+                    style.inset.left = LengthPercentageAuto::Points(rect.x0 as f32);
+                    style.inset.top = LengthPercentageAuto::Points(rect.y0 as f32);
+                    style.size.width = Dimension::Points(rect.width() as f32);
+                    style.size.height = Dimension::Points(rect.height() as f32);
+                    let _ = taffy.set_style(node, style);
+                }
+                nodes.push(node);
+            }
+
+            let size_node = *self.size_node.get_or_insert_with(|| {
+                cx.app_state_mut()
+                    .taffy
+                    .new_leaf(taffy::style::Style::DEFAULT)
+                    .unwrap()
+            });
+            let _ = cx.app_state_mut().taffy.set_style(
+                size_node,
+                taffy::style::Style {
+                    size: taffy::prelude::Size {
+                        width: Dimension::Points(self.size.width as f32),
+                        height: Dimension::Points(self.size.height as f32),
+                    },
+                    flex_shrink: 0.0,
+                    ..Default::default()
+                },
+            );
+            nodes.push(size_node);
+            nodes
+        })
+    }
+
+    fn compute_layout(&mut self, cx: &mut LayoutCx) -> Option<Rect> {
+        let viewport = cx.viewport.unwrap_or_default();
+        if self.viewport != viewport {
+            self.viewport = viewport;
+            self.set_viewport.set(viewport);
+        }
+        let width = cx
+            .app_state()
+            .get_layout(self.id)
+            .map(|layout| layout.size.width as f64)
+            .unwrap_or_default();
+        if self.width != width {
+            self.width = width;
+            self.set_width.set(width);
+        }
+
+        let mut layout_rect = Rect::ZERO;
+        for child in &mut self.children {
+            if let Some((child, _)) = child.as_mut() {
+                layout_rect = layout_rect.union(child.compute_layout_main(cx));
+            }
+        }
+        Some(layout_rect)
+    }
+
+    fn event(&mut self, cx: &mut EventCx, id_path: Option<&[Id]>, event: Event) -> bool {
+        for child in self.children.iter_mut() {
+            if let Some((child, _)) = child.as_mut() {
+                let id = child.id();
+                if cx.should_send(id, &event) && child.event_main(cx, id_path, event.clone()) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    fn paint(&mut self, cx: &mut PaintCx) {
+        for child in self.children.iter_mut() {
+            if let Some((child, _)) = child.as_mut() {
+                child.paint_main(cx);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fit_columns, Tracks, VirtualGridSize};
+
+    #[test]
+    fn visible_tracks() {
+        let fixed = Tracks::new(&VirtualGridSize::Fixed(Box::new(|| 10.0)), 100);
+        assert_eq!(fixed.visible(15.0, 35.0), 1..4);
+        assert_eq!(fixed.visible(0.0, 0.0), 0..0);
+        assert_eq!(fixed.visible(990.0, 2000.0), 99..100);
+
+        let sized = Tracks::new(&VirtualGridSize::Fn(Box::new(|i| (i + 1) as f64 * 10.0)), 4);
+        // the tracks are at 0, 10, 30 and 60, ending at 100
+        assert_eq!(sized.total(), 100.0);
+        assert_eq!(sized.visible(15.0, 30.0), 1..2);
+        assert_eq!(sized.visible(15.0, 31.0), 1..3);
+        assert_eq!(sized.visible(70.0, 200.0), 3..4);
+    }
+
+    #[test]
+    fn columns_fit_the_width() {
+        assert_eq!(fit_columns(500.0, 120.0), 4);
+        assert_eq!(fit_columns(50.0, 120.0), 1);
+        assert_eq!(fit_columns(500.0, 0.0), 1);
+    }
+}
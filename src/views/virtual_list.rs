@@ -1,9 +1,16 @@
-use std::{hash::Hash, marker::PhantomData, ops::Range};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Range,
+};
 
-use glazier::kurbo::{Rect, Size};
+use glazier::kurbo::{Rect, Size, Vec2};
 use leptos_reactive::{
     create_effect, create_signal, ScopeDisposer, SignalGet, SignalSet, WriteSignal,
 };
+use rustc_hash::FxHasher;
 use smallvec::SmallVec;
 use taffy::{prelude::Node, style::Dimension};
 
@@ -14,7 +21,10 @@ use crate::{
     view::{ChangeFlags, View},
 };
 
-use super::{apply_diff, diff, Diff, DiffOpAdd, FxIndexSet, HashRun};
+use super::{
+    apply_diff, diff, enclosing_scroll, paint_children, reorder_items, scroll_by, Diff, DiffOpAdd,
+    FxIndexSet, HashRun, Reorder, ReorderTick,
+};
 
 #[derive(Clone, Copy)]
 pub enum VirtualListDirection {
@@ -25,6 +35,72 @@ pub enum VirtualListDirection {
 pub enum VirtualListItemSize<T> {
     Fn(Box<dyn Fn(&T) -> f64>),
     Fixed(Box<dyn Fn() -> f64>),
+    /// The items are measured once they're laid out, like items with wrapped text, and this
+    /// estimate is used until then. The measured sizes are kept while the items' keys stay.
+    Measured(Box<dyn Fn() -> f64>),
+}
+
+/// Where [VirtualList::scroll_to_index] puts the item in the viewport.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VirtualListAlign {
+    Start,
+    Center,
+    End,
+    /// Scrolls as little as possible for the item to be visible
+    Nearest,
+}
+
+/// How far to scroll for the item at `item` to be aligned with `align` in `view`, both ranges
+/// along the list.
+fn align_delta(item: Range<f64>, view: Range<f64>, align: VirtualListAlign) -> f64 {
+    let item_size = item.end - item.start;
+    let view_size = view.end - view.start;
+    match align {
+        VirtualListAlign::Start => item.start - view.start,
+        VirtualListAlign::Center => item.start + item_size / 2.0 - (view.start + view_size / 2.0),
+        VirtualListAlign::End => item.end - view.end,
+        VirtualListAlign::Nearest => {
+            if item.start < view.start || item_size > view_size {
+                item.start - view.start
+            } else if item.end > view.end {
+                item.end - view.end
+            } else {
+                0.0
+            }
+        }
+    }
+}
+
+/// Updates the `measure`d sizes of the children with the start and size they were `laid_out`
+/// with along the list, returning the sizes that changed by key hash, and how far to scroll.
+/// An item measured to another size than the one it was given moves the items after it, which
+/// stay in place if it starts above the viewport.
+fn correct_measured(
+    measure: &mut [(u64, f64)],
+    laid_out: &[Option<(f64, f64)>],
+    view_start: f64,
+) -> (Vec<(u64, f64)>, f64) {
+    let mut changed = Vec::new();
+    let mut delta = 0.0;
+    for ((hash, size), laid_out) in measure.iter_mut().zip(laid_out) {
+        let Some((start, measured)) = *laid_out else {
+            continue;
+        };
+        if (measured - *size).abs() > 0.5 {
+            if start < view_start {
+                delta += measured - *size;
+            }
+            changed.push((*hash, measured));
+            *size = measured;
+        }
+    }
+    (changed, delta)
+}
+
+fn key_hash<K: Hash>(key: &K) -> u64 {
+    let mut hasher = FxHasher::default();
+    key.hash(&mut hasher);
+    hasher.finish()
 }
 
 pub trait VirtualListVector<T> {
@@ -60,12 +136,26 @@ where
     after_size: f64,
     before_node: Option<Node>,
     after_node: Option<Node>,
+    /// The index of the first child in the whole list
+    first: usize,
+    total_len: usize,
+    reorder: Option<Reorder>,
+    /// The key hash and the size used for each child, when they're measured
+    measure: Vec<(u64, f64)>,
+    set_measured: WriteSignal<Vec<(u64, f64)>>,
+    set_scroll_target: WriteSignal<Option<(usize, VirtualListAlign)>>,
+    /// Where to scroll to after the next layout, along the list
+    scroll_to: Option<(Range<f64>, VirtualListAlign)>,
 }
 
 struct VirtualListState<T> {
     diff: Diff<T>,
     before_size: f64,
     after_size: f64,
+    first: usize,
+    total_len: usize,
+    measure: Vec<(u64, f64)>,
+    scroll_to: Option<(Range<f64>, VirtualListAlign)>,
 }
 
 pub fn virtual_list<T, IF, I, KF, K, VF, V>(
@@ -91,6 +181,11 @@ where
     child_cx.id = id;
 
     let (viewport, set_viewport) = create_signal(cx.scope, Rect::ZERO);
+    let (measured, set_measured) = create_signal(cx.scope, Vec::<(u64, f64)>::new());
+    let (scroll_target, set_scroll_target) =
+        create_signal(cx.scope, None::<(usize, VirtualListAlign)>);
+    // the measured sizes of the items, by the hash of their key
+    let measured_sizes = RefCell::new(HashMap::<u64, f64>::new());
 
     create_effect(cx.scope, move |prev_hash_run| {
         let mut items_vector = each_fn();
@@ -103,14 +198,56 @@ where
             VirtualListDirection::Vertical => viewport.height() + viewport.y0,
             VirtualListDirection::Horizontal => viewport.width() + viewport.x0,
         };
+        let is_measured = matches!(item_size, VirtualListItemSize::Measured(_));
+        if is_measured {
+            measured_sizes.borrow_mut().extend(measured.get());
+        }
+        let sizes = measured_sizes.borrow();
+        let size_of = |item: &T| match &item_size {
+            VirtualListItemSize::Fn(size_fn) => size_fn(item),
+            VirtualListItemSize::Fixed(size) => size(),
+            VirtualListItemSize::Measured(estimate) => sizes
+                .get(&key_hash(&key_fn(item)))
+                .copied()
+                .unwrap_or_else(estimate),
+        };
         let mut items = Vec::new();
+        let mut measure = Vec::new();
+        // the key hashes of all the items, when they're measured
+        let mut keys = HashSet::new();
 
         let mut before_size = 0.0;
         let mut after_size = 0.0;
+        let mut first = 0;
+        let total_len = items_vector.total_len();
+
+        let scroll_to = scroll_target
+            .get()
+            .filter(|_| total_len > 0)
+            .map(|(index, align)| {
+                let index = index.min(total_len - 1);
+                let (start, size) = match &item_size {
+                    VirtualListItemSize::Fixed(size) => {
+                        let size = size();
+                        (size * index as f64, size)
+                    }
+                    _ => {
+                        // slicing takes the items out of the vector, which is still needed for
+                        // the items in view
+                        let mut sizes = each_fn()
+                            .slice(0..index + 1)
+                            .map(|item| size_of(&item))
+                            .collect::<Vec<_>>();
+                        let size = sizes.pop().unwrap_or_default();
+                        (sizes.iter().sum(), size)
+                    }
+                };
+                (start..start + size, align)
+            });
+
         match &item_size {
             VirtualListItemSize::Fixed(item_size) => {
                 let item_size = item_size();
-                let total_len = items_vector.total_len();
                 let start = if item_size > 0.0 {
                     (min / item_size).floor() as usize
                 } else {
@@ -122,6 +259,7 @@ where
                     usize::MAX
                 };
                 before_size = item_size * start as f64;
+                first = start;
 
                 for item in items_vector.slice(start..end) {
                     items.push(item);
@@ -129,20 +267,30 @@ where
 
                 after_size = item_size * (total_len.saturating_sub(end)) as f64;
             }
-            VirtualListItemSize::Fn(size_fn) => {
+            VirtualListItemSize::Fn(_) | VirtualListItemSize::Measured(_) => {
                 let mut main_axis = 0.0;
-                let total_len = items_vector.total_len();
-                let total_size = items_vector.total_size();
+                let total_size = if is_measured {
+                    None
+                } else {
+                    items_vector.total_size()
+                };
                 for item in items_vector.slice(0..total_len) {
-                    let item_size = size_fn(&item);
+                    if is_measured {
+                        keys.insert(key_hash(&key_fn(&item)));
+                    }
+                    let item_size = size_of(&item);
                     if main_axis + item_size < min {
                         main_axis += item_size;
                         before_size += item_size;
+                        first += 1;
                         continue;
                     }
 
                     if main_axis <= max {
                         main_axis += item_size;
+                        if is_measured {
+                            measure.push((key_hash(&key_fn(&item)), item_size));
+                        }
                         items.push(item);
                     } else {
                         if let Some(total_size) = total_size {
@@ -154,6 +302,13 @@ where
                 }
             }
         };
+        drop(sizes);
+        if is_measured {
+            // the sizes of the items that are gone aren't needed anymore
+            measured_sizes
+                .borrow_mut()
+                .retain(|hash, _| keys.contains(hash));
+        }
 
         let hashed_items = items.iter().map(&key_fn).collect::<FxIndexSet<_>>();
         let diff = if let Some(HashRun(prev_hash_run)) = prev_hash_run {
@@ -176,14 +331,22 @@ where
             }
             diff
         };
+        let scrolls = scroll_to.is_some();
         id.update_state(
             VirtualListState {
                 diff,
                 before_size,
                 after_size,
+                first,
+                total_len,
+                measure,
+                scroll_to,
             },
             false,
         );
+        if scrolls {
+            set_scroll_target.set(None);
+        }
         HashRun(hashed_items)
     });
 
@@ -200,6 +363,47 @@ where
         after_size: 0.0,
         before_node: None,
         after_node: None,
+        first: 0,
+        total_len: 0,
+        reorder: None,
+        measure: Vec::new(),
+        set_measured,
+        set_scroll_target,
+        scroll_to: None,
+    }
+}
+
+impl<V: View, VF, T> VirtualList<V, VF, T>
+where
+    VF: Fn(T) -> V + 'static,
+{
+    /// Lets the items be reordered by dragging them, or with the keyboard, like
+    /// [List::reorderable](super::List::reorderable). The indexes are in the whole list,
+    /// not only the items that are shown.
+    pub fn reorderable(mut self, on_reorder: impl Fn(usize, usize) + 'static) -> Self {
+        self.reorder = Some(Reorder::new(on_reorder));
+        self
+    }
+
+    /// Scrolls the scroll view the list is in to the item at the index given by `index`, in the
+    /// whole list, whenever it changes to some index.
+    pub fn scroll_to_index(
+        self,
+        index: impl Fn() -> Option<usize> + 'static,
+        align: VirtualListAlign,
+    ) -> Self {
+        let cx = ViewContext::get_current();
+        let set_scroll_target = self.set_scroll_target;
+        create_effect(cx.scope, move |_| {
+            if let Some(index) = index() {
+                set_scroll_target.set(Some((index, align)));
+            }
+        });
+        self
+    }
+
+    fn is_vertical(&self) -> bool {
+        matches!(self.direction, VirtualListDirection::Vertical)
     }
 }
 
@@ -260,12 +464,35 @@ where
         cx: &mut crate::context::UpdateCx,
         state: Box<dyn std::any::Any>,
     ) -> crate::view::ChangeFlags {
+        if state.is::<ReorderTick>() {
+            let vertical = self.is_vertical();
+            if let Some(reorder) = self.reorder.as_mut() {
+                reorder.autoscroll(cx.app_state, self.id, vertical);
+                let items = reorder_items(cx.app_state, vertical, self.first, &self.children);
+                reorder.update_slot(&items, self.first, self.total_len);
+            }
+            self.id.request_paint();
+            return ChangeFlags::empty();
+        }
         if let Ok(state) = state.downcast::<VirtualListState<T>>() {
+            self.first = state.first;
+            self.total_len = state.total_len;
+            self.measure = state.measure;
+            let scrolls = state.scroll_to.is_some();
+            if scrolls {
+                // scrolls once the list has its new size
+                self.scroll_to = state.scroll_to;
+                cx.request_layout(self.id());
+            }
             if self.before_size == state.before_size
                 && self.after_size == state.after_size
                 && state.diff.is_empty()
             {
-                return ChangeFlags::empty();
+                return if scrolls {
+                    ChangeFlags::LAYOUT
+                } else {
+                    ChangeFlags::empty()
+                };
             }
             self.before_size = state.before_size;
             self.after_size = state.after_size;
@@ -355,6 +582,47 @@ where
             self.set_viewport.set(viewport);
         }
 
+        let vertical = self.is_vertical();
+        let view = if vertical {
+            viewport.y0..viewport.y1
+        } else {
+            viewport.x0..viewport.x1
+        };
+        let mut delta = 0.0;
+        if let Some((item, align)) = self.scroll_to.take() {
+            delta += align_delta(item, view.clone(), align);
+        }
+        let laid_out = self
+            .children
+            .iter()
+            .filter_map(|child| child.as_ref())
+            .map(|(child, _)| {
+                let layout = cx.app_state().get_layout(child.id())?;
+                Some(if vertical {
+                    (layout.location.y as f64, layout.size.height as f64)
+                } else {
+                    (layout.location.x as f64, layout.size.width as f64)
+                })
+            })
+            .collect::<Vec<_>>();
+        let (changed, anchor_delta) = correct_measured(&mut self.measure, &laid_out, view.start);
+        delta += anchor_delta;
+        if !changed.is_empty() {
+            self.set_measured.set(changed);
+        }
+        if delta != 0.0 {
+            if let Some((scroll, ..)) = enclosing_scroll(cx.app_state(), self.id) {
+                scroll_by(
+                    scroll,
+                    if vertical {
+                        Vec2::new(0.0, delta)
+                    } else {
+                        Vec2::new(delta, 0.0)
+                    },
+                );
+            }
+        }
+
         let mut layout_rect = Rect::ZERO;
         for child in &mut self.children {
             if let Some((child, _)) = child.as_mut() {
@@ -370,6 +638,21 @@ where
         id_path: Option<&[Id]>,
         event: crate::event::Event,
     ) -> bool {
+        let vertical = self.is_vertical();
+        if let Some(reorder) = self.reorder.as_mut() {
+            let items = reorder_items(cx.app_state, vertical, self.first, &self.children);
+            if reorder.event(
+                cx,
+                self.id,
+                vertical,
+                self.first,
+                self.total_len,
+                &items,
+                &event,
+            ) {
+                return true;
+            }
+        }
         for child in self.children.iter_mut() {
             if let Some((child, _)) = child.as_mut() {
                 let id = child.id();
@@ -382,11 +665,15 @@ where
     }
 
     fn paint(&mut self, cx: &mut crate::context::PaintCx) {
-        for child in &mut self.children {
-            if let Some((child, _)) = child.as_mut() {
-                child.paint_main(cx);
-            }
-        }
+        let vertical = self.is_vertical();
+        paint_children(
+            cx,
+            self.id,
+            self.reorder.as_ref(),
+            vertical,
+            self.first,
+            &mut self.children,
+        );
     }
 }
 
@@ -401,3 +688,57 @@ impl<T: Clone> VirtualListVector<T> for im::Vector<T> {
         self.slice(range).into_iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{align_delta, correct_measured, VirtualListAlign};
+
+    #[test]
+    fn aligns_item_in_view() {
+        let view = 100.0..200.0;
+        assert_eq!(
+            align_delta(300.0..320.0, view.clone(), VirtualListAlign::Start),
+            200.0
+        );
+        assert_eq!(
+            align_delta(300.0..320.0, view.clone(), VirtualListAlign::End),
+            120.0
+        );
+        assert_eq!(
+            align_delta(300.0..320.0, view.clone(), VirtualListAlign::Center),
+            160.0
+        );
+        assert_eq!(
+            align_delta(40.0..60.0, view.clone(), VirtualListAlign::End),
+            -140.0
+        );
+    }
+
+    #[test]
+    fn nearest_scrolls_as_little_as_possible() {
+        let view = 100.0..200.0;
+        let nearest = |item| align_delta(item, view.clone(), VirtualListAlign::Nearest);
+        assert_eq!(nearest(120.0..140.0), 0.0);
+        assert_eq!(nearest(190.0..230.0), 30.0);
+        assert_eq!(nearest(80.0..100.0), -20.0);
+        // an item larger than the view shows its start
+        assert_eq!(nearest(150.0..400.0), 50.0);
+    }
+
+    #[test]
+    fn measured_items_above_the_view_keep_it_in_place() {
+        let mut measure = [(1, 20.0), (2, 20.0), (3, 20.0)];
+        // the first item grew by 30 above the view, the second is unchanged and the third,
+        // in view, shrank by 5
+        let laid_out = [Some((0.0, 50.0)), Some((50.0, 20.0)), Some((110.0, 15.0))];
+        let (changed, delta) = correct_measured(&mut measure, &laid_out, 100.0);
+        assert_eq!(changed, vec![(1, 50.0), (3, 15.0)]);
+        assert_eq!(delta, 30.0);
+        assert_eq!(measure, [(1, 50.0), (2, 20.0), (3, 15.0)]);
+
+        // once corrected, the same layout changes nothing
+        let (changed, delta) = correct_measured(&mut measure, &laid_out, 100.0);
+        assert!(changed.is_empty());
+        assert_eq!(delta, 0.0);
+    }
+}
@@ -1,10 +1,13 @@
 use glazier::kurbo::{Point, Size};
 
+use crate::keymap::Keymap;
+
 #[derive(Default)]
 pub struct WindowConfig {
     pub(crate) size: Option<Size>,
     pub(crate) position: Option<Point>,
     pub(crate) show_titlebar: Option<bool>,
+    pub(crate) keymap: Option<Keymap>,
 }
 
 impl WindowConfig {
@@ -22,4 +25,10 @@ impl WindowConfig {
         self.show_titlebar = Some(show_titlebar);
         self
     }
+
+    /// Key bindings added on top of the built in ones, like Tab for focus navigation.
+    pub fn keymap(mut self, keymap: Keymap) -> Self {
+        self.keymap = Some(keymap);
+        self
+    }
 }